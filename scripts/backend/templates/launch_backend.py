@@ -1,15 +1,20 @@
 from __future__ import annotations
 
+import asyncio
 import atexit
 import ctypes
 import functools
+import hmac
+import ipaddress
 import json
 import os
 import runpy
+import signal
 import ssl
 import sys
 import threading
 import time
+import types
 from pathlib import Path
 
 BACKEND_DIR = Path(__file__).resolve().parent
@@ -18,8 +23,23 @@ _WINDOWS_DLL_DIRECTORY_HANDLES: list[object] = []
 # Keep this in sync with BACKEND_STARTUP_HEARTBEAT_PATH_ENV in src-tauri/src/app_constants.rs.
 STARTUP_HEARTBEAT_ENV = "ASTRBOT_BACKEND_STARTUP_HEARTBEAT_PATH"
 RUNTIME_CORE_LOCK_ENV = "ASTRBOT_DESKTOP_CORE_LOCK_PATH"
+# Keep this in sync with BACKEND_LOG_PATH_ENV in src-tauri/src/app_constants.rs.
+BACKEND_LOG_PATH_ENV = "ASTRBOT_DESKTOP_BACKEND_LOG_PATH"
+# Keep this in sync with STARTUP_HEARTBEAT_SCHEMA_VERSION in src-tauri/src/backend/readiness.rs.
+STARTUP_HEARTBEAT_SCHEMA_VERSION = 2
 STARTUP_HEARTBEAT_INTERVAL_SECONDS = 2.0
+STARTUP_REPORTER_MODULE = "astrbot_desktop_startup"
+STARTUP_WARNING_LIMIT = 20
+_STARTUP_REPORT_LOCK = threading.Lock()
+_STARTUP_REPORT: dict[str, object] = {}
+_STARTUP_WARNINGS: list[str] = []
 STARTUP_HEARTBEAT_STOP_JOIN_TIMEOUT_SECONDS = 1.0
+# Keep these in sync with src-tauri/src/desktop_auth.rs.
+DESKTOP_SESSION_SECRET_ENV = "ASTRBOT_DESKTOP_SESSION_SECRET"
+DESKTOP_SESSION_HEADER = "X-AstrBot-Desktop-Session"
+DESKTOP_SHUTDOWN_ENDPOINT = "/api/v1/desktop/shutdown"
+# Gives the response time to reach the shell before the backend starts tearing down.
+DESKTOP_SHUTDOWN_DELAY_SECONDS = 0.2
 
 
 def configure_stdio_utf8() -> None:
@@ -37,6 +57,56 @@ def configure_stdio_utf8() -> None:
             continue
 
 
+def open_detached_output() -> object:
+    log_path = os.environ.get(BACKEND_LOG_PATH_ENV, "").strip()
+    if log_path:
+        try:
+            return open(log_path, "a", encoding="utf-8", errors="replace", buffering=1)
+        except OSError:
+            pass
+    return open(os.devnull, "w", encoding="utf-8")
+
+
+class DetachSafeStream:
+    """Moves output to the backend log once the desktop shell reading our pipe has quit
+    with "detach on quit"; without a log path it is dropped."""
+
+    def __init__(self, stream: object) -> None:
+        self._stream = stream
+        self._detached = False
+
+    def _detach(self) -> None:
+        self._detached = True
+        self._stream = open_detached_output()
+
+    def write(self, data: str) -> int:
+        try:
+            return self._stream.write(data)
+        except OSError:
+            if self._detached:
+                raise
+            self._detach()
+            return self._stream.write(data)
+
+    def flush(self) -> None:
+        try:
+            self._stream.flush()
+        except OSError:
+            if self._detached:
+                raise
+            self._detach()
+
+    def __getattr__(self, name: str) -> object:
+        return getattr(self._stream, name)
+
+
+def install_detach_safe_stdio() -> None:
+    for stream_name in ("stdout", "stderr"):
+        stream = getattr(sys, stream_name, None)
+        if stream is not None and not isinstance(stream, DetachSafeStream):
+            setattr(sys, stream_name, DetachSafeStream(stream))
+
+
 def configure_windows_dll_search_path() -> None:
     if sys.platform != "win32" or not hasattr(os, "add_dll_directory"):
         return
@@ -180,12 +250,45 @@ def resolve_startup_heartbeat_path() -> Path | None:
     return Path(raw)
 
 
+def report_startup_phase(
+    phase: str, *, progress: float | None = None, message: str | None = None
+) -> None:
+    report: dict[str, object] = {"phase": str(phase)}
+    if progress is not None:
+        report["progress"] = min(max(float(progress), 0.0), 1.0)
+    if message:
+        report["message"] = str(message)
+    with _STARTUP_REPORT_LOCK:
+        _STARTUP_REPORT.clear()
+        _STARTUP_REPORT.update(report)
+
+
+def report_startup_warning(message: str) -> None:
+    with _STARTUP_REPORT_LOCK:
+        _STARTUP_WARNINGS.append(str(message))
+        del _STARTUP_WARNINGS[:-STARTUP_WARNING_LIMIT]
+
+
+def install_startup_reporter() -> None:
+    # Lets the backend report progress with `import astrbot_desktop_startup` when run by the desktop.
+    module = types.ModuleType(STARTUP_REPORTER_MODULE)
+    module.report_phase = report_startup_phase
+    module.report_warning = report_startup_warning
+    sys.modules.setdefault(STARTUP_REPORTER_MODULE, module)
+
+
 def build_heartbeat_payload(state: str) -> dict[str, object]:
-    return {
+    payload: dict[str, object] = {
+        "schema_version": STARTUP_HEARTBEAT_SCHEMA_VERSION,
         "pid": os.getpid(),
         "state": state,
         "updated_at_ms": int(time.time() * 1000),
     }
+    with _STARTUP_REPORT_LOCK:
+        payload.update(_STARTUP_REPORT)
+        if _STARTUP_WARNINGS:
+            payload["warnings"] = list(_STARTUP_WARNINGS)
+    return payload
 
 
 def atomic_write_json(path: Path, payload: dict[str, object]) -> None:
@@ -278,15 +381,76 @@ def configure_runtime_core_lock_path() -> None:
         os.environ.setdefault(RUNTIME_CORE_LOCK_ENV, str(lock_path))
 
 
+def is_authorized_shutdown_request(
+    presented_secret: str | None, remote_addr: str | None, expected_secret: str
+) -> bool:
+    if not expected_secret or not presented_secret or not remote_addr:
+        return False
+    try:
+        address = ipaddress.ip_address(remote_addr)
+    except ValueError:
+        return False
+    mapped = getattr(address, "ipv4_mapped", None)
+    if not (address.is_loopback or (mapped is not None and mapped.is_loopback)):
+        return False
+    return hmac.compare_digest(presented_secret.encode(), expected_secret.encode())
+
+
+def request_clean_exit() -> None:
+    # Same path as Ctrl+C, so the backend's own shutdown handlers run.
+    signal.raise_signal(signal.SIGINT)
+
+
+def install_desktop_shutdown_route() -> None:
+    """Serves the shell's shutdown request on the dashboard app, ahead of its auth middleware."""
+    expected_secret = os.environ.get(DESKTOP_SESSION_SECRET_ENV, "").strip()
+    if not expected_secret:
+        return
+    try:
+        import quart
+    except Exception:
+        return
+    original_init = quart.Quart.__init__
+    if getattr(original_init, "_astrbot_desktop_shutdown", False):
+        return
+
+    async def handle_desktop_shutdown():
+        request = quart.request
+        if request.path != DESKTOP_SHUTDOWN_ENDPOINT or request.method != "POST":
+            return None
+        if not is_authorized_shutdown_request(
+            request.headers.get(DESKTOP_SESSION_HEADER),
+            request.remote_addr,
+            expected_secret,
+        ):
+            return quart.jsonify({"status": "error", "message": "forbidden"}), 403
+        asyncio.get_running_loop().call_later(
+            DESKTOP_SHUTDOWN_DELAY_SECONDS, request_clean_exit
+        )
+        return quart.jsonify({"status": "ok", "data": {}}), 202
+
+    @functools.wraps(original_init)
+    def init(self, *args, **kwargs) -> None:
+        original_init(self, *args, **kwargs)
+        # Registered before the backend adds its own hooks, so this one runs first.
+        self.before_request(handle_desktop_shutdown)
+
+    init._astrbot_desktop_shutdown = True
+    quart.Quart.__init__ = init
+
+
 def main() -> None:
     configure_stdio_utf8()
+    install_detach_safe_stdio()
     configure_windows_dll_search_path()
     preload_windows_runtime_dlls()
     configure_windows_safe_default_ssl_context()
+    install_startup_reporter()
     start_startup_heartbeat()
     configure_runtime_core_lock_path()
 
     sys.path.insert(0, str(APP_DIR))
+    install_desktop_shutdown_route()
 
     main_file = APP_DIR / "main.py"
     if not main_file.is_file():
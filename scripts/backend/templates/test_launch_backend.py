@@ -1,6 +1,8 @@
+import asyncio
 import importlib.util
 import ssl
 import tempfile
+import types
 import unittest
 from pathlib import Path
 from unittest import mock
@@ -17,6 +19,8 @@ SPEC.loader.exec_module(launch_backend)
 class StartupHeartbeatTests(unittest.TestCase):
     def setUp(self) -> None:
         launch_backend._ORIGINAL_CREATE_DEFAULT_CONTEXT = None
+        launch_backend._STARTUP_REPORT.clear()
+        launch_backend._STARTUP_WARNINGS.clear()
 
     def test_windows_ssl_context_patch_uses_certifi_with_original_context(self) -> None:
         sentinel_context = mock.Mock(spec=ssl.SSLContext)
@@ -150,6 +154,131 @@ class StartupHeartbeatTests(unittest.TestCase):
 
             self.assertFalse(temp_path.exists())
 
+    def test_heartbeat_payload_without_report_keeps_legacy_fields(self) -> None:
+        payload = launch_backend.build_heartbeat_payload("starting")
+
+        self.assertEqual(
+            set(payload),
+            {"schema_version", "pid", "state", "updated_at_ms"},
+        )
+        self.assertEqual(
+            payload["schema_version"], launch_backend.STARTUP_HEARTBEAT_SCHEMA_VERSION
+        )
+
+    def test_heartbeat_payload_carries_latest_phase_and_bounded_warnings(self) -> None:
+        launch_backend.report_startup_phase(
+            "installing_dependencies", progress=0.25, message="Installing 3/12"
+        )
+        launch_backend.report_startup_phase("loading_plugins", progress=2)
+        for index in range(launch_backend.STARTUP_WARNING_LIMIT + 2):
+            launch_backend.report_startup_warning(f"warning {index}")
+
+        payload = launch_backend.build_heartbeat_payload("starting")
+
+        self.assertEqual(payload["phase"], "loading_plugins")
+        self.assertEqual(payload["progress"], 1.0)
+        self.assertNotIn("message", payload)
+        self.assertEqual(len(payload["warnings"]), launch_backend.STARTUP_WARNING_LIMIT)
+        self.assertEqual(payload["warnings"][0], "warning 2")
+
+    def test_detach_safe_stream_drops_output_after_pipe_breaks(self) -> None:
+        broken = mock.Mock()
+        broken.write.side_effect = BrokenPipeError()
+        stream = launch_backend.DetachSafeStream(broken)
+
+        with mock.patch.dict(launch_backend.os.environ, {}, clear=True):
+            self.assertEqual(stream.write("hello"), 5)
+        self.assertEqual(stream.write("again"), 5)
+        stream.flush()
+        self.assertEqual(broken.write.call_count, 1)
+
+    def test_detach_safe_stream_moves_output_to_backend_log_after_pipe_breaks(self) -> None:
+        broken = mock.Mock()
+        broken.write.side_effect = BrokenPipeError()
+        stream = launch_backend.DetachSafeStream(broken)
+
+        with tempfile.TemporaryDirectory() as temp_dir:
+            log_path = Path(temp_dir) / "backend.log"
+            log_path.write_text("from the shell\n", encoding="utf-8")
+            with mock.patch.dict(
+                launch_backend.os.environ,
+                {launch_backend.BACKEND_LOG_PATH_ENV: str(log_path)},
+            ):
+                stream.write("hello\n")
+            stream.write("again\n")
+            stream.flush()
+            stream._stream.close()
+
+            self.assertEqual(
+                log_path.read_text(encoding="utf-8"),
+                "from the shell\nhello\nagain\n",
+            )
+
+    def test_shutdown_request_requires_loopback_and_matching_secret(self) -> None:
+        authorized = launch_backend.is_authorized_shutdown_request
+        self.assertTrue(authorized("secret", "127.0.0.1", "secret"))
+        self.assertTrue(authorized("secret", "::1", "secret"))
+        self.assertTrue(authorized("secret", "::ffff:127.0.0.1", "secret"))
+        self.assertFalse(authorized("secret", "192.168.1.5", "secret"))
+        self.assertFalse(authorized("other", "127.0.0.1", "secret"))
+        self.assertFalse(authorized(None, "127.0.0.1", "secret"))
+        self.assertFalse(authorized("secret", "not-an-ip", "secret"))
+
+    def test_desktop_shutdown_route_answers_before_backend_hooks(self) -> None:
+        class FakeQuart:
+            def __init__(self, name: str) -> None:
+                self.hooks = []
+
+            def before_request(self, hook):
+                self.hooks.append(hook)
+                return hook
+
+        fake_quart = types.ModuleType("quart")
+        fake_quart.Quart = FakeQuart
+        fake_quart.jsonify = lambda payload: payload
+        fake_quart.request = types.SimpleNamespace(
+            path="/api/v1/desktop/shutdown",
+            method="POST",
+            headers={"X-AstrBot-Desktop-Session": "secret"},
+            remote_addr="127.0.0.1",
+        )
+
+        with mock.patch.dict("sys.modules", {"quart": fake_quart}):
+            with mock.patch.dict(
+                launch_backend.os.environ, {"ASTRBOT_DESKTOP_SESSION_SECRET": "secret"}
+            ):
+                launch_backend.install_desktop_shutdown_route()
+                launch_backend.install_desktop_shutdown_route()
+            app = FakeQuart("dashboard")
+            self.assertEqual(len(app.hooks), 1)
+
+            with mock.patch.object(launch_backend, "request_clean_exit") as clean_exit:
+                async def shutdown_then_wait():
+                    response = await app.hooks[0]()
+                    await asyncio.sleep(launch_backend.DESKTOP_SHUTDOWN_DELAY_SECONDS + 0.1)
+                    return response
+
+                response = asyncio.run(shutdown_then_wait())
+            self.assertEqual(response[1], 202)
+            clean_exit.assert_called_once()
+
+            fake_quart.request.headers = {}
+            self.assertEqual(asyncio.run(app.hooks[0]())[1], 403)
+            fake_quart.request.path = "/api/stat/version"
+            self.assertIsNone(asyncio.run(app.hooks[0]()))
+
+    def test_install_startup_reporter_exposes_report_functions(self) -> None:
+        with mock.patch.dict("sys.modules"):
+            launch_backend.install_startup_reporter()
+            import astrbot_desktop_startup
+
+            astrbot_desktop_startup.report_phase("booting", message="Starting core")
+
+        self.assertEqual(
+            launch_backend.build_heartbeat_payload("starting")["message"],
+            "Starting core",
+        )
+
     def test_repeated_failures_warn_before_first_success(self) -> None:
         stop_event = mock.Mock()
         stop_event.wait.side_effect = [False, True]
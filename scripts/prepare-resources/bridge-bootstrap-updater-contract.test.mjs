@@ -52,8 +52,16 @@ function runBootstrap(source, authResults, sharedState = {}) {
       return this.href;
     },
   };
+  const eventListeners = sharedState.eventListeners || new Map();
+  sharedState.eventListeners = eventListeners;
   const window = {
     __TAURI_INTERNALS__: {
+      event: {
+        async listen(eventName, handler) {
+          eventListeners.set(eventName, handler);
+          return () => eventListeners.delete(eventName);
+        },
+      },
       async invoke(command, payload = {}) {
         invocations.push({ command, payload });
         if (command === 'desktop_bridge_get_auth_token') {
@@ -99,6 +107,7 @@ function runBootstrap(source, authResults, sharedState = {}) {
   runInNewContext(
     source
       .replace('{TRAY_RESTART_BACKEND_EVENT}', 'astrbot://tray-restart-backend')
+      .replace('{DESKTOP_EVENT}', 'astrbot://desktop-event')
       .replace('{CHAT_TRANSPORT_MODE_STORAGE_KEY}', 'chat_transport_mode')
       .replace('{CHAT_TRANSPORT_MODE_WEBSOCKET}', 'websocket'),
     {
@@ -119,6 +128,7 @@ function runBootstrap(source, authResults, sharedState = {}) {
     invocations,
     intervals,
     navigation,
+    eventListeners,
   };
 }
 
@@ -234,6 +244,68 @@ test('bridge bootstrap normalizes unexpected desktop authentication refresh erro
   assert.equal(runtime.window.location.hash, '#/auth/login');
 });
 
+test('bridge bootstrap forwards log queries to the desktop bridge', async () => {
+  const source = await readFile(bootstrapPath, 'utf8');
+  const runtime = runBootstrap(source, []);
+
+  await runtime.window.astrbotDesktop.queryLogs('desktop', {
+    limit: 50,
+    categories: ['startup'],
+    contains: 'failed',
+  });
+  await runtime.window.astrbotDesktop.queryLogs('backend', null);
+
+  const queries = runtime.invocations.filter(
+    ({ command }) => command === 'desktop_bridge_query_logs',
+  );
+  assert.deepEqual(
+    JSON.parse(JSON.stringify(queries.map(({ payload }) => payload))),
+    [
+      {
+        source: 'desktop',
+        query: { limit: 50, categories: ['startup'], contains: 'failed' },
+      },
+      { source: 'backend', query: {} },
+    ],
+  );
+});
+
+test('bridge bootstrap exposes diagnostic bundle export', async () => {
+  const source = await readFile(bootstrapPath, 'utf8');
+  const runtime = runBootstrap(source, []);
+
+  await runtime.window.astrbotDesktop.exportDiagnostics();
+
+  assert.deepEqual(
+    runtime.invocations
+      .filter(({ command }) => command === 'desktop_bridge_export_diagnostics')
+      .map(({ command }) => command),
+    ['desktop_bridge_export_diagnostics'],
+  );
+});
+
+test('bridge bootstrap exposes profile listing and switching', async () => {
+  const source = await readFile(bootstrapPath, 'utf8');
+  const runtime = runBootstrap(source, []);
+
+  await runtime.window.astrbotDesktop.listProfiles();
+  await runtime.window.astrbotDesktop.switchProfile('customer-a');
+
+  assert.deepEqual(
+    JSON.parse(
+      JSON.stringify(
+        runtime.invocations
+          .filter(({ command }) => command.includes('_profile'))
+          .map(({ command, payload }) => ({ command, payload })),
+      ),
+    ),
+    [
+      { command: 'desktop_bridge_list_profiles', payload: {} },
+      { command: 'desktop_bridge_switch_profile', payload: { profileId: 'customer-a' } },
+    ],
+  );
+});
+
 test('bridge bootstrap transport placeholders are backed by the shared contract', async () => {
   const [source, rawContract] = await Promise.all([
     readFile(bootstrapPath, 'utf8'),
@@ -247,3 +319,27 @@ test('bridge bootstrap transport placeholders are backed by the shared contract'
   assert.match(source, /\{CHAT_TRANSPORT_MODE_STORAGE_KEY\}/);
   assert.match(source, /\{CHAT_TRANSPORT_MODE_WEBSOCKET\}/);
 });
+
+test('bridge bootstrap dispatches desktop events by kind and wildcard', async () => {
+  const source = await readFile(bootstrapPath, 'utf8');
+  const runtime = runBootstrap(source, []);
+  await flushAsyncWork();
+
+  const ready = [];
+  const all = [];
+  const unsubscribeReady = runtime.window.astrbotDesktop.on('backendReady', (payload) =>
+    ready.push(payload.pid),
+  );
+  runtime.window.astrbotDesktop.on('*', (payload) => all.push(payload.kind));
+
+  const emit = runtime.eventListeners.get('astrbot://desktop-event');
+  assert.equal(typeof emit, 'function');
+  emit({ payload: { schemaVersion: 1, kind: 'backendSpawned', pid: 42 } });
+  emit({ payload: { schemaVersion: 1, kind: 'backendReady', pid: 42 } });
+  unsubscribeReady();
+  emit({ payload: { schemaVersion: 1, kind: 'backendReady', pid: 43 } });
+  emit({ payload: null });
+
+  assert.deepEqual(ready, [42]);
+  assert.deepEqual(all, ['backendSpawned', 'backendReady', 'backendReady']);
+});
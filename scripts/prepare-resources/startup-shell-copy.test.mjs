@@ -1,6 +1,7 @@
 import { test } from 'node:test';
 import assert from 'node:assert/strict';
 import { readFile } from 'node:fs/promises';
+import { runInNewContext } from 'node:vm';
 
 const startupShellPath = new URL('../../ui/index.html', import.meta.url);
 const startupCopyConfigPath = new URL('../../ui/startup-copy.js', import.meta.url);
@@ -109,3 +110,181 @@ test('startup shell loads shared copy config, reuses applyStartupMode, and expos
     'expected shared startup copy config to include Chinese startup copy',
   );
 });
+
+test('startup shell renders streamed startup progress from the desktop event channel', async () => {
+  const [source, configSource] = await Promise.all([
+    readFile(startupShellPath, 'utf8'),
+    readFile(startupCopyConfigPath, 'utf8'),
+  ]);
+  const inlineScript = source.match(/<script>([\s\S]*?)<\/script>/)?.[1];
+  assert.ok(inlineScript, 'expected an inline startup shell script');
+
+  const elements = Object.fromEntries(
+    ['startup-title', 'startup-desc', 'startup-status', 'startup-progress'].map((id) => [
+      id,
+      { textContent: '' },
+    ]),
+  );
+  const listens = [];
+  const callbacks = [];
+  const window = {
+    __TAURI_INTERNALS__: {
+      transformCallback(callback) {
+        callbacks.push(callback);
+        return callbacks.length - 1;
+      },
+      async invoke(command, payload) {
+        listens.push({ command, payload });
+      },
+    },
+  };
+  const context = {
+    window,
+    document: { getElementById: (id) => elements[id] ?? null },
+    navigator: { language: 'en-US' },
+  };
+  runInNewContext(configSource, context);
+  runInNewContext(inlineScript, context);
+
+  assert.deepEqual(
+    listens.map(({ command, payload }) => [command, payload.event]),
+    [['plugin:event|listen', 'astrbot://desktop-event']],
+  );
+
+  callbacks[0]({
+    payload: {
+      kind: 'startupProgress',
+      stage: 'waitingForHttp',
+      pid: 42,
+      elapsedMs: 65_400,
+      timeoutMs: 900_000,
+      tcpReachable: true,
+      httpStatus: 503,
+      heartbeat: 'stale',
+      heartbeatAgeMs: 7_200,
+    },
+  });
+  assert.equal(elements['startup-status'].textContent, 'Waiting for the dashboard to respond...');
+  assert.equal(
+    elements['startup-progress'].textContent,
+    'PID 42 · port open · HTTP 503 · no heartbeat for 7s · 65s / 900s',
+  );
+
+  callbacks[0]({
+    payload: {
+      kind: 'startupProgress',
+      stage: 'waitingForHttp',
+      pid: 42,
+      elapsedMs: 30_000,
+      timeoutMs: null,
+      tcpReachable: false,
+      httpStatus: null,
+      heartbeat: 'fresh',
+      phase: 'installing_dependencies',
+      phaseProgress: 0.25,
+      message: 'Installing dependencies 3/12',
+      warnings: ['pip mirror unreachable'],
+    },
+  });
+  assert.equal(elements['startup-status'].textContent, 'Installing dependencies 3/12');
+  assert.equal(
+    elements['startup-progress'].textContent,
+    'PID 42 · 25% · port not open yet · backend active · 30s elapsed · 1 warning(s)',
+  );
+
+  callbacks[0]({ payload: { kind: 'backendReady', pid: 42 } });
+  window.__astrbotSetStartupProgress({ stage: 'ready', pid: 42, elapsedMs: 66_000, timeoutMs: null });
+  assert.equal(elements['startup-status'].textContent, 'Dashboard ready, opening...');
+  assert.equal(elements['startup-progress'].textContent, 'PID 42 · 66s elapsed');
+});
+
+test('startup shell shows a notice when the dashboard port is relocated', async () => {
+  const [source, configSource] = await Promise.all([
+    readFile(startupShellPath, 'utf8'),
+    readFile(startupCopyConfigPath, 'utf8'),
+  ]);
+  const inlineScript = source.match(/<script>([\s\S]*?)<\/script>/)?.[1];
+  assert.match(source, /<p id="startup-notice" class="notice"><\/p>/);
+
+  const elements = Object.fromEntries(
+    ['startup-title', 'startup-desc', 'startup-status', 'startup-progress', 'startup-notice'].map(
+      (id) => [id, { textContent: '' }],
+    ),
+  );
+  const callbacks = [];
+  const window = {
+    __TAURI_INTERNALS__: {
+      transformCallback(callback) {
+        callbacks.push(callback);
+        return callbacks.length - 1;
+      },
+      async invoke() {},
+    },
+  };
+  const context = {
+    window,
+    document: { getElementById: (id) => elements[id] ?? null },
+    navigator: { language: 'zh-CN' },
+  };
+  runInNewContext(configSource, context);
+  runInNewContext(inlineScript, context);
+
+  callbacks[0]({ payload: { kind: 'portRelocated', from: 6185, to: 51234 } });
+  assert.equal(elements['startup-notice'].textContent, '面板端口 6185 已被占用，已改用 51234。');
+});
+
+test('startup shell ignores desktop events from background profiles', async () => {
+  const [source, configSource] = await Promise.all([
+    readFile(startupShellPath, 'utf8'),
+    readFile(startupCopyConfigPath, 'utf8'),
+  ]);
+  const inlineScript = source.match(/<script>([\s\S]*?)<\/script>/)?.[1];
+
+  const elements = Object.fromEntries(
+    ['startup-title', 'startup-desc', 'startup-status', 'startup-progress', 'startup-notice'].map(
+      (id) => [id, { textContent: '' }],
+    ),
+  );
+  const callbacks = [];
+  const window = {
+    __TAURI_INTERNALS__: {
+      transformCallback(callback) {
+        callbacks.push(callback);
+        return callbacks.length - 1;
+      },
+      async invoke() {},
+    },
+  };
+  const context = {
+    window,
+    document: { getElementById: (id) => elements[id] ?? null },
+    navigator: { language: 'en-US' },
+  };
+  runInNewContext(configSource, context);
+  runInNewContext(inlineScript, context);
+  window.__astrbotSetStartupMode('loading', 'default');
+
+  callbacks[0]({
+    payload: {
+      kind: 'startupProgress',
+      profileId: 'default',
+      stage: 'waitingForHttp',
+      pid: 42,
+      elapsedMs: 0,
+    },
+  });
+  callbacks[0]({
+    payload: {
+      kind: 'startupProgress',
+      profileId: 'work',
+      stage: 'ready',
+      pid: 77,
+      elapsedMs: 0,
+    },
+  });
+  callbacks[0]({ payload: { kind: 'portRelocated', profileId: 'work', from: 6185, to: 51234 } });
+
+  assert.equal(elements['startup-status'].textContent, 'Waiting for the dashboard to respond...');
+  assert.equal(elements['startup-progress'].textContent, 'PID 42 · port not open yet');
+  assert.equal(elements['startup-notice'].textContent, '');
+});
@@ -2,6 +2,7 @@ use std::time::Duration;
 
 pub(crate) const DEFAULT_BACKEND_URL: &str = "http://127.0.0.1:6185/";
 pub(crate) const ASTRBOT_ROOT_ENV: &str = "ASTRBOT_ROOT";
+pub(crate) const BACKEND_CMD_ENV: &str = "ASTRBOT_BACKEND_CMD";
 pub(crate) const BACKEND_TIMEOUT_ENV: &str = "ASTRBOT_BACKEND_TIMEOUT_MS";
 pub(crate) const PACKAGED_BACKEND_TIMEOUT_FALLBACK_MS: u64 = 15 * 60 * 1000;
 pub(crate) const GRACEFUL_RESTART_REQUEST_TIMEOUT_MS: u64 = 2_500;
@@ -9,6 +10,11 @@ pub(crate) const DESKTOP_AUTH_REQUEST_TIMEOUT_MS: u64 = 2_500;
 pub(crate) const GRACEFUL_RESTART_START_TIME_TIMEOUT_MS: u64 = 1_800;
 pub(crate) const GRACEFUL_RESTART_POLL_INTERVAL_MS: u64 = 350;
 pub(crate) const GRACEFUL_STOP_TIMEOUT_MS: u64 = 10_000;
+pub(crate) const BACKEND_SHUTDOWN_REQUEST_TIMEOUT_MS: u64 = 2_000;
+pub(crate) const DEFAULT_BACKEND_SHUTDOWN_GRACE_MS: u64 = 5_000;
+pub(crate) const BACKEND_SHUTDOWN_GRACE_MIN_MS: u64 = 500;
+pub(crate) const BACKEND_SHUTDOWN_GRACE_MAX_MS: u64 = 60_000;
+pub(crate) const BACKEND_SHUTDOWN_GRACE_ENV: &str = "ASTRBOT_BACKEND_SHUTDOWN_GRACE_MS";
 #[cfg(target_os = "windows")]
 pub(crate) const SYSTEM_SHUTDOWN_STOP_TIMEOUT_MS: u64 = 2_000;
 pub(crate) const DEFAULT_BACKEND_READY_POLL_INTERVAL_MS: u64 = 300;
@@ -16,7 +22,9 @@ pub(crate) const BACKEND_READY_POLL_INTERVAL_MIN_MS: u64 = 50;
 pub(crate) const BACKEND_READY_POLL_INTERVAL_MAX_MS: u64 = 10_000;
 pub(crate) const BACKEND_READY_POLL_INTERVAL_ENV: &str = "ASTRBOT_BACKEND_READY_POLL_INTERVAL_MS";
 pub(crate) const DEFAULT_BACKEND_READY_HTTP_PATH: &str = "/api/stat/start-time";
+pub(crate) const BACKEND_VERSION_ENDPOINT: &str = "/api/stat/version";
 pub(crate) const BACKEND_READY_HTTP_PATH_ENV: &str = "ASTRBOT_BACKEND_READY_HTTP_PATH";
+pub(crate) const BACKEND_READY_PROBES_ENV: &str = "ASTRBOT_BACKEND_READY_PROBES";
 pub(crate) const BACKEND_READY_PROBE_TIMEOUT_ENV: &str = "ASTRBOT_BACKEND_READY_PROBE_TIMEOUT_MS";
 pub(crate) const BACKEND_READY_PROBE_TIMEOUT_MIN_MS: u64 = 100;
 pub(crate) const BACKEND_READY_PROBE_TIMEOUT_MAX_MS: u64 = 30_000;
@@ -28,8 +36,13 @@ pub(crate) const BACKEND_STARTUP_IDLE_TIMEOUT_MAX_MS: u64 = 15 * 60 * 1000;
 // Keep this in sync with STARTUP_HEARTBEAT_ENV in scripts/backend/templates/launch_backend.py.
 pub(crate) const BACKEND_STARTUP_HEARTBEAT_PATH_ENV: &str =
     "ASTRBOT_BACKEND_STARTUP_HEARTBEAT_PATH";
+// Keep this in sync with BACKEND_LOG_PATH_ENV in scripts/backend/templates/launch_backend.py.
+pub(crate) const BACKEND_LOG_PATH_ENV: &str = "ASTRBOT_DESKTOP_BACKEND_LOG_PATH";
 pub(crate) const DEFAULT_BACKEND_STARTUP_HEARTBEAT_RELATIVE_PATH: &str =
     "data/backend-startup-heartbeat.json";
+pub(crate) const BACKEND_PID_RECORD_RELATIVE_PATH: &str = "data/desktop-backend-pid.json";
+pub(crate) const BACKEND_DETACHED_RECORD_RELATIVE_PATH: &str = "data/desktop-detached-backend.json";
+pub(crate) const DESKTOP_INSTANCE_LOCK_RELATIVE_PATH: &str = "data/desktop-instance.lock";
 pub(crate) const DEFAULT_BACKEND_PING_TIMEOUT_MS: u64 = 800;
 pub(crate) const BACKEND_PING_TIMEOUT_MIN_MS: u64 = 50;
 pub(crate) const BACKEND_PING_TIMEOUT_MAX_MS: u64 = 30_000;
@@ -38,12 +51,54 @@ pub(crate) const BRIDGE_BACKEND_PING_TIMEOUT_ENV: &str = "ASTRBOT_BRIDGE_BACKEND
 pub(crate) const DESKTOP_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
 pub(crate) const BACKEND_LOG_MAX_BYTES: u64 = 20 * 1024 * 1024;
 pub(crate) const LOG_BACKUP_COUNT: usize = 5;
-pub(crate) const BACKEND_LOG_ROTATION_CHECK_INTERVAL: Duration = Duration::from_secs(20);
+pub(crate) const LOG_MAX_BYTES_MIN: u64 = 64 * 1024;
+pub(crate) const LOG_MAX_BYTES_MAX: u64 = 1024 * 1024 * 1024;
+pub(crate) const LOG_BACKUP_COUNT_MIN: u64 = 1;
+pub(crate) const LOG_BACKUP_COUNT_MAX: u64 = 50;
+pub(crate) const LOG_MAX_AGE_DAYS_MIN: u64 = 1;
+pub(crate) const LOG_MAX_AGE_DAYS_MAX: u64 = 3_650;
+pub(crate) const DESKTOP_LOG_MAX_BYTES_ENV: &str = "ASTRBOT_DESKTOP_LOG_MAX_BYTES";
+pub(crate) const BACKEND_LOG_MAX_BYTES_ENV: &str = "ASTRBOT_BACKEND_LOG_MAX_BYTES";
+pub(crate) const LOG_BACKUP_COUNT_ENV: &str = "ASTRBOT_LOG_BACKUP_COUNT";
+pub(crate) const LOG_COMPRESS_BACKUPS_ENV: &str = "ASTRBOT_LOG_COMPRESS_BACKUPS";
+pub(crate) const LOG_MAX_AGE_DAYS_ENV: &str = "ASTRBOT_LOG_MAX_AGE_DAYS";
+pub(crate) const BACKEND_OUTPUT_BUFFER_LINES: usize = 2_000;
+pub(crate) const BACKEND_EXIT_STDERR_TAIL_LINES: usize = 20;
+pub(crate) const BACKEND_LIVENESS_INTERVAL_ENV: &str = "ASTRBOT_BACKEND_LIVENESS_INTERVAL_MS";
+pub(crate) const DEFAULT_BACKEND_LIVENESS_INTERVAL_MS: u64 = 15_000;
+pub(crate) const BACKEND_LIVENESS_INTERVAL_MIN_MS: u64 = 1_000;
+pub(crate) const BACKEND_LIVENESS_INTERVAL_MAX_MS: u64 = 10 * 60 * 1000;
+pub(crate) const BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV: &str =
+    "ASTRBOT_BACKEND_LIVENESS_FAILURE_THRESHOLD";
+pub(crate) const DEFAULT_BACKEND_LIVENESS_FAILURE_THRESHOLD: u64 = 3;
+pub(crate) const BACKEND_LIVENESS_FAILURE_THRESHOLD_MIN: u64 = 1;
+pub(crate) const BACKEND_LIVENESS_FAILURE_THRESHOLD_MAX: u64 = 100;
+pub(crate) const BACKEND_LIVENESS_HTTP_PATH_ENV: &str = "ASTRBOT_BACKEND_LIVENESS_HTTP_PATH";
+pub(crate) const BACKEND_RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+pub(crate) const BACKEND_RSS_LIMIT_MIN_MB: u64 = 64;
+pub(crate) const BACKEND_RSS_LIMIT_MAX_MB: u64 = 1024 * 1024;
+pub(crate) const DEFAULT_BACKEND_RSS_RESTART_AFTER_SECS: u64 = 5 * 60;
+pub(crate) const BACKEND_RSS_RESTART_AFTER_MIN_SECS: u64 = 10;
+pub(crate) const BACKEND_RSS_RESTART_AFTER_MAX_SECS: u64 = 24 * 60 * 60;
+pub(crate) const BACKEND_SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(1);
+pub(crate) const BACKEND_CRASH_RESTART_INITIAL_BACKOFF_MS: u64 = 1_000;
+pub(crate) const BACKEND_CRASH_RESTART_MAX_BACKOFF_MS: u64 = 60_000;
+pub(crate) const BACKEND_CRASH_LOOP_MAX_RESTARTS: usize = 5;
+pub(crate) const BACKEND_CRASH_LOOP_WINDOW: Duration = Duration::from_secs(10 * 60);
 pub(crate) const DESKTOP_LOG_FILE: &str = "desktop.log";
+pub(crate) const DESKTOP_LOG_FORMAT_ENV: &str = "ASTRBOT_DESKTOP_LOG_FORMAT";
+pub(crate) const DESKTOP_LOG_LEVEL_ENV: &str = "ASTRBOT_DESKTOP_LOG_LEVEL";
+pub(crate) const DEFAULT_LOG_QUERY_LIMIT: usize = 200;
+pub(crate) const MAX_LOG_QUERY_LIMIT: usize = 5_000;
 pub(crate) const TRAY_ID: &str = "astrbot-tray";
 pub(crate) const TRAY_RESTART_BACKEND_EVENT: &str = "astrbot://tray-restart-backend";
+pub(crate) const DESKTOP_EVENT_NAME: &str = "astrbot://desktop-event";
+pub(crate) const DESKTOP_EVENT_SCHEMA_VERSION: u32 = 1;
 pub(crate) const DEFAULT_SHELL_LOCALE: &str = "zh-CN";
 pub(crate) const STARTUP_MODE_ENV: &str = "ASTRBOT_DESKTOP_STARTUP_MODE";
+pub(crate) const DESKTOP_PROFILE_ENV: &str = "ASTRBOT_DESKTOP_PROFILE";
+pub(crate) const HEADLESS_ARG: &str = "--headless";
+pub(crate) const HEADLESS_ENV: &str = "ASTRBOT_DESKTOP_HEADLESS";
 #[cfg(target_os = "windows")]
 pub(crate) const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 #[cfg(target_os = "windows")]
@@ -1,21 +1,34 @@
 use std::{
+    env,
     ffi::OsString,
-    sync::{Mutex, OnceLock},
+    sync::{atomic::Ordering, Arc, Mutex, OnceLock},
+    time::Duration,
 };
 
 use tauri::{AppHandle, Manager};
 
 use crate::{
-    backend, bridge, logging, runtime_paths, window, BackendState, LaunchPlan, DESKTOP_LOG_FILE,
-    DESKTOP_LOG_MAX_BYTES, LOG_BACKUP_COUNT,
+    backend, bridge, desktop_settings,
+    logging::{self, DesktopLogCategory, DesktopLogLevel},
+    profiles::registry::ProfileRegistry,
+    runtime_paths, window, BackendState, LaunchPlan, BACKEND_LOG_MAX_BYTES,
+    BACKEND_LOG_MAX_BYTES_ENV, DESKTOP_LOG_FILE, DESKTOP_LOG_FORMAT_ENV, DESKTOP_LOG_LEVEL_ENV,
+    DESKTOP_LOG_MAX_BYTES, DESKTOP_LOG_MAX_BYTES_ENV, LOG_BACKUP_COUNT, LOG_BACKUP_COUNT_ENV,
+    LOG_BACKUP_COUNT_MAX, LOG_BACKUP_COUNT_MIN, LOG_COMPRESS_BACKUPS_ENV, LOG_MAX_AGE_DAYS_ENV,
+    LOG_MAX_AGE_DAYS_MAX, LOG_MAX_AGE_DAYS_MIN, LOG_MAX_BYTES_MAX, LOG_MAX_BYTES_MIN,
 };
 
 static DESKTOP_LOG_WRITE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 static BACKEND_PATH_OVERRIDE: OnceLock<Option<OsString>> = OnceLock::new();
+static DESKTOP_LOG_CONFIG: OnceLock<DesktopLogConfig> = OnceLock::new();
+
+pub(crate) fn active_backend_state(app_handle: &AppHandle) -> Arc<BackendState> {
+    app_handle.state::<ProfileRegistry>().active_state()
+}
 
 pub(crate) fn navigate_main_window_to_backend(app_handle: &AppHandle) -> Result<(), String> {
-    let state = app_handle.state::<BackendState>();
-    window::main_window::navigate_main_window_to_backend(app_handle, &state.backend_url)
+    let state = active_backend_state(app_handle);
+    window::main_window::navigate_main_window_to_backend(app_handle, &state.backend_url())
 }
 
 pub(crate) fn inject_desktop_bridge(webview: &tauri::Webview<tauri::Wry>) {
@@ -37,33 +50,266 @@ pub(crate) fn build_debug_command(plan: &LaunchPlan) -> Vec<String> {
 }
 
 pub(crate) fn append_desktop_log(message: &str) {
-    append_desktop_log_with_category(logging::DesktopLogCategory::Runtime, message);
+    append_desktop_log_with_category(DesktopLogCategory::Runtime, DesktopLogLevel::Info, message);
 }
 
 pub(crate) fn append_startup_log(message: &str) {
-    append_desktop_log_with_category(logging::DesktopLogCategory::Startup, message);
+    append_desktop_log_with_category(DesktopLogCategory::Startup, DesktopLogLevel::Info, message);
 }
 
 pub(crate) fn append_restart_log(message: &str) {
-    append_desktop_log_with_category(logging::DesktopLogCategory::Restart, message);
+    append_desktop_log_with_category(DesktopLogCategory::Restart, DesktopLogLevel::Info, message);
 }
 
 pub(crate) fn append_shutdown_log(message: &str) {
-    append_desktop_log_with_category(logging::DesktopLogCategory::Shutdown, message);
+    append_desktop_log_with_category(DesktopLogCategory::Shutdown, DesktopLogLevel::Info, message);
+}
+
+pub(crate) fn append_desktop_log_at(level: DesktopLogLevel, message: &str) {
+    append_desktop_log_with_category(DesktopLogCategory::Runtime, level, message);
+}
+
+pub(crate) fn append_startup_log_at(level: DesktopLogLevel, message: &str) {
+    append_desktop_log_with_category(DesktopLogCategory::Startup, level, message);
+}
+
+pub(crate) fn append_restart_log_at(level: DesktopLogLevel, message: &str) {
+    append_desktop_log_with_category(DesktopLogCategory::Restart, level, message);
+}
+
+#[derive(Debug)]
+struct DesktopLogConfig {
+    format: logging::DesktopLogFormat,
+    levels: logging::DesktopLogLevelFilter,
+    desktop_rotation: logging::LogRotationPolicy,
+    backend_rotation: logging::LogRotationPolicy,
+}
+
+fn resolve_log_limit(
+    env_name: &str,
+    setting_name: &str,
+    setting: Option<&str>,
+    fallback: u64,
+    bounds: (u64, u64),
+    warnings: &mut Vec<String>,
+) -> u64 {
+    let (source, raw) = match env::var(env_name) {
+        Ok(raw) => (env_name, raw),
+        Err(_) => match setting {
+            Some(raw) => (setting_name, raw.to_string()),
+            None => return fallback,
+        },
+    };
+    backend::config::parse_clamped_u64(&raw, source, fallback, bounds.0, bounds.1, |message| {
+        warnings.push(message)
+    })
+}
+
+fn resolve_log_rotation(
+    settings: &desktop_settings::LogRotationSettings,
+    warnings: &mut Vec<String>,
+) -> (logging::LogRotationPolicy, logging::LogRotationPolicy) {
+    let max_bytes_bounds = (LOG_MAX_BYTES_MIN, LOG_MAX_BYTES_MAX);
+    let desktop_max_bytes = resolve_log_limit(
+        DESKTOP_LOG_MAX_BYTES_ENV,
+        "logRotation.desktopMaxBytes",
+        settings.desktop_max_bytes.as_deref(),
+        DESKTOP_LOG_MAX_BYTES,
+        max_bytes_bounds,
+        warnings,
+    );
+    let backend_max_bytes = resolve_log_limit(
+        BACKEND_LOG_MAX_BYTES_ENV,
+        "logRotation.backendMaxBytes",
+        settings.backend_max_bytes.as_deref(),
+        BACKEND_LOG_MAX_BYTES,
+        max_bytes_bounds,
+        warnings,
+    );
+    let backup_count = resolve_log_limit(
+        LOG_BACKUP_COUNT_ENV,
+        "logRotation.backupCount",
+        settings.backup_count.as_deref(),
+        LOG_BACKUP_COUNT as u64,
+        (LOG_BACKUP_COUNT_MIN, LOG_BACKUP_COUNT_MAX),
+        warnings,
+    ) as usize;
+
+    let compress_source = env::var(LOG_COMPRESS_BACKUPS_ENV)
+        .ok()
+        .map(|raw| (LOG_COMPRESS_BACKUPS_ENV, raw))
+        .or_else(|| {
+            settings
+                .compress_backups
+                .clone()
+                .map(|raw| ("logRotation.compressBackups", raw))
+        });
+    let compress_backups = match compress_source {
+        Some((source, raw)) => logging::parse_log_flag(&raw).unwrap_or_else(|| {
+            warnings.push(format!(
+                "invalid {source}='{raw}', backups stay uncompressed"
+            ));
+            false
+        }),
+        None => false,
+    };
+
+    // Zero (or unset) keeps age-based retention disabled.
+    let max_age_days = match env::var(LOG_MAX_AGE_DAYS_ENV)
+        .ok()
+        .or_else(|| settings.max_age_days.clone())
+    {
+        Some(raw) if raw.trim() != "0" => resolve_log_limit(
+            LOG_MAX_AGE_DAYS_ENV,
+            "logRotation.maxAgeDays",
+            Some(&raw),
+            0,
+            (LOG_MAX_AGE_DAYS_MIN, LOG_MAX_AGE_DAYS_MAX),
+            warnings,
+        ),
+        _ => 0,
+    };
+    let max_age =
+        (max_age_days > 0).then(|| Duration::from_secs(max_age_days.saturating_mul(24 * 60 * 60)));
+
+    let policy = |max_bytes| logging::LogRotationPolicy {
+        compress_backups,
+        max_age,
+        ..logging::LogRotationPolicy::new(max_bytes, backup_count)
+    };
+    (policy(desktop_max_bytes), policy(backend_max_bytes))
 }
 
-fn append_desktop_log_with_category(category: logging::DesktopLogCategory, message: &str) {
+fn resolve_desktop_log_config(warnings: &mut Vec<String>) -> DesktopLogConfig {
+    let settings = desktop_settings::read_logging_settings(
+        runtime_paths::default_packaged_root_dir().as_deref(),
+    );
+    let env_format = env::var(DESKTOP_LOG_FORMAT_ENV).ok();
+    let format = env_format
+        .as_deref()
+        .and_then(logging::DesktopLogFormat::parse)
+        .or_else(|| {
+            settings
+                .format
+                .as_deref()
+                .and_then(logging::DesktopLogFormat::parse)
+        })
+        .unwrap_or_default();
+
+    // Settings first, then the env var on top, so one-off runs can raise verbosity.
+    let mut levels = logging::DesktopLogLevelFilter::default();
+    let specs = [
+        ("logLevels setting", settings.level_spec),
+        (DESKTOP_LOG_LEVEL_ENV, env::var(DESKTOP_LOG_LEVEL_ENV).ok()),
+    ];
+    for (source, spec) in specs {
+        let Some(spec) = spec else {
+            continue;
+        };
+        for entry in levels.apply_spec(&spec) {
+            warnings.push(format!(
+                "ignoring invalid log level entry '{entry}' from {source}"
+            ));
+        }
+    }
+
+    let (desktop_rotation, backend_rotation) = resolve_log_rotation(&settings.rotation, warnings);
+
+    DesktopLogConfig {
+        format,
+        levels,
+        desktop_rotation,
+        backend_rotation,
+    }
+}
+
+fn desktop_log_config() -> &'static DesktopLogConfig {
+    // Resolution must not log: that would re-enter this OnceLock. Warnings are
+    // collected and written once initialization has finished.
+    let mut warnings = Vec::new();
+    let config = DESKTOP_LOG_CONFIG.get_or_init(|| resolve_desktop_log_config(&mut warnings));
+    for warning in warnings {
+        append_desktop_log_at(DesktopLogLevel::Warn, &warning);
+    }
+    config
+}
+
+pub(crate) fn desktop_log_rotation_policy() -> logging::LogRotationPolicy {
+    desktop_log_config().desktop_rotation
+}
+
+pub(crate) fn backend_log_rotation_policy() -> logging::LogRotationPolicy {
+    desktop_log_config().backend_rotation
+}
+
+fn append_desktop_log_with_category(
+    category: DesktopLogCategory,
+    level: DesktopLogLevel,
+    message: &str,
+) {
+    append_desktop_log_for_backend(category, level, message, None);
+}
+
+fn append_desktop_log_for_backend(
+    category: DesktopLogCategory,
+    level: DesktopLogLevel,
+    message: &str,
+    backend_pid: Option<u32>,
+) {
+    let config = desktop_log_config();
+    if !config.levels.allows(category, level) {
+        return;
+    }
     logging::append_desktop_log(
         category,
+        level,
         message,
+        backend_pid,
         runtime_paths::default_packaged_root_dir(),
         DESKTOP_LOG_FILE,
-        DESKTOP_LOG_MAX_BYTES,
-        LOG_BACKUP_COUNT,
+        &config.desktop_rotation,
+        config.format,
         &DESKTOP_LOG_WRITE_LOCK,
     )
 }
 
+/// Log helpers for messages about one profile's backend; structured records
+/// carry that backend's pid rather than whichever profile spawned last.
+impl BackendState {
+    pub(crate) fn set_backend_pid(&self, pid: Option<u32>) {
+        self.backend_pid.store(pid.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    pub(crate) fn backend_pid(&self) -> Option<u32> {
+        match self.backend_pid.load(Ordering::Relaxed) {
+            0 => None,
+            pid => Some(pid),
+        }
+    }
+
+    pub(crate) fn append_desktop_log(&self, message: &str) {
+        self.append_desktop_log_at(DesktopLogLevel::Info, message);
+    }
+
+    pub(crate) fn append_desktop_log_at(&self, level: DesktopLogLevel, message: &str) {
+        append_desktop_log_for_backend(
+            DesktopLogCategory::Runtime,
+            level,
+            message,
+            self.backend_pid(),
+        );
+    }
+
+    pub(crate) fn append_restart_log(&self, message: &str) {
+        append_desktop_log_for_backend(
+            DesktopLogCategory::Restart,
+            DesktopLogLevel::Info,
+            message,
+            self.backend_pid(),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -80,6 +326,8 @@ mod tests {
             root_dir: None,
             webui_dir: None,
             startup_heartbeat_path: None,
+            dashboard_port: None,
+            runtime_dir: None,
             packaged_mode: false,
         };
 
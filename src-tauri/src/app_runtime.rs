@@ -59,9 +59,13 @@ mod linux_webkit_workaround {
 }
 
 use crate::{
-    app_runtime_events, append_desktop_log, append_startup_log, bridge, desktop_settings,
-    lifecycle, runtime_paths, startup_task, tray, window, BackendState, DesktopSettingsCache,
-    DEFAULT_SHELL_LOCALE, DESKTOP_LOG_FILE, STARTUP_MODE_ENV,
+    active_backend_state, app_runtime_events, append_desktop_log, append_desktop_log_at,
+    append_startup_log, append_startup_log_at, bridge,
+    cli::{self, CliArgs},
+    desktop_settings, headless, instance_lock, lifecycle,
+    logging::DesktopLogLevel,
+    profiles, runtime_paths, shell_locale, startup_task, tray, window, DesktopSettingsCache,
+    DEFAULT_SHELL_LOCALE, DESKTOP_LOG_FILE, HEADLESS_ENV, STARTUP_MODE_ENV,
 };
 
 fn configure_plugins(builder: Builder<tauri::Wry>) -> Builder<tauri::Wry> {
@@ -72,15 +76,50 @@ fn configure_plugins(builder: Builder<tauri::Wry>) -> Builder<tauri::Wry> {
         ))
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
-        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
-            append_desktop_log("detected second instance launch, focusing existing main window");
-            window::actions::show_main_window(app, DEFAULT_SHELL_LOCALE, append_desktop_log);
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            handle_second_instance(app, &argv);
         }))
 }
 
+fn handle_second_instance(app_handle: &tauri::AppHandle, argv: &[String]) {
+    let cli_args = cli::parse_cli_args(argv).unwrap_or_else(|error| {
+        append_desktop_log_at(
+            DesktopLogLevel::Warn,
+            &format!("ignoring arguments forwarded by second instance: {error}"),
+        );
+        CliArgs::default()
+    });
+    let restart_only = cli_args.restart_only_options();
+    if !restart_only.is_empty() {
+        append_desktop_log_at(
+            DesktopLogLevel::Warn,
+            &format!(
+                "second instance options need a restart to apply: {}",
+                restart_only.join(", ")
+            ),
+        );
+    }
+    if let Some(locale) = cli_args.locale {
+        if let Err(error) = window::actions::set_shell_locale(
+            app_handle,
+            Some(locale),
+            DEFAULT_SHELL_LOCALE,
+            append_desktop_log,
+        ) {
+            append_desktop_log(&format!("failed to persist shell locale: {error}"));
+        }
+    }
+    if cli_args.silent {
+        append_desktop_log("detected second instance launch with --silent, keeping window state");
+        return;
+    }
+    append_desktop_log("detected second instance launch, focusing existing main window");
+    window::actions::show_main_window(app_handle, DEFAULT_SHELL_LOCALE, append_desktop_log);
+}
+
 fn configure_window_events(builder: Builder<tauri::Wry>) -> Builder<tauri::Wry> {
     builder.on_window_event(|window, event| {
-        let is_quitting = window.app_handle().state::<BackendState>().is_quitting();
+        let is_quitting = active_backend_state(window.app_handle()).is_quitting();
         let desktop_settings = window.app_handle().state::<DesktopSettingsCache>().get();
         let action = match &event {
             WindowEvent::CloseRequested { .. } => app_runtime_events::main_window_action(
@@ -133,10 +172,10 @@ fn configure_window_events(builder: Builder<tauri::Wry>) -> Builder<tauri::Wry>
 
 fn handle_page_load_started(webview: &tauri::Webview<tauri::Wry>, payload: &PageLoadPayload<'_>) {
     append_desktop_log(&format!("page-load started: {}", payload.url()));
-    let state = webview.app_handle().state::<BackendState>();
+    let state = active_backend_state(webview.app_handle());
     let action = app_runtime_events::page_load_action(
         PageLoadEvent::Started,
-        bridge::desktop::should_inject_desktop_bridge(&state.backend_url, payload.url()),
+        bridge::desktop::should_inject_desktop_bridge(&state.backend_url(), payload.url()),
         false,
     );
 
@@ -147,10 +186,10 @@ fn handle_page_load_started(webview: &tauri::Webview<tauri::Wry>, payload: &Page
 
 fn handle_page_load_finished(webview: &tauri::Webview<tauri::Wry>, payload: &PageLoadPayload<'_>) {
     append_desktop_log(&format!("page-load finished: {}", payload.url()));
-    let state = webview.app_handle().state::<BackendState>();
+    let state = active_backend_state(webview.app_handle());
     let action = app_runtime_events::page_load_action(
         PageLoadEvent::Finished,
-        bridge::desktop::should_inject_desktop_bridge(&state.backend_url, payload.url()),
+        bridge::desktop::should_inject_desktop_bridge(&state.backend_url(), payload.url()),
         window::startup_loading::should_apply_startup_loading_mode(
             webview.window().label(),
             payload.url(),
@@ -190,18 +229,47 @@ fn apply_startup_window_visibility(app_handle: &tauri::AppHandle, silent_launch:
     }
 }
 
-fn configure_setup(builder: Builder<tauri::Wry>) -> Builder<tauri::Wry> {
-    builder.setup(|app| {
+fn configure_setup(builder: Builder<tauri::Wry>, cli_args: CliArgs) -> Builder<tauri::Wry> {
+    builder.setup(move |app| {
         let app_handle = app.handle().clone();
+        // Runs after the single-instance plugin, so only a headless daemon on the
+        // same root can still hold the lock here.
+        if let Err(error) = instance_lock::acquire_instance_lock(|message| {
+            append_startup_log_at(DesktopLogLevel::Warn, message)
+        }) {
+            append_startup_log_at(DesktopLogLevel::Error, &error);
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        if let Some(locale) = cli_args.locale {
+            if let Err(error) = shell_locale::write_cached_shell_locale(
+                Some(locale),
+                runtime_paths::default_packaged_root_dir().as_deref(),
+            ) {
+                append_startup_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!("failed to persist --locale {locale}: {error}"),
+                );
+            }
+        }
         if let Err(error) = tray::setup::setup_tray(&app_handle) {
-            append_startup_log(&format!("failed to initialize tray: {error}"));
+            append_startup_log_at(
+                DesktopLogLevel::Error,
+                &format!("failed to initialize tray: {error}"),
+            );
         }
         crate::windows_shutdown::install(&app_handle);
+        crate::unix_signals::install(&app_handle);
+        crate::desktop_events::install(&app_handle);
 
         let desktop_settings = app_handle.state::<DesktopSettingsCache>().get();
-        apply_startup_window_visibility(&app_handle, desktop_settings.silent_launch);
+        apply_startup_window_visibility(
+            &app_handle,
+            desktop_settings.silent_launch || cli_args.silent,
+        );
 
         startup_task::spawn_startup_task(app_handle.clone(), append_startup_log);
+        profiles::runtime::spawn_auto_start_profiles(&app_handle);
         Ok(())
     })
 }
@@ -225,7 +293,18 @@ fn handle_run_event(app_handle: &tauri::AppHandle, event: RunEvent) {
     }
 }
 
-pub(crate) fn run() {
+pub(crate) fn run(cli_args: CliArgs) {
+    let context = tauri::generate_context!();
+    if cli_args.print_launch_plan {
+        cli::attach_parent_console();
+        std::process::exit(cli::print_launch_plan(
+            runtime_paths::packaged_resource_dir(context.package_info()).as_deref(),
+        ));
+    }
+    if headless::headless_requested(std::env::var(HEADLESS_ENV).ok().as_deref()) {
+        std::process::exit(headless::run(context.package_info()));
+    }
+
     #[cfg(target_os = "linux")]
     linux_webkit_workaround::configure(append_startup_log);
 
@@ -242,10 +321,10 @@ pub(crate) fn run() {
     let builder = configure_plugins(builder);
     let builder = configure_window_events(builder);
     let builder = configure_page_load_events(builder);
-    let builder = configure_setup(builder);
+    let builder = configure_setup(builder, cli_args);
 
     builder
-        .manage(BackendState::default())
+        .manage(profiles::runtime::load_profile_registry())
         .manage(DesktopSettingsCache::new(
             desktop_settings::read_desktop_settings(
                 runtime_paths::default_packaged_root_dir().as_deref(),
@@ -261,11 +340,15 @@ pub(crate) fn run() {
             crate::bridge::commands::desktop_bridge_set_app_update_channel,
             crate::bridge::commands::desktop_bridge_restart_backend,
             crate::bridge::commands::desktop_bridge_stop_backend,
+            crate::bridge::commands::desktop_bridge_list_profiles,
+            crate::bridge::commands::desktop_bridge_switch_profile,
             crate::bridge::commands::desktop_bridge_open_external_url,
+            crate::bridge::commands::desktop_bridge_query_logs,
+            crate::bridge::commands::desktop_bridge_export_diagnostics,
             crate::bridge::commands::desktop_bridge_check_app_update,
             crate::bridge::commands::desktop_bridge_install_app_update
         ])
-        .build(tauri::generate_context!())
+        .build(context)
         .expect("error while building tauri application")
         .run(handle_run_event);
 }
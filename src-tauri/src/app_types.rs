@@ -1,26 +1,34 @@
 use serde::Deserialize;
 use std::{
-    env,
     path::PathBuf,
     process::Child,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU32},
         Arc, Mutex,
     },
 };
-use tauri::menu::{CheckMenuItem, MenuItem};
+use tauri::menu::{CheckMenuItem, MenuItem, Submenu};
 
-use crate::{backend, desktop_auth::DesktopSessionSecret, exit_state, DEFAULT_BACKEND_URL};
+use crate::{
+    backend, desktop_auth::DesktopSessionSecret, exit_state, launch_plan::LaunchOverrides,
+    profiles, BACKEND_OUTPUT_BUFFER_LINES, DEFAULT_BACKEND_URL,
+};
 
 #[derive(Clone)]
 pub(crate) struct TrayMenuState {
+    pub(crate) backend_status_item: MenuItem<tauri::Wry>,
     pub(crate) toggle_item: MenuItem<tauri::Wry>,
     pub(crate) reload_item: MenuItem<tauri::Wry>,
     pub(crate) restart_backend_item: MenuItem<tauri::Wry>,
+    pub(crate) export_diagnostics_item: MenuItem<tauri::Wry>,
+    pub(crate) profiles_submenu: Option<Submenu<tauri::Wry>>,
+    pub(crate) profile_items: Vec<(String, CheckMenuItem<tauri::Wry>)>,
     pub(crate) launch_at_login_item: CheckMenuItem<tauri::Wry>,
     pub(crate) silent_launch_item: CheckMenuItem<tauri::Wry>,
     pub(crate) close_to_tray_item: CheckMenuItem<tauri::Wry>,
+    pub(crate) detach_on_quit_item: CheckMenuItem<tauri::Wry>,
     pub(crate) quit_item: MenuItem<tauri::Wry>,
+    pub(crate) stop_and_quit_item: MenuItem<tauri::Wry>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,20 +45,38 @@ pub(crate) struct LaunchPlan {
     pub(crate) root_dir: Option<PathBuf>,
     pub(crate) webui_dir: Option<PathBuf>,
     pub(crate) startup_heartbeat_path: Option<PathBuf>,
+    pub(crate) dashboard_port: Option<u16>,
+    pub(crate) runtime_dir: Option<PathBuf>,
     pub(crate) packaged_mode: bool,
 }
 
 #[derive(Debug)]
 pub(crate) struct BackendState {
     pub(crate) child: Mutex<Option<Child>>,
-    pub(crate) backend_url: String,
+    pub(crate) profile_id: String,
+    // Starts as the configured URL; moves when the dashboard port gets relocated.
+    pub(crate) backend_url: Mutex<String>,
+    pub(crate) relocated_dashboard_port: Mutex<Option<u16>>,
+    pub(crate) attached_backend: Mutex<Option<backend::attach::AttachedBackend>>,
+    pub(crate) launch_overrides: LaunchOverrides,
     pub(crate) restart_auth_token: Mutex<Option<String>>,
     pub(crate) desktop_session_secret: DesktopSessionSecret,
+    // Set while attached to a backend detached by an earlier session.
+    pub(crate) adopted_session_secret: Mutex<Option<DesktopSessionSecret>>,
     pub(crate) startup_loading_mode: Mutex<Option<&'static str>>,
-    pub(crate) log_rotator_stop: Mutex<Option<Arc<AtomicBool>>>,
-    pub(crate) exit_state: Mutex<exit_state::ExitStateMachine>,
-    pub(crate) is_spawning: AtomicBool,
-    pub(crate) is_restarting: AtomicBool,
+    pub(crate) backend_output: Arc<backend::output::BackendOutputBuffer>,
+    pub(crate) supervisor_stop: Mutex<Option<Arc<AtomicBool>>>,
+    pub(crate) crash_restarts: Mutex<backend::supervisor::CrashRestartTracker>,
+    pub(crate) liveness_watchdog_stop: Mutex<Option<Arc<AtomicBool>>>,
+    pub(crate) resource_monitor_stop: Mutex<Option<Arc<AtomicBool>>>,
+    pub(crate) resource_usage: Mutex<Option<backend::resources::BackendResourceUsage>>,
+    // Shared by every profile: quitting is a shell-wide decision.
+    pub(crate) exit_state: Arc<Mutex<exit_state::ExitStateMachine>>,
+    pub(crate) lifecycle: Mutex<backend::state_machine::BackendLifecycle>,
+    pub(crate) startup_cancel: Mutex<Option<Arc<AtomicBool>>>,
+    pub(crate) startup_progress: Mutex<Option<backend::startup_progress::StartupProgress>>,
+    // Pid of this profile's spawned backend child, 0 when none; tags its log records.
+    pub(crate) backend_pid: AtomicU32,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -60,6 +86,9 @@ pub(crate) struct BackendBridgeState {
     pub(crate) spawning: bool,
     pub(crate) restarting: bool,
     pub(crate) can_manage: bool,
+    pub(crate) attached: bool,
+    pub(crate) resources: Option<backend::resources::BackendResourceUsage>,
+    pub(crate) lifecycle: backend::state_machine::BackendLifecycleSnapshot,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -77,76 +106,49 @@ pub(crate) struct DesktopAuthBridgeResult {
     pub(crate) reason: Option<String>,
 }
 
-pub(crate) struct AtomicFlagGuard<'a> {
-    flag: &'a AtomicBool,
-}
-
-impl<'a> AtomicFlagGuard<'a> {
-    pub(crate) fn set(flag: &'a AtomicBool) -> Self {
-        flag.store(true, Ordering::Relaxed);
-        Self { flag }
-    }
-
-    pub(crate) fn try_set(flag: &'a AtomicBool) -> Option<Self> {
-        flag.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
-            .ok()?;
-        Some(Self { flag })
-    }
-}
-
-impl Drop for AtomicFlagGuard<'_> {
-    fn drop(&mut self) {
-        self.flag.store(false, Ordering::Relaxed);
-    }
-}
-
-impl Default for BackendState {
-    fn default() -> Self {
+impl BackendState {
+    pub(crate) fn new(
+        profile_id: String,
+        backend_url: String,
+        launch_overrides: LaunchOverrides,
+        exit_state: Arc<Mutex<exit_state::ExitStateMachine>>,
+    ) -> Self {
         Self {
             child: Mutex::new(None),
-            backend_url: backend::config::normalize_backend_url(
-                &env::var("ASTRBOT_BACKEND_URL")
-                    .unwrap_or_else(|_| DEFAULT_BACKEND_URL.to_string()),
-                DEFAULT_BACKEND_URL,
-            ),
+            profile_id,
+            backend_url: Mutex::new(backend_url),
+            relocated_dashboard_port: Mutex::new(None),
+            attached_backend: Mutex::new(None),
+            launch_overrides,
             restart_auth_token: Mutex::new(None),
             desktop_session_secret: DesktopSessionSecret::generate()
                 .expect("failed to generate secure desktop session secret"),
+            adopted_session_secret: Mutex::new(None),
             startup_loading_mode: Mutex::new(None),
-            log_rotator_stop: Mutex::new(None),
-            exit_state: Mutex::new(exit_state::ExitStateMachine::default()),
-            is_spawning: AtomicBool::new(false),
-            is_restarting: AtomicBool::new(false),
+            backend_output: Arc::new(backend::output::BackendOutputBuffer::new(
+                BACKEND_OUTPUT_BUFFER_LINES,
+            )),
+            supervisor_stop: Mutex::new(None),
+            crash_restarts: Mutex::new(backend::supervisor::CrashRestartTracker::default()),
+            liveness_watchdog_stop: Mutex::new(None),
+            resource_monitor_stop: Mutex::new(None),
+            resource_usage: Mutex::new(None),
+            exit_state,
+            lifecycle: Mutex::default(),
+            startup_cancel: Mutex::new(None),
+            startup_progress: Mutex::new(None),
+            backend_pid: AtomicU32::new(0),
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::sync::atomic::{AtomicBool, Ordering};
-
-    use super::AtomicFlagGuard;
-
-    #[test]
-    fn atomic_flag_guard_set_resets_flag_on_drop() {
-        let flag = AtomicBool::new(false);
-        {
-            let _guard = AtomicFlagGuard::set(&flag);
-            assert!(flag.load(Ordering::Relaxed));
-        }
-        assert!(!flag.load(Ordering::Relaxed));
-    }
-
-    #[test]
-    fn atomic_flag_guard_try_set_rejects_double_set_until_drop() {
-        let flag = AtomicBool::new(false);
-
-        let guard = AtomicFlagGuard::try_set(&flag).expect("first set should succeed");
-        assert!(flag.load(Ordering::Relaxed));
-        assert!(AtomicFlagGuard::try_set(&flag).is_none());
-
-        drop(guard);
-        assert!(!flag.load(Ordering::Relaxed));
-        assert!(AtomicFlagGuard::try_set(&flag).is_some());
+impl Default for BackendState {
+    fn default() -> Self {
+        Self::new(
+            profiles::config::DEFAULT_PROFILE_ID.to_string(),
+            backend::config::backend_url_from_env(DEFAULT_BACKEND_URL),
+            LaunchOverrides::from_env(),
+            Arc::default(),
+        )
     }
 }
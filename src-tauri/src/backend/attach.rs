@@ -0,0 +1,174 @@
+use std::sync::TryLockError;
+
+use crate::{append_desktop_log, backend, backend::detach::ReattachedBackend, BackendState};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AttachedBackend {
+    pub(crate) start_time: i64,
+    pub(crate) version: String,
+    // Set when the backend was spawned by an earlier desktop session that detached on quit.
+    pub(crate) reattached: Option<ReattachedBackend>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BackendOwnership {
+    Managed,
+    Reattached,
+    Attached,
+    Absent,
+}
+
+fn resolve_backend_ownership(
+    has_managed_child: bool,
+    attached: Option<&AttachedBackend>,
+) -> BackendOwnership {
+    match attached {
+        _ if has_managed_child => BackendOwnership::Managed,
+        Some(attached) if attached.reattached.is_some() => BackendOwnership::Reattached,
+        Some(_) => BackendOwnership::Attached,
+        None => BackendOwnership::Absent,
+    }
+}
+
+impl BackendState {
+    /// An AstrBot dashboard answers both the start-time and version endpoints with
+    /// its `{"status": "ok", "data": ...}` envelope; anything else is not ours to adopt.
+    pub(crate) fn identify_running_backend(&self) -> Option<AttachedBackend> {
+        if !self.ping_backend(backend::runtime::backend_ping_timeout_ms(
+            append_desktop_log,
+        )) {
+            return None;
+        }
+        let start_time = self.fetch_backend_start_time()?;
+        let version = self.fetch_backend_version()?;
+        Some(AttachedBackend {
+            start_time,
+            version,
+            reattached: None,
+        })
+    }
+
+    pub(crate) fn attach_running_backend(&self, attached: AttachedBackend) {
+        let kind = match attached.reattached.as_ref() {
+            Some(reattached) => format!(
+                "detached backend from last session (pid {})",
+                reattached.pid
+            ),
+            None => "running backend (unmanaged)".to_string(),
+        };
+        append_desktop_log(&format!(
+            "attached to {kind}: profile={}, url={}, version={}, start_time={}",
+            self.profile_id,
+            self.backend_url(),
+            attached.version,
+            attached.start_time
+        ));
+        match self.attached_backend.lock() {
+            Ok(mut guard) => *guard = Some(attached),
+            Err(error) => *error.into_inner() = Some(attached),
+        }
+        self.transition_backend_phase(backend::state_machine::BackendPhase::Ready);
+    }
+
+    pub(crate) fn clear_attached_backend(&self) {
+        match self.attached_backend.lock() {
+            Ok(mut guard) => *guard = None,
+            Err(error) => *error.into_inner() = None,
+        }
+        self.clear_adopted_session_secret();
+    }
+
+    pub(crate) fn attached_backend(&self) -> Option<AttachedBackend> {
+        match self.attached_backend.lock() {
+            Ok(guard) => guard.clone(),
+            Err(error) => error.into_inner().clone(),
+        }
+    }
+
+    pub(crate) fn has_live_managed_child(&self) -> Result<bool, String> {
+        let mut guard = self
+            .child
+            .lock()
+            .map_err(|_| "Backend process lock poisoned.".to_string())?;
+        Ok(match guard.as_mut() {
+            Some(child) => matches!(child.try_wait(), Ok(None)),
+            None => false,
+        })
+    }
+
+    pub(crate) fn managed_child_pid(&self) -> Option<u32> {
+        let guard = match self.child.lock() {
+            Ok(guard) => guard,
+            Err(error) => error.into_inner(),
+        };
+        guard.as_ref().map(|child| child.id())
+    }
+
+    /// Never blocks: the tray calls this on the main thread while a stop may hold the child lock.
+    pub(crate) fn backend_ownership(&self) -> BackendOwnership {
+        let has_managed_child = match self.child.try_lock() {
+            Ok(guard) => guard.is_some(),
+            Err(TryLockError::WouldBlock) => true,
+            Err(TryLockError::Poisoned(error)) => error.into_inner().is_some(),
+        };
+        resolve_backend_ownership(has_managed_child, self.attached_backend().as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{resolve_backend_ownership, AttachedBackend, BackendOwnership};
+    use crate::{backend::detach::ReattachedBackend, BackendState};
+
+    fn attached(reattached: Option<ReattachedBackend>) -> AttachedBackend {
+        AttachedBackend {
+            start_time: 42,
+            version: "4.5.0".to_string(),
+            reattached,
+        }
+    }
+
+    #[test]
+    fn resolve_backend_ownership_prefers_managed_child() {
+        let unmanaged = attached(None);
+        let reattached = attached(Some(ReattachedBackend {
+            pid: 4242,
+            process_start_time: None,
+            record_path: PathBuf::from("data/desktop-detached-backend.json"),
+        }));
+        assert_eq!(
+            resolve_backend_ownership(true, Some(&unmanaged)),
+            BackendOwnership::Managed
+        );
+        assert_eq!(
+            resolve_backend_ownership(false, Some(&unmanaged)),
+            BackendOwnership::Attached
+        );
+        assert_eq!(
+            resolve_backend_ownership(false, Some(&reattached)),
+            BackendOwnership::Reattached
+        );
+        assert_eq!(
+            resolve_backend_ownership(false, None),
+            BackendOwnership::Absent
+        );
+    }
+
+    #[test]
+    fn attached_backend_round_trips_and_clears() {
+        let state = BackendState::default();
+        assert_eq!(state.backend_ownership(), BackendOwnership::Absent);
+
+        *state.attached_backend.lock().expect("attached lock") = Some(attached(None));
+        assert_eq!(state.backend_ownership(), BackendOwnership::Attached);
+        assert_eq!(
+            state.attached_backend().map(|attached| attached.start_time),
+            Some(42)
+        );
+
+        state.clear_attached_backend();
+        assert_eq!(state.backend_ownership(), BackendOwnership::Absent);
+    }
+}
@@ -3,13 +3,17 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 use url::Url;
 
-#[derive(Debug, Clone)]
+use crate::backend::readiness_probe::ReadinessProbe;
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BackendReadinessConfig {
     pub path: String,
     pub probe_timeout_ms: u64,
     pub poll_interval_ms: u64,
     pub startup_idle_timeout_ms: u64,
     pub startup_heartbeat_path: Option<PathBuf>,
+    pub probes: Vec<ReadinessProbe>,
 }
 
 pub fn resolve_backend_ready_http_path<F>(env_name: &str, default_path: &str, mut log: F) -> String
@@ -86,6 +90,40 @@ where
     }
 }
 
+pub fn parse_clamped_u64<F>(
+    raw: &str,
+    source: &str,
+    fallback: u64,
+    min: u64,
+    max: u64,
+    mut log: F,
+) -> u64
+where
+    F: FnMut(String),
+{
+    match raw.trim().parse::<u128>() {
+        Ok(parsed) if parsed > 0 => {
+            if parsed < min as u128 {
+                log(format!(
+                    "{source}='{raw}' is below minimum {min}, clamped to {min}"
+                ));
+                min
+            } else if parsed > max as u128 {
+                log(format!(
+                    "{source}='{raw}' is above maximum {max}, clamped to {max}"
+                ));
+                max
+            } else {
+                parsed as u64
+            }
+        }
+        _ => {
+            log(format!("invalid {source}='{raw}', fallback to {fallback}"));
+            fallback
+        }
+    }
+}
+
 pub fn parse_ping_timeout_env<F>(
     raw: &str,
     env_name: &str,
@@ -114,19 +152,47 @@ where
     parse_clamped_timeout_env(raw, env_name, fallback_ms, min_ms, max_ms, log)
 }
 
+/// `0` turns the graceful shutdown request off; other values are clamped like timeouts.
+pub fn resolve_backend_shutdown_grace_ms<F>(
+    raw: &str,
+    env_name: &str,
+    fallback_ms: u64,
+    min_ms: u64,
+    max_ms: u64,
+    log: F,
+) -> u64
+where
+    F: FnMut(String),
+{
+    if raw.trim() == "0" {
+        return 0;
+    }
+    parse_clamped_timeout_env(raw, env_name, fallback_ms, min_ms, max_ms, log)
+}
+
 pub fn resolve_backend_startup_heartbeat_path(
     root_dir: Option<&Path>,
     packaged_root: Option<PathBuf>,
     relative_path: &str,
+) -> Option<PathBuf> {
+    // Prefer the launch plan's resolved root so spawn-time and readiness-time heartbeat paths
+    // stay aligned.
+    resolve_backend_runtime_data_path(root_dir, packaged_root, relative_path)
+}
+
+/// Resolves a desktop-owned runtime file (pid record, detached-backend record, ...)
+/// under the backend's root: the launch plan's root, then `ASTRBOT_ROOT` for call
+/// sites without a plan root, then the packaged root.
+pub fn resolve_backend_runtime_data_path(
+    root_dir: Option<&Path>,
+    packaged_root: Option<PathBuf>,
+    relative_path: &str,
 ) -> Option<PathBuf> {
     let trimmed = relative_path.trim();
     if trimmed.is_empty() {
         return None;
     }
 
-    // Prefer the launch plan's resolved root so spawn-time and readiness-time heartbeat paths
-    // stay aligned. Falling back to ASTRBOT_ROOT only helps older/custom call sites that do not
-    // pass a root dir; packaged launches may finally fall back to the default packaged root.
     if let Some(root) = root_dir {
         return Some(root.join(trimmed));
     }
@@ -231,6 +297,13 @@ pub fn normalize_backend_url(raw: &str, default_backend_url: &str) -> String {
     }
 }
 
+pub fn backend_url_from_env(default_backend_url: &str) -> String {
+    normalize_backend_url(
+        &env::var("ASTRBOT_BACKEND_URL").unwrap_or_else(|_| default_backend_url.to_string()),
+        default_backend_url,
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn backend_readiness_config<F>(
     ready_http_path_env: &str,
@@ -262,6 +335,7 @@ where
         &mut log,
     );
     BackendReadinessConfig {
+        probes: vec![ReadinessProbe::default_http(&path)],
         path,
         probe_timeout_ms,
         poll_interval_ms,
@@ -300,12 +374,50 @@ mod tests {
         assert!(logs.iter().any(|line| line.contains("invalid TEST_ENV")));
     }
 
+    #[test]
+    fn parse_clamped_u64_clamps_and_falls_back() {
+        let mut messages = Vec::new();
+        assert_eq!(
+            parse_clamped_u64("5", "TEST", 20, 10, 100, |m| messages.push(m)),
+            10
+        );
+        assert_eq!(
+            parse_clamped_u64("500", "TEST", 20, 10, 100, |m| messages.push(m)),
+            100
+        );
+        assert_eq!(
+            parse_clamped_u64("abc", "TEST", 20, 10, 100, |m| messages.push(m)),
+            20
+        );
+        assert_eq!(
+            parse_clamped_u64(" 42 ", "TEST", 20, 10, 100, |m| messages.push(m)),
+            42
+        );
+        assert_eq!(messages.len(), 3);
+    }
+
     #[test]
     fn parse_ping_timeout_delegates_to_clamp() {
         let value = parse_ping_timeout_env("99999", "TEST_ENV", 500, 100, 3_000, |_| {});
         assert_eq!(value, 3_000);
     }
 
+    #[test]
+    fn resolve_backend_shutdown_grace_allows_zero_to_disable() {
+        assert_eq!(
+            resolve_backend_shutdown_grace_ms(" 0 ", "TEST_GRACE_ENV", 5_000, 500, 60_000, |_| {}),
+            0
+        );
+        assert_eq!(
+            resolve_backend_shutdown_grace_ms("100", "TEST_GRACE_ENV", 5_000, 500, 60_000, |_| {}),
+            500
+        );
+        assert_eq!(
+            resolve_backend_shutdown_grace_ms("soon", "TEST_GRACE_ENV", 5_000, 500, 60_000, |_| {}),
+            5_000
+        );
+    }
+
     #[test]
     fn resolve_backend_startup_idle_timeout_clamps_large_value() {
         let value = resolve_backend_startup_idle_timeout_ms(
@@ -347,6 +459,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_backend_runtime_data_path_joins_relative_path_under_root() {
+        assert_eq!(
+            resolve_backend_runtime_data_path(
+                Some(Path::new("/tmp/astrbot-root")),
+                Some(PathBuf::from("/tmp/packaged-root")),
+                "data/desktop-backend-pid.json",
+            ),
+            Some(PathBuf::from("/tmp/astrbot-root").join("data/desktop-backend-pid.json"))
+        );
+        assert_eq!(
+            resolve_backend_runtime_data_path(Some(Path::new("/tmp/astrbot-root")), None, "  "),
+            None
+        );
+    }
+
     #[test]
     fn resolve_backend_timeout_uses_packaged_fallback_when_zero() {
         let timeout = resolve_backend_timeout_ms(true, "TEST_TIMEOUT_ENV_MISSING", 20_000, 300_000);
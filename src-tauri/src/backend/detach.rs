@@ -0,0 +1,406 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    append_desktop_log, append_desktop_log_at,
+    backend::{self, attach::AttachedBackend, host::BackendHost},
+    desktop_auth::DesktopSessionSecret,
+    logging::DesktopLogLevel,
+    process_control, runtime_paths, BackendState, BACKEND_DETACHED_RECORD_RELATIVE_PATH,
+    BACKEND_SHUTDOWN_REQUEST_TIMEOUT_MS, GRACEFUL_STOP_TIMEOUT_MS,
+};
+
+/// Written when the desktop quits without stopping its backend, so the next launch can adopt it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DetachedBackendRecord {
+    pid: u32,
+    url: String,
+    profile: String,
+    #[serde(default)]
+    process_start_time: Option<u64>,
+    desktop_session_secret: String,
+    detached_at_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReattachedBackend {
+    pub(crate) pid: u32,
+    pub(crate) process_start_time: Option<u64>,
+    pub(crate) record_path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DetachedRecordCheck {
+    Adoptable,
+    OtherProfile,
+    ProcessGone,
+}
+
+fn check_detached_record(
+    record: &DetachedBackendRecord,
+    profile_id: &str,
+    observed_start_time: Option<u64>,
+) -> DetachedRecordCheck {
+    if record.profile != profile_id {
+        return DetachedRecordCheck::OtherProfile;
+    }
+    match record.process_start_time {
+        Some(expected) if observed_start_time != Some(expected) => DetachedRecordCheck::ProcessGone,
+        _ => DetachedRecordCheck::Adoptable,
+    }
+}
+
+fn observed_process_start_time(pid: u32) -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        process_control::process_start_time(pid)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+fn unix_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn read_detached_record(path: &Path) -> Option<DetachedBackendRecord> {
+    let payload = fs::read(path).ok()?;
+    match serde_json::from_slice(&payload) {
+        Ok(record) => Some(record),
+        Err(error) => {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "discarding unreadable detached backend record {}: {error}",
+                    path.display()
+                ),
+            );
+            remove_detached_record(path);
+            None
+        }
+    }
+}
+
+fn write_detached_record(path: &Path, record: &DetachedBackendRecord) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let payload = serde_json::to_vec(record).map_err(|error| error.to_string())?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // The record carries the desktop session secret.
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    io::Write::write_all(
+        &mut options.open(path).map_err(|error| error.to_string())?,
+        &payload,
+    )
+    .map_err(|error| error.to_string())
+}
+
+fn remove_detached_record(path: &Path) {
+    if let Err(error) = fs::remove_file(path) {
+        if error.kind() != io::ErrorKind::NotFound {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "failed to remove detached backend record {}: {error}",
+                    path.display()
+                ),
+            );
+        }
+    }
+}
+
+impl BackendState {
+    fn detached_record_path(&self, app: &impl BackendHost) -> Option<PathBuf> {
+        let plan = self.resolve_launch_plan(app).ok()?;
+        backend::config::resolve_backend_runtime_data_path(
+            plan.root_dir.as_deref(),
+            runtime_paths::default_packaged_root_dir(),
+            BACKEND_DETACHED_RECORD_RELATIVE_PATH,
+        )
+    }
+
+    pub(crate) fn reattached_backend(&self) -> Option<ReattachedBackend> {
+        self.attached_backend()?.reattached
+    }
+
+    /// A reattached backend only accepts the secret it was spawned with.
+    pub(crate) fn desktop_session_secret_for_requests(&self) -> String {
+        let guard = match self.adopted_session_secret.lock() {
+            Ok(guard) => guard,
+            Err(error) => error.into_inner(),
+        };
+        guard
+            .as_ref()
+            .unwrap_or(&self.desktop_session_secret)
+            .as_str()
+            .to_string()
+    }
+
+    fn set_adopted_session_secret(&self, secret: Option<DesktopSessionSecret>) {
+        match self.adopted_session_secret.lock() {
+            Ok(mut guard) => *guard = secret,
+            Err(error) => *error.into_inner() = secret,
+        }
+    }
+
+    pub(crate) fn clear_adopted_session_secret(&self) {
+        self.set_adopted_session_secret(None);
+    }
+
+    /// Leaves the managed backend running past the desktop's exit. Returns `false` when
+    /// there is nothing to detach or the record cannot be written, so the caller stops it.
+    pub(crate) fn detach_backend_for_exit(&self, app: &impl BackendHost) -> Result<bool, String> {
+        if let Some(reattached) = self.reattached_backend() {
+            self.append_desktop_log(&format!(
+                "leaving reattached backend running: profile={}, pid={}",
+                self.profile_id, reattached.pid
+            ));
+            return Ok(true);
+        }
+        if !self.has_live_managed_child()? {
+            return Ok(false);
+        }
+        let Some(path) = self.detached_record_path(app) else {
+            self.append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                "detached backend record path is unavailable; stopping backend instead",
+            );
+            return Ok(false);
+        };
+        // Custom and dev commands lack the launcher that survives our pipes closing.
+        let plan = match self.resolve_launch_plan(app) {
+            Ok(plan) if plan.packaged_mode => plan,
+            _ => {
+                self.append_desktop_log_at(
+                    DesktopLogLevel::Warn,
+                    "detach on quit needs the packaged backend launcher; stopping backend instead",
+                );
+                return Ok(false);
+            }
+        };
+
+        self.stop_backend_supervisor();
+        self.stop_liveness_watchdog();
+        self.stop_resource_monitor();
+        let mut guard = self
+            .child
+            .lock()
+            .map_err(|_| "Backend process lock poisoned.".to_string())?;
+        let Some(pid) = guard.as_ref().map(|child| child.id()) else {
+            return Ok(false);
+        };
+        let record = DetachedBackendRecord {
+            pid,
+            url: self.backend_url(),
+            profile: self.profile_id.clone(),
+            process_start_time: observed_process_start_time(pid),
+            desktop_session_secret: self.desktop_session_secret_for_requests(),
+            detached_at_ms: unix_time_ms(),
+        };
+        if let Err(error) = write_detached_record(&path, &record) {
+            self.append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "failed to write detached backend record {}: {error}; stopping backend instead",
+                    path.display()
+                ),
+            );
+            return Ok(false);
+        }
+        self.release_detached_backend_pid_record(&plan, pid);
+
+        // Dropping `Child` neither kills nor waits for the process.
+        *guard = None;
+        self.set_backend_pid(None);
+        self.append_desktop_log(&format!(
+            "detached backend left running after quit: profile={}, pid={pid}, url={}",
+            self.profile_id, record.url
+        ));
+        Ok(true)
+    }
+
+    /// Adopts the backend a previous session detached, verified through the usual identity
+    /// probes rather than trusting the record alone.
+    pub(crate) fn reattach_detached_backend(&self, app: &impl BackendHost) -> bool {
+        let Some(path) = self.detached_record_path(app) else {
+            return false;
+        };
+        let Some(record) = read_detached_record(&path) else {
+            return false;
+        };
+        match check_detached_record(
+            &record,
+            &self.profile_id,
+            observed_process_start_time(record.pid),
+        ) {
+            DetachedRecordCheck::Adoptable => {}
+            DetachedRecordCheck::OtherProfile => {
+                self.append_desktop_log(&format!(
+                    "ignoring detached backend record for profile {}: pid={}",
+                    record.profile, record.pid
+                ));
+                return false;
+            }
+            DetachedRecordCheck::ProcessGone => {
+                self.append_desktop_log(&format!(
+                    "detached backend from last session is gone: pid={}",
+                    record.pid
+                ));
+                remove_detached_record(&path);
+                return false;
+            }
+        }
+
+        let previous = self.backend_url();
+        let moved_port = (record.url != previous)
+            .then(|| Url::parse(&record.url).ok()?.port_or_known_default())
+            .flatten();
+        let previous_port = self.relocated_dashboard_port();
+        if let Some(port) = moved_port {
+            self.set_relocated_backend_url(record.url.clone(), port);
+        }
+
+        let Some(identified) = self.identify_running_backend() else {
+            self.append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "detached backend from last session did not answer at {}: pid={}",
+                    record.url, record.pid
+                ),
+            );
+            if moved_port.is_some() {
+                self.restore_backend_url(previous, previous_port);
+            }
+            remove_detached_record(&path);
+            return false;
+        };
+
+        self.set_adopted_session_secret(DesktopSessionSecret::from_persisted(
+            &record.desktop_session_secret,
+        ));
+        self.attach_running_backend(AttachedBackend {
+            reattached: Some(ReattachedBackend {
+                pid: record.pid,
+                process_start_time: record.process_start_time,
+                record_path: path,
+            }),
+            ..identified
+        });
+        true
+    }
+
+    /// "Stop bot and quit" for a backend adopted from a detached record: ask it to shut
+    /// down first, then terminate whatever is left of its process tree.
+    pub(crate) fn stop_reattached_backend(&self) -> Result<(), String> {
+        let Some(reattached) = self.reattached_backend() else {
+            return Ok(());
+        };
+        let pid = reattached.pid;
+        let grace_ms = backend::runtime::backend_shutdown_grace_ms(append_desktop_log);
+        if grace_ms > 0
+            && self
+                .request_backend_shutdown(BACKEND_SHUTDOWN_REQUEST_TIMEOUT_MS)
+                .is_some_and(|status| (200..300).contains(&status))
+        {
+            let started = Instant::now();
+            let ping_timeout_ms = backend::runtime::backend_ping_timeout_ms(append_desktop_log);
+            while started.elapsed() < Duration::from_millis(grace_ms)
+                && self.ping_backend(ping_timeout_ms)
+            {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+        }
+
+        let stopped = process_control::terminate_detached_process_tree(
+            pid,
+            reattached.process_start_time,
+            Duration::from_millis(GRACEFUL_STOP_TIMEOUT_MS),
+            append_desktop_log_at,
+        );
+        if !stopped {
+            return Err(format!("Reattached backend did not exit: pid={pid}."));
+        }
+        self.append_desktop_log(&format!(
+            "stopped reattached backend: profile={}, pid={pid}",
+            self.profile_id
+        ));
+        remove_detached_record(&reattached.record_path);
+        self.clear_attached_backend();
+        self.transition_backend_phase(backend::state_machine::BackendPhase::Stopped);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_detached_record, DetachedBackendRecord, DetachedRecordCheck};
+
+    fn record() -> DetachedBackendRecord {
+        DetachedBackendRecord {
+            pid: 4242,
+            url: "http://127.0.0.1:6185/".to_string(),
+            profile: "default".to_string(),
+            process_start_time: Some(987_654),
+            desktop_session_secret: "ab".repeat(32),
+            detached_at_ms: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn detached_record_requires_matching_profile_and_process() {
+        assert_eq!(
+            check_detached_record(&record(), "default", Some(987_654)),
+            DetachedRecordCheck::Adoptable
+        );
+        assert_eq!(
+            check_detached_record(&record(), "customer-a", Some(987_654)),
+            DetachedRecordCheck::OtherProfile
+        );
+        assert_eq!(
+            check_detached_record(&record(), "default", Some(1)),
+            DetachedRecordCheck::ProcessGone
+        );
+        assert_eq!(
+            check_detached_record(&record(), "default", None),
+            DetachedRecordCheck::ProcessGone
+        );
+    }
+
+    #[test]
+    fn detached_record_without_start_time_defers_to_identity_probes() {
+        let record = DetachedBackendRecord {
+            process_start_time: None,
+            ..record()
+        };
+        assert_eq!(
+            check_detached_record(&record, "default", None),
+            DetachedRecordCheck::Adoptable
+        );
+    }
+
+    #[test]
+    fn detached_record_round_trips_as_camel_case_json() {
+        let payload = serde_json::to_value(record()).expect("serialize record");
+        assert_eq!(payload["processStartTime"], 987_654);
+        assert_eq!(payload["detachedAtMs"], 1_700_000_000_000_u64);
+        let parsed: DetachedBackendRecord =
+            serde_json::from_value(payload).expect("deserialize record");
+        assert_eq!(parsed, record());
+    }
+}
@@ -0,0 +1,49 @@
+use std::{path::PathBuf, sync::Arc};
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    append_desktop_log, backend::port::PortRelocation, profiles::registry::ProfileRegistry, tray,
+    BackendState, DEFAULT_SHELL_LOCALE,
+};
+
+/// What the backend lifecycle needs from whoever hosts it: the desktop app or
+/// the headless daemon.
+pub(crate) trait BackendHost: Clone + Send + Sync + 'static {
+    fn profile_state(&self, profile_id: &str) -> Option<Arc<BackendState>>;
+
+    fn resource_dir(&self) -> Option<PathBuf>;
+
+    fn backend_status_changed(&self);
+
+    fn dashboard_port_relocated(&self, relocation: PortRelocation);
+
+    /// The crash-loop guard stopped restarting this profile's backend.
+    fn backend_supervision_gave_up(&self, profile_id: &str);
+}
+
+impl BackendHost for AppHandle {
+    fn profile_state(&self, profile_id: &str) -> Option<Arc<BackendState>> {
+        self.state::<ProfileRegistry>().state(profile_id)
+    }
+
+    fn resource_dir(&self) -> Option<PathBuf> {
+        self.path().resource_dir().ok()
+    }
+
+    fn backend_status_changed(&self) {
+        tray::labels::update_backend_status_label(self, DEFAULT_SHELL_LOCALE, append_desktop_log);
+    }
+
+    fn dashboard_port_relocated(&self, relocation: PortRelocation) {
+        tray::labels::show_port_relocation_tooltip(
+            self,
+            DEFAULT_SHELL_LOCALE,
+            relocation,
+            append_desktop_log,
+        );
+    }
+
+    // The backend stays stopped and the tray shows it; the user decides what to do next.
+    fn backend_supervision_gave_up(&self, _profile_id: &str) {}
+}
@@ -8,8 +8,12 @@ use url::Url;
 
 use crate::{
     backend::http_response,
-    desktop_auth::{DesktopAuthSession, DESKTOP_SESSION_ENDPOINT, DESKTOP_SESSION_HEADER},
-    BackendState, DESKTOP_AUTH_REQUEST_TIMEOUT_MS, GRACEFUL_RESTART_START_TIME_TIMEOUT_MS,
+    desktop_auth::{
+        DesktopAuthSession, DESKTOP_SESSION_ENDPOINT, DESKTOP_SESSION_HEADER,
+        DESKTOP_SHUTDOWN_ENDPOINT,
+    },
+    BackendState, BACKEND_VERSION_ENDPOINT, DESKTOP_AUTH_REQUEST_TIMEOUT_MS,
+    GRACEFUL_RESTART_START_TIME_TIMEOUT_MS,
 };
 
 #[derive(Default)]
@@ -21,7 +25,7 @@ struct BackendRequestOptions<'a> {
 
 impl BackendState {
     pub(crate) fn ping_backend(&self, timeout_ms: u64) -> bool {
-        let parsed = match Url::parse(&self.backend_url) {
+        let parsed = match Url::parse(&self.backend_url()) {
             Ok(url) => url,
             Err(_) => return false,
         };
@@ -69,7 +73,7 @@ impl BackendState {
         body: Option<&str>,
         options: BackendRequestOptions<'_>,
     ) -> Option<Vec<u8>> {
-        let base = Url::parse(&self.backend_url).ok()?;
+        let base = Url::parse(&self.backend_url()).ok()?;
         let request_url = base.join(api_path).ok()?;
         if request_url.scheme() != "http" {
             return None;
@@ -194,14 +198,26 @@ Content-Length: {}\r\n\
         http_response::parse_backend_start_time(&payload)
     }
 
+    pub(crate) fn fetch_backend_version(&self) -> Option<String> {
+        let payload = self.request_backend_json(
+            "GET",
+            BACKEND_VERSION_ENDPOINT,
+            GRACEFUL_RESTART_START_TIME_TIMEOUT_MS,
+            None,
+            None,
+        )?;
+        http_response::parse_backend_version(&payload)
+    }
+
     pub(crate) fn request_desktop_auth_session(&self) -> Option<DesktopAuthSession> {
+        let session_secret = self.desktop_session_secret_for_requests();
         let response = self.request_backend_response_bytes_internal(
             "POST",
             DESKTOP_SESSION_ENDPOINT,
             DESKTOP_AUTH_REQUEST_TIMEOUT_MS,
             Some("{}"),
             BackendRequestOptions {
-                desktop_session_secret: Some(self.desktop_session_secret.as_str()),
+                desktop_session_secret: Some(session_secret.as_str()),
                 require_loopback: true,
                 ..BackendRequestOptions::default()
             },
@@ -209,6 +225,23 @@ Content-Length: {}\r\n\
         let payload = http_response::parse_http_json_response(&response)?;
         parse_desktop_auth_session(&payload)
     }
+
+    /// Returns the HTTP status of the desktop-authenticated shutdown request, if any.
+    pub(crate) fn request_backend_shutdown(&self, timeout_ms: u64) -> Option<u16> {
+        let session_secret = self.desktop_session_secret_for_requests();
+        let response = self.request_backend_response_bytes_internal(
+            "POST",
+            DESKTOP_SHUTDOWN_ENDPOINT,
+            timeout_ms,
+            Some("{}"),
+            BackendRequestOptions {
+                desktop_session_secret: Some(session_secret.as_str()),
+                require_loopback: true,
+                ..BackendRequestOptions::default()
+            },
+        )?;
+        http_response::parse_http_status_code(&response)
+    }
 }
 
 fn is_loopback_socket_address(address: &std::net::SocketAddr) -> bool {
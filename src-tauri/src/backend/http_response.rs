@@ -38,6 +38,19 @@ pub fn parse_backend_start_time(payload: &serde_json::Value) -> Option<i64> {
         .and_then(|value| i64::try_from(value).ok())
 }
 
+pub fn parse_backend_version(payload: &serde_json::Value) -> Option<String> {
+    if payload.get("status").and_then(|value| value.as_str()) != Some("ok") {
+        return None;
+    }
+    payload
+        .get("data")?
+        .get("version")?
+        .as_str()
+        .map(str::trim)
+        .filter(|version| !version.is_empty())
+        .map(str::to_string)
+}
+
 fn parse_http_response_parts(raw: &[u8]) -> Option<(Cow<'_, str>, &[u8])> {
     let header_end = raw.windows(4).position(|window| window == b"\r\n\r\n")?;
     let (header_bytes, body_bytes) = raw.split_at(header_end + 4);
@@ -130,6 +143,23 @@ mod tests {
         assert_eq!(parse_backend_start_time(&unsigned), Some(123));
     }
 
+    #[test]
+    fn parse_backend_version_requires_ok_envelope_and_version() {
+        let payload = json!({
+            "status": "ok",
+            "data": { "version": " 4.5.0 " }
+        });
+        assert_eq!(parse_backend_version(&payload).as_deref(), Some("4.5.0"));
+        assert_eq!(
+            parse_backend_version(&json!({ "status": "ok", "data": { "version": "" } })),
+            None
+        );
+        assert_eq!(
+            parse_backend_version(&json!({ "status": "error", "data": { "version": "4.5.0" } })),
+            None
+        );
+    }
+
     #[test]
     fn parse_backend_start_time_rejects_non_ok_status() {
         let payload = json!({
@@ -1,25 +1,28 @@
 use std::{
     env,
     ffi::{OsStr, OsString},
-    fs::{self, OpenOptions},
-    io,
+    fs, io,
     path::Path,
     process::{Command, Stdio},
+    sync::{Arc, Mutex},
     thread,
     time::Duration,
 };
 
+#[cfg(unix)]
+use std::os::unix::process::CommandExt as _;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
 use serde::Deserialize;
 use serde_json::Value;
-use tauri::AppHandle;
 
+use crate::backend::output::{spawn_backend_output_reader, BackendLogWriter, BackendOutputStream};
 use crate::desktop_auth::{DesktopSessionSecret, DESKTOP_SESSION_SECRET_ENV};
 use crate::{
-    append_desktop_log, backend_path_override, build_debug_command, launch_plan, logging,
-    runtime_paths, BackendState, BACKEND_LOG_MAX_BYTES, DEFAULT_SHELL_LOCALE, LOG_BACKUP_COUNT,
+    append_desktop_log, backend::host::BackendHost, backend_log_rotation_policy,
+    backend_path_override, build_debug_command, launch_plan, logging, runtime_paths, BackendState,
+    DEFAULT_SHELL_LOCALE,
 };
 #[cfg(target_os = "windows")]
 use crate::{CREATE_NEW_PROCESS_GROUP, CREATE_NO_WINDOW};
@@ -32,10 +35,10 @@ const ASTRBOT_DASHBOARD_SKIP_DEFAULT_PASSWORD_AUTH_ENV: &str =
     "ASTRBOT_DASHBOARD_SKIP_DEFAULT_PASSWORD_AUTH";
 const DASHBOARD_SKIP_DEFAULT_PASSWORD_AUTH_ENV: &str = "DASHBOARD_SKIP_DEFAULT_PASSWORD_AUTH";
 const ASTRBOT_DESKTOP_CLIENT_ENV: &str = "ASTRBOT_DESKTOP_CLIENT";
-const ASTRBOT_DESKTOP_MANAGED_ENV: &str = "ASTRBOT_DESKTOP_MANAGED";
+pub(crate) const ASTRBOT_DESKTOP_MANAGED_ENV: &str = "ASTRBOT_DESKTOP_MANAGED";
 const ASTRBOT_INSTALLATION_SOURCE_ENV: &str = "ASTRBOT_INSTALLATION_SOURCE";
 const DESKTOP_INSTALLATION_SOURCE: &str = "desktop";
-const ENABLED_ENV_VALUE: &str = "1";
+pub(crate) const ENABLED_ENV_VALUE: &str = "1";
 const DEFAULT_DASHBOARD_HOST: &str = "127.0.0.1";
 const DEFAULT_DASHBOARD_PORT: &str = "6185";
 const CMD_CONFIG_RELATIVE_PATH: &str = "data/cmd_config.json";
@@ -189,6 +192,14 @@ fn configure_desktop_dashboard_environment(
     }
 }
 
+fn pin_dashboard_port(command: &mut Command, port: u16) {
+    // Profiles own their port; it must win over inherited env and cmd_config so
+    // concurrently running backends never collide.
+    let port = port.to_string();
+    command.env(DASHBOARD_PORT_ENV, &port);
+    command.env(ASTRBOT_DASHBOARD_PORT_ENV, &port);
+}
+
 fn resolve_dashboard_value(
     primary_env: Option<OsString>,
     legacy_env: Option<OsString>,
@@ -371,27 +382,37 @@ fn is_local_dashboard_host(host: &str) -> bool {
 }
 
 impl BackendState {
-    pub(crate) fn resolve_launch_plan(&self, app: &AppHandle) -> Result<crate::LaunchPlan, String> {
-        if let Some(custom_cmd) = env::var("ASTRBOT_BACKEND_CMD")
-            .ok()
-            .map(|value| value.trim().to_string())
-            .filter(|value| !value.is_empty())
-        {
-            return launch_plan::resolve_custom_launch(custom_cmd);
+    pub(crate) fn resolve_launch_plan(
+        &self,
+        app: &impl BackendHost,
+    ) -> Result<crate::LaunchPlan, String> {
+        self.resolve_launch_plan_with_resource_dir(app.resource_dir().as_deref())
+    }
+
+    pub(crate) fn resolve_launch_plan_with_resource_dir(
+        &self,
+        resource_dir: Option<&Path>,
+    ) -> Result<crate::LaunchPlan, String> {
+        let overrides = &self.launch_overrides;
+        if let Some(custom_cmd) = overrides.backend_cmd.clone() {
+            return launch_plan::resolve_custom_launch(custom_cmd, overrides);
         }
 
-        if let Some(plan) =
-            launch_plan::resolve_packaged_launch(app, DEFAULT_SHELL_LOCALE, append_desktop_log)?
-        {
+        if let Some(plan) = launch_plan::resolve_packaged_launch(
+            resource_dir,
+            DEFAULT_SHELL_LOCALE,
+            overrides,
+            append_desktop_log,
+        )? {
             return Ok(plan);
         }
 
-        launch_plan::resolve_dev_launch()
+        launch_plan::resolve_dev_launch(overrides)
     }
 
     pub(crate) fn start_backend_process(
         &self,
-        app: &AppHandle,
+        app: &impl BackendHost,
         plan: &crate::LaunchPlan,
     ) -> Result<(), String> {
         if self
@@ -400,7 +421,7 @@ impl BackendState {
             .map_err(|_| "Backend process lock poisoned.")?
             .is_some()
         {
-            append_desktop_log("backend child already exists, skip re-spawn");
+            self.append_desktop_log("backend child already exists, skip re-spawn");
             return Ok(());
         }
 
@@ -425,6 +446,9 @@ impl BackendState {
             }
         }
 
+        self.relocate_dashboard_port_if_taken(app)?;
+        let dashboard_port = self.relocated_dashboard_port().or(plan.dashboard_port);
+
         let mut command = Command::new(&plan.cmd);
         command
             .args(&plan.args)
@@ -442,14 +466,20 @@ impl BackendState {
         if let Some(path_override) = backend_path_override() {
             command.env("PATH", path_override);
         }
-        let mut log = |message: &str| append_desktop_log(message);
+        let mut log = |message: &str| self.append_desktop_log(message);
         configure_desktop_dashboard_environment(&mut command, plan.root_dir.as_deref(), &mut log);
+        if let Some(port) = dashboard_port {
+            pin_dashboard_port(&mut command, port);
+        }
         #[cfg(target_os = "windows")]
         {
             if plan.packaged_mode {
                 command.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
             }
         }
+        // Own process group (pgid == pid) so stop can signal plugin subprocesses too.
+        #[cfg(unix)]
+        command.process_group(0);
 
         mark_as_desktop_managed(
             &mut command,
@@ -475,46 +505,36 @@ impl BackendState {
             plan.root_dir.as_deref(),
             runtime_paths::default_packaged_root_dir(),
         ));
-        if let Some(log_path) = backend_log_path.as_ref() {
-            if let Some(log_parent) = log_path.parent() {
-                fs::create_dir_all(log_parent).map_err(|error| {
-                    format!(
-                        "Failed to create backend log directory {}: {}",
-                        log_parent.display(),
-                        error
-                    )
-                })?;
+        let backend_log_writer = match backend_log_path {
+            Some(log_path) => {
+                // Where the launcher sends output once a detached backend loses our pipes.
+                command.env(crate::BACKEND_LOG_PATH_ENV, &log_path);
+                if let Some(log_parent) = log_path.parent() {
+                    fs::create_dir_all(log_parent).map_err(|error| {
+                        format!(
+                            "Failed to create backend log directory {}: {}",
+                            log_parent.display(),
+                            error
+                        )
+                    })?;
+                }
+                let writer =
+                    BackendLogWriter::open(log_path.clone(), backend_log_rotation_policy())
+                        .map_err(|error| {
+                            format!(
+                                "Failed to open backend log {}: {}",
+                                log_path.display(),
+                                error
+                            )
+                        })?;
+                Some(Arc::new(Mutex::new(writer)))
             }
-            logging::rotate_log_if_needed(
-                log_path,
-                BACKEND_LOG_MAX_BYTES,
-                LOG_BACKUP_COUNT,
-                "backend",
-                false,
-            );
-            let stdout_file = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(log_path)
-                .map_err(|error| {
-                    format!(
-                        "Failed to open backend log {}: {}",
-                        log_path.display(),
-                        error
-                    )
-                })?;
-            let stderr_file = stdout_file
-                .try_clone()
-                .map_err(|error| format!("Failed to clone backend log handle: {error}"))?;
-            command.stdout(Stdio::from(stdout_file));
-            command.stderr(Stdio::from(stderr_file));
-        } else {
-            self.stop_backend_log_rotation_worker();
-            command.stdout(Stdio::null());
-            command.stderr(Stdio::null());
-        }
+            None => None,
+        };
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
 
-        let child = command.spawn().map_err(|error| {
+        let mut child = command.spawn().map_err(|error| {
             format!(
                 "Failed to spawn backend process with command {:?}: {}",
                 build_debug_command(plan),
@@ -522,20 +542,43 @@ impl BackendState {
             )
         })?;
         let child_pid = child.id();
-        append_desktop_log(&format!(
-            "spawned backend: cmd={:?}, cwd={}",
+        self.set_backend_pid(Some(child_pid));
+        self.clear_attached_backend();
+        self.record_spawned_backend(plan, child_pid);
+        self.record_backend_spawned(child_pid);
+        self.append_desktop_log(&format!(
+            "spawned backend: profile={}, cmd={:?}, cwd={}",
+            self.profile_id,
             build_debug_command(plan),
             plan.cwd.display()
         ));
+        let output_generation = self.backend_output.begin_generation();
+        if let Some(stdout) = child.stdout.take() {
+            spawn_backend_output_reader(
+                stdout,
+                BackendOutputStream::Stdout,
+                child_pid,
+                backend_log_writer.clone(),
+                self.backend_output.clone(),
+                output_generation,
+            );
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_backend_output_reader(
+                stderr,
+                BackendOutputStream::Stderr,
+                child_pid,
+                backend_log_writer,
+                self.backend_output.clone(),
+                output_generation,
+            );
+        }
         *self
             .child
             .lock()
             .map_err(|_| "Backend process lock poisoned.")? = Some(child);
-        if let Some(log_path) = backend_log_path {
-            self.start_backend_log_rotation_worker(app, log_path, child_pid);
-        } else {
-            self.stop_backend_log_rotation_worker();
-        }
+        self.start_backend_supervisor(app, child_pid);
+        self.start_resource_monitor(app, plan, child_pid);
         Ok(())
     }
 }
@@ -555,7 +598,7 @@ mod tests {
     use std::os::unix::ffi::OsStringExt;
 
     use super::{
-        configure_desktop_dashboard_environment, mark_as_desktop_managed,
+        configure_desktop_dashboard_environment, mark_as_desktop_managed, pin_dashboard_port,
         read_cmd_config_file_with_retry_and_hook, sanitize_packaged_python_environment,
         ASTRBOT_DASHBOARD_HOST_ENV, ASTRBOT_DASHBOARD_PORT_ENV,
         ASTRBOT_DASHBOARD_SKIP_DEFAULT_PASSWORD_AUTH_ENV, ASTRBOT_DESKTOP_CLIENT_ENV,
@@ -739,6 +782,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn pin_dashboard_port_overrides_explicit_dashboard_env() {
+        with_clean_dashboard_env(|| {
+            env::set_var(DASHBOARD_PORT_ENV, "7000");
+            let mut command = Command::new("sh");
+
+            configure_desktop_dashboard_environment(&mut command, None, &mut |_| {});
+            pin_dashboard_port(&mut command, 6190);
+
+            assert_eq!(
+                get_command_env_value(&command, DASHBOARD_PORT_ENV),
+                Some(Some("6190".to_string()))
+            );
+            assert_eq!(
+                get_command_env_value(&command, ASTRBOT_DASHBOARD_PORT_ENV),
+                Some(Some("6190".to_string()))
+            );
+        });
+    }
+
     #[test]
     fn configure_desktop_dashboard_environment_does_not_skip_auth_for_remote_host() {
         with_clean_dashboard_env(|| {
@@ -1,10 +1,23 @@
+pub(crate) mod attach;
 pub(crate) mod config;
+pub(crate) mod detach;
+pub(crate) mod host;
 pub(crate) mod http;
 pub(crate) mod http_response;
 pub(crate) mod launch;
+pub(crate) mod orphan;
+pub(crate) mod output;
 pub(crate) mod path;
+pub(crate) mod port;
 pub(crate) mod process_lifecycle;
 pub(crate) mod readiness;
+pub(crate) mod readiness_probe;
+pub(crate) mod resources;
 pub(crate) mod restart;
 pub(crate) mod restart_strategy;
 pub(crate) mod runtime;
+pub(crate) mod startup_cancel;
+pub(crate) mod startup_progress;
+pub(crate) mod state_machine;
+pub(crate) mod supervisor;
+pub(crate) mod watchdog;
@@ -0,0 +1,443 @@
+#[cfg(any(target_os = "linux", test))]
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(any(target_os = "linux", test))]
+use crate::{append_desktop_log_at, logging::DesktopLogLevel};
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackendPidRecord {
+    pid: u32,
+    start_time: u64,
+    exe: PathBuf,
+    profile: String,
+    // The shell that spawned it; records from older builds have neither.
+    #[serde(default)]
+    owner_pid: Option<u32>,
+    #[serde(default)]
+    owner_start_time: Option<u64>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug)]
+struct ObservedProcess {
+    start_time: u64,
+    exe: PathBuf,
+    desktop_managed: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn environ_has_entry(raw_environ: &[u8], key: &str, value: &str) -> bool {
+    let entry = format!("{key}={value}");
+    raw_environ
+        .split(|byte| *byte == 0)
+        .any(|candidate| candidate == entry.as_bytes())
+}
+
+/// Whether the shell that wrote the record is still running, given the start
+/// time now observed for its pid.
+#[cfg(any(target_os = "linux", test))]
+fn is_owner_alive(record: &BackendPidRecord, owner_start_time: Option<u64>) -> bool {
+    match (record.owner_pid, record.owner_start_time) {
+        (None, _) => false,
+        (Some(_), Some(expected)) => owner_start_time == Some(expected),
+        (Some(_), None) => owner_start_time.is_some(),
+    }
+}
+
+/// Every check must pass before touching the process: a recycled pid, a user's
+/// own Python, a backend started outside the desktop, or one whose shell is
+/// still running (another instance on the same root) are all left alone.
+#[cfg(any(target_os = "linux", test))]
+fn is_orphaned_desktop_backend(
+    record: &BackendPidRecord,
+    observed: &ObservedProcess,
+    owner_start_time: Option<u64>,
+    runtime_dir: &Path,
+) -> bool {
+    observed.start_time == record.start_time
+        && observed.exe == record.exe
+        && observed.exe.starts_with(runtime_dir)
+        && observed.desktop_managed
+        && !is_owner_alive(record, owner_start_time)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn read_pid_record(path: &Path) -> Option<BackendPidRecord> {
+    fs::read(path)
+        .ok()
+        .and_then(|payload| serde_json::from_slice(&payload).ok())
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn remove_pid_record(path: &Path) {
+    if let Err(error) = fs::remove_file(path) {
+        if error.kind() != io::ErrorKind::NotFound {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "failed to remove backend pid record {}: {error}",
+                    path.display()
+                ),
+            );
+        }
+    }
+}
+
+/// A detached backend has no owning shell any more, so a failed reattach on the
+/// next launch must not find it in the pid record and treat it as an orphan. Only
+/// a record still naming `pid` is removed: another profile on the same root may
+/// have rewritten it since.
+#[cfg(any(target_os = "linux", test))]
+fn release_pid_record(path: &Path, pid: u32) -> bool {
+    match read_pid_record(path) {
+        Some(record) if record.pid == pid => {
+            remove_pid_record(path);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{fs, path::PathBuf, time::Duration};
+
+    use super::{
+        environ_has_entry, is_orphaned_desktop_backend, is_owner_alive, read_pid_record,
+        release_pid_record, remove_pid_record, BackendPidRecord, ObservedProcess,
+    };
+    use crate::{
+        append_desktop_log, append_desktop_log_at,
+        backend::{
+            self,
+            host::BackendHost,
+            launch::{ASTRBOT_DESKTOP_MANAGED_ENV, ENABLED_ENV_VALUE},
+        },
+        logging::DesktopLogLevel,
+        process_control, runtime_paths, BackendState, LaunchPlan, BACKEND_PID_RECORD_RELATIVE_PATH,
+        GRACEFUL_STOP_TIMEOUT_MS,
+    };
+
+    fn pid_record_path(plan: &LaunchPlan) -> Option<PathBuf> {
+        plan.runtime_dir.as_ref()?;
+        backend::config::resolve_backend_runtime_data_path(
+            plan.root_dir.as_deref(),
+            runtime_paths::default_packaged_root_dir(),
+            BACKEND_PID_RECORD_RELATIVE_PATH,
+        )
+    }
+
+    fn read_process_exe(pid: u32) -> Option<PathBuf> {
+        fs::read_link(format!("/proc/{pid}/exe")).ok()
+    }
+
+    fn observe_process(pid: u32) -> Option<ObservedProcess> {
+        let start_time = process_control::process_start_time(pid)?;
+        let exe = read_process_exe(pid)?;
+        let environ = fs::read(format!("/proc/{pid}/environ")).ok()?;
+        Some(ObservedProcess {
+            start_time,
+            exe,
+            desktop_managed: environ_has_entry(
+                &environ,
+                ASTRBOT_DESKTOP_MANAGED_ENV,
+                ENABLED_ENV_VALUE,
+            ),
+        })
+    }
+
+    impl BackendState {
+        pub(crate) fn record_spawned_backend(&self, plan: &LaunchPlan, pid: u32) {
+            let Some(path) = pid_record_path(plan) else {
+                return;
+            };
+            let record = match (
+                process_control::process_start_time(pid),
+                read_process_exe(pid),
+            ) {
+                (Some(start_time), Some(exe)) => BackendPidRecord {
+                    pid,
+                    start_time,
+                    exe,
+                    profile: self.profile_id.clone(),
+                    owner_pid: Some(std::process::id()),
+                    owner_start_time: process_control::process_start_time(std::process::id()),
+                },
+                _ => {
+                    append_desktop_log_at(
+                        DesktopLogLevel::Warn,
+                        &format!("backend exited before its pid record was written: pid={pid}"),
+                    );
+                    return;
+                }
+            };
+            let result = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .map_err(|error| error.to_string())
+                .and_then(|()| serde_json::to_vec(&record).map_err(|error| error.to_string()))
+                .and_then(|payload| fs::write(&path, payload).map_err(|error| error.to_string()));
+            if let Err(error) = result {
+                append_desktop_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!(
+                        "failed to write backend pid record {}: {error}",
+                        path.display()
+                    ),
+                );
+            }
+        }
+
+        pub(crate) fn release_detached_backend_pid_record(&self, plan: &LaunchPlan, pid: u32) {
+            if pid_record_path(plan).is_some_and(|path| release_pid_record(&path, pid)) {
+                append_desktop_log(&format!(
+                    "released backend pid record for detached backend: profile={}, pid={pid}",
+                    self.profile_id
+                ));
+            }
+        }
+
+        pub(crate) fn cleanup_orphaned_backend(&self, app: &impl BackendHost) {
+            let Ok(plan) = self.resolve_launch_plan(app) else {
+                return;
+            };
+            let (Some(runtime_dir), Some(path)) =
+                (plan.runtime_dir.as_ref(), pid_record_path(&plan))
+            else {
+                return;
+            };
+            let Some(record) = read_pid_record(&path) else {
+                remove_pid_record(&path);
+                return;
+            };
+
+            let runtime_dir = fs::canonicalize(runtime_dir).unwrap_or_else(|_| runtime_dir.clone());
+            // A record this shell wrote has no live owner: the child is no longer managed here.
+            let owner_start_time = record
+                .owner_pid
+                .filter(|owner_pid| *owner_pid != std::process::id())
+                .and_then(process_control::process_start_time);
+            if is_owner_alive(&record, owner_start_time) {
+                append_desktop_log(&format!(
+                    "backend pid record belongs to a running desktop shell, leaving it: profile={}, pid={}, owner_pid={}",
+                    record.profile,
+                    record.pid,
+                    record.owner_pid.unwrap_or_default()
+                ));
+                return;
+            }
+            match observe_process(record.pid) {
+                Some(observed)
+                    if is_orphaned_desktop_backend(
+                        &record,
+                        &observed,
+                        owner_start_time,
+                        &runtime_dir,
+                    ) =>
+                {
+                    append_desktop_log_at(
+                        DesktopLogLevel::Warn,
+                        &format!(
+                            "terminating orphaned backend from a previous desktop session: profile={}, pid={}, exe={}",
+                            record.profile,
+                            record.pid,
+                            record.exe.display()
+                        ),
+                    );
+                    if !process_control::terminate_orphaned_process_tree(
+                        record.pid,
+                        record.start_time,
+                        Duration::from_millis(GRACEFUL_STOP_TIMEOUT_MS),
+                        append_desktop_log_at,
+                    ) {
+                        append_desktop_log_at(
+                            DesktopLogLevel::Error,
+                            &format!("orphaned backend survived termination: pid={}", record.pid),
+                        );
+                    }
+                }
+                _ => append_desktop_log(&format!(
+                    "discarding stale backend pid record: pid={}",
+                    record.pid
+                )),
+            }
+            remove_pid_record(&path);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl crate::BackendState {
+    pub(crate) fn record_spawned_backend(&self, _plan: &crate::LaunchPlan, _pid: u32) {}
+
+    pub(crate) fn release_detached_backend_pid_record(&self, _plan: &crate::LaunchPlan, _pid: u32) {
+    }
+
+    pub(crate) fn cleanup_orphaned_backend(&self, _app: &impl crate::backend::host::BackendHost) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{
+        environ_has_entry, is_orphaned_desktop_backend, is_owner_alive, read_pid_record,
+        release_pid_record, BackendPidRecord, ObservedProcess,
+    };
+
+    fn record() -> BackendPidRecord {
+        BackendPidRecord {
+            pid: 4242,
+            start_time: 987_654,
+            exe: PathBuf::from("/opt/AstrBot/resources/backend/python/bin/python3.12"),
+            profile: "default".to_string(),
+            owner_pid: Some(1200),
+            owner_start_time: Some(900_000),
+        }
+    }
+
+    fn observed() -> ObservedProcess {
+        ObservedProcess {
+            start_time: 987_654,
+            exe: PathBuf::from("/opt/AstrBot/resources/backend/python/bin/python3.12"),
+            desktop_managed: true,
+        }
+    }
+
+    #[test]
+    fn environ_has_entry_matches_exact_entries_only() {
+        let environ = b"PATH=/usr/bin\0ASTRBOT_DESKTOP_MANAGED=1\0HOME=/root\0";
+        assert!(environ_has_entry(environ, "ASTRBOT_DESKTOP_MANAGED", "1"));
+        assert!(!environ_has_entry(environ, "ASTRBOT_DESKTOP_MANAGED", "0"));
+        assert!(!environ_has_entry(
+            b"XASTRBOT_DESKTOP_MANAGED=1\0",
+            "ASTRBOT_DESKTOP_MANAGED",
+            "1"
+        ));
+    }
+
+    #[test]
+    fn orphan_detection_requires_every_identity_check() {
+        let runtime_dir = Path::new("/opt/AstrBot/resources/backend");
+        assert!(is_orphaned_desktop_backend(
+            &record(),
+            &observed(),
+            None,
+            runtime_dir
+        ));
+
+        let recycled = ObservedProcess {
+            start_time: 1,
+            ..observed()
+        };
+        assert!(!is_orphaned_desktop_backend(
+            &record(),
+            &recycled,
+            None,
+            runtime_dir
+        ));
+
+        let unmarked = ObservedProcess {
+            desktop_managed: false,
+            ..observed()
+        };
+        assert!(!is_orphaned_desktop_backend(
+            &record(),
+            &unmarked,
+            None,
+            runtime_dir
+        ));
+
+        assert!(!is_orphaned_desktop_backend(
+            &record(),
+            &observed(),
+            None,
+            Path::new("/opt/Other/resources/backend")
+        ));
+
+        let system_python = ObservedProcess {
+            exe: PathBuf::from("/usr/bin/python3.12"),
+            ..observed()
+        };
+        assert!(!is_orphaned_desktop_backend(
+            &BackendPidRecord {
+                exe: PathBuf::from("/usr/bin/python3.12"),
+                ..record()
+            },
+            &system_python,
+            None,
+            runtime_dir
+        ));
+    }
+
+    #[test]
+    fn orphan_detection_leaves_backends_of_a_running_owner_alone() {
+        let runtime_dir = Path::new("/opt/AstrBot/resources/backend");
+        assert!(!is_orphaned_desktop_backend(
+            &record(),
+            &observed(),
+            Some(900_000),
+            runtime_dir
+        ));
+        // The owner pid now belongs to a different process.
+        assert!(is_orphaned_desktop_backend(
+            &record(),
+            &observed(),
+            Some(1),
+            runtime_dir
+        ));
+
+        let legacy = BackendPidRecord {
+            owner_pid: None,
+            owner_start_time: None,
+            ..record()
+        };
+        assert!(!is_owner_alive(&legacy, Some(900_000)));
+        assert!(is_orphaned_desktop_backend(
+            &legacy,
+            &observed(),
+            Some(900_000),
+            runtime_dir
+        ));
+    }
+
+    #[test]
+    fn failed_reattach_finds_no_pid_record_for_a_detached_backend() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("desktop-backend-pid.json");
+        std::fs::write(
+            &path,
+            serde_json::to_vec(&record()).expect("serialize record"),
+        )
+        .expect("write record");
+
+        // Another profile's backend now owns the record: keep it.
+        assert!(!release_pid_record(&path, 1));
+        assert_eq!(read_pid_record(&path), Some(record()));
+
+        // Detaching the recorded backend drops it, so the cleanup that runs after a
+        // failed reattach has nothing to terminate.
+        assert!(release_pid_record(&path, record().pid));
+        assert_eq!(read_pid_record(&path), None);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn pid_record_round_trips_as_camel_case_json() {
+        let payload = serde_json::to_value(record()).expect("serialize record");
+        assert_eq!(payload["startTime"], 987_654);
+        let parsed: BackendPidRecord = serde_json::from_value(payload).expect("deserialize record");
+        assert_eq!(parsed, record());
+
+        let legacy: BackendPidRecord = serde_json::from_str(
+            r#"{"pid":1,"startTime":2,"exe":"/opt/python","profile":"default"}"#,
+        )
+        .expect("deserialize legacy record");
+        assert_eq!(legacy.owner_pid, None);
+    }
+}
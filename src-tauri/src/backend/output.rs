@@ -0,0 +1,466 @@
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    append_desktop_log,
+    logging::{self, LogRotationPolicy},
+    BackendState, BACKEND_EXIT_STDERR_TAIL_LINES,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BackendOutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl BackendOutputStream {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackendOutputLine {
+    pub(crate) stream: BackendOutputStream,
+    pub(crate) timestamp_ms: u64,
+    pub(crate) text: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct BackendOutputBuffer {
+    lines: Mutex<VecDeque<BackendOutputLine>>,
+    capacity: usize,
+    generation: AtomicU64,
+}
+
+impl BackendOutputBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Drops the previous child's lines and returns the generation its
+    /// successor's readers push under; late lines from older readers are ignored.
+    pub(crate) fn begin_generation(&self) -> u64 {
+        let mut lines = match self.lines.lock() {
+            Ok(lines) => lines,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        lines.clear();
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn push(&self, generation: u64, line: BackendOutputLine) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut lines = match self.lines.lock() {
+            Ok(lines) => lines,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        while lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Returns up to `limit` of the most recent lines in output order.
+    pub(crate) fn recent(
+        &self,
+        stream: Option<BackendOutputStream>,
+        limit: usize,
+    ) -> Vec<BackendOutputLine> {
+        let lines = match self.lines.lock() {
+            Ok(lines) => lines,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut recent = lines
+            .iter()
+            .rev()
+            .filter(|line| stream.is_none_or(|stream| line.stream == stream))
+            .take(limit)
+            .cloned()
+            .collect::<Vec<_>>();
+        recent.reverse();
+        recent
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BackendLogWriter {
+    path: PathBuf,
+    file: Option<File>,
+    written_bytes: u64,
+    rotation: LogRotationPolicy,
+    finishing_rotation: Arc<AtomicBool>,
+}
+
+impl BackendLogWriter {
+    pub(crate) fn open(path: PathBuf, rotation: LogRotationPolicy) -> io::Result<Self> {
+        let finishing_rotation = Arc::new(AtomicBool::new(true));
+        let rotated = logging::shift_log_backups_if_needed(&path, &rotation, "backend");
+        let (file, written_bytes) = Self::open_append(&path)?;
+        let writer = Self {
+            path,
+            file: Some(file),
+            written_bytes,
+            rotation,
+            finishing_rotation,
+        };
+        writer.finish_rotation(rotated);
+        Ok(writer)
+    }
+
+    /// Compresses and prunes backups on a helper thread so the pipe reader never
+    /// waits on gzip. Until it is done no further rotation starts, which keeps
+    /// backup slot 1 stable while it is being compressed.
+    fn finish_rotation(&self, rotated: bool) {
+        if !rotated {
+            self.finishing_rotation.store(false, Ordering::Release);
+            return;
+        }
+        let path = self.path.clone();
+        let rotation = self.rotation;
+        let finishing_rotation = self.finishing_rotation.clone();
+        let spawn_result = thread::Builder::new()
+            .name("backend-log-rotation".to_string())
+            .spawn(move || {
+                logging::finish_log_rotation(&path, &rotation, "backend");
+                finishing_rotation.store(false, Ordering::Release);
+            });
+        if let Err(error) = spawn_result {
+            append_desktop_log(&format!(
+                "failed to spawn backend log rotation thread: {error}"
+            ));
+            logging::finish_log_rotation(&self.path, &self.rotation, "backend");
+            self.finishing_rotation.store(false, Ordering::Release);
+        }
+    }
+
+    fn open_append(path: &Path) -> io::Result<(File, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written_bytes = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        Ok((file, written_bytes))
+    }
+
+    fn rotate(&mut self) {
+        // Keep appending past the limit until the previous backup is compressed.
+        if self.finishing_rotation.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        // The shell is the only writer, so the active file can be renamed away safely.
+        self.file = None;
+        let rotated = logging::shift_log_backups_if_needed(&self.path, &self.rotation, "backend");
+        match Self::open_append(&self.path) {
+            Ok((file, written_bytes)) => {
+                self.file = Some(file);
+                self.written_bytes = written_bytes;
+            }
+            Err(error) => append_desktop_log(&format!(
+                "failed to reopen backend log {} after rotation: {}",
+                self.path.display(),
+                error
+            )),
+        }
+        self.finish_rotation(rotated);
+    }
+
+    fn write_line(&mut self, line: &[u8]) {
+        if self.rotation.max_bytes > 0 && self.written_bytes >= self.rotation.max_bytes {
+            self.rotate();
+        }
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        match file.write_all(line) {
+            Ok(()) => self.written_bytes += line.len() as u64,
+            Err(error) => append_desktop_log(&format!(
+                "failed to write backend log {}: {}",
+                self.path.display(),
+                error
+            )),
+        }
+    }
+}
+
+fn trim_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn now_timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn pump_backend_output<R: Read>(
+    reader: R,
+    stream: BackendOutputStream,
+    log_writer: Option<&Mutex<BackendLogWriter>>,
+    buffer: &BackendOutputBuffer,
+    generation: u64,
+) {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                append_desktop_log(&format!(
+                    "failed to read backend {}: {}",
+                    stream.as_label(),
+                    error
+                ));
+                break;
+            }
+        }
+
+        if let Some(log_writer) = log_writer {
+            let mut log_writer = match log_writer.lock() {
+                Ok(log_writer) => log_writer,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            log_writer.write_line(&line);
+        }
+        buffer.push(
+            generation,
+            BackendOutputLine {
+                stream,
+                timestamp_ms: now_timestamp_ms(),
+                text: String::from_utf8_lossy(trim_line_ending(&line)).into_owned(),
+            },
+        );
+    }
+}
+
+pub(crate) fn spawn_backend_output_reader<R: Read + Send + 'static>(
+    reader: R,
+    stream: BackendOutputStream,
+    child_pid: u32,
+    log_writer: Option<Arc<Mutex<BackendLogWriter>>>,
+    buffer: Arc<BackendOutputBuffer>,
+    generation: u64,
+) {
+    let spawn_result = thread::Builder::new()
+        .name(format!("backend-{}-{child_pid}", stream.as_label()))
+        .spawn(move || {
+            pump_backend_output(reader, stream, log_writer.as_deref(), &buffer, generation);
+        });
+    if let Err(error) = spawn_result {
+        append_desktop_log(&format!(
+            "failed to spawn backend {} reader thread: pid={child_pid}, error={error}",
+            stream.as_label()
+        ));
+    }
+}
+
+impl BackendState {
+    pub(crate) fn describe_recent_backend_stderr(&self) -> Option<String> {
+        let lines = self.backend_output.recent(
+            Some(BackendOutputStream::Stderr),
+            BACKEND_EXIT_STDERR_TAIL_LINES,
+        );
+        if lines.is_empty() {
+            return None;
+        }
+        Some(
+            lines
+                .iter()
+                .map(|line| line.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::Cursor,
+        sync::{atomic::Ordering, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use super::{
+        pump_backend_output, trim_line_ending, BackendLogWriter, BackendOutputBuffer,
+        BackendOutputLine, BackendOutputStream,
+    };
+    use crate::logging::{self, LogRotationPolicy};
+
+    fn line(stream: BackendOutputStream, text: &str) -> BackendOutputLine {
+        BackendOutputLine {
+            stream,
+            timestamp_ms: 0,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn output_buffer_evicts_oldest_lines_beyond_capacity() {
+        let buffer = BackendOutputBuffer::new(2);
+        buffer.push(0, line(BackendOutputStream::Stdout, "one"));
+        buffer.push(0, line(BackendOutputStream::Stderr, "two"));
+        buffer.push(0, line(BackendOutputStream::Stdout, "three"));
+
+        let texts = buffer
+            .recent(None, 10)
+            .into_iter()
+            .map(|line| line.text)
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["two", "three"]);
+    }
+
+    #[test]
+    fn output_buffer_recent_filters_by_stream_and_keeps_order() {
+        let buffer = BackendOutputBuffer::new(10);
+        buffer.push(0, line(BackendOutputStream::Stderr, "e1"));
+        buffer.push(0, line(BackendOutputStream::Stdout, "o1"));
+        buffer.push(0, line(BackendOutputStream::Stderr, "e2"));
+        buffer.push(0, line(BackendOutputStream::Stderr, "e3"));
+
+        let texts = buffer
+            .recent(Some(BackendOutputStream::Stderr), 2)
+            .into_iter()
+            .map(|line| line.text)
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["e2", "e3"]);
+    }
+
+    #[test]
+    fn trim_line_ending_strips_lf_and_crlf() {
+        assert_eq!(trim_line_ending(b"abc\r\n"), b"abc");
+        assert_eq!(trim_line_ending(b"abc\n"), b"abc");
+        assert_eq!(trim_line_ending(b"abc"), b"abc");
+    }
+
+    #[test]
+    fn pump_backend_output_tees_lines_to_log_and_buffer() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let log_path = temp.path().join("backend.log");
+        let writer = Mutex::new(
+            BackendLogWriter::open(log_path.clone(), LogRotationPolicy::new(1024, 2))
+                .expect("open backend log"),
+        );
+        let buffer = BackendOutputBuffer::new(10);
+
+        pump_backend_output(
+            Cursor::new(b"first\r\nsecond\npartial".to_vec()),
+            BackendOutputStream::Stderr,
+            Some(&writer),
+            &buffer,
+            0,
+        );
+
+        assert_eq!(
+            fs::read_to_string(&log_path).expect("read backend log"),
+            "first\r\nsecond\npartial"
+        );
+        let recent = buffer.recent(None, 10);
+        assert_eq!(
+            recent
+                .iter()
+                .map(|line| line.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["first", "second", "partial"]
+        );
+        assert!(recent
+            .iter()
+            .all(|line| line.stream == BackendOutputStream::Stderr));
+    }
+
+    #[test]
+    fn log_writer_rotates_when_size_limit_is_reached() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let log_path = temp.path().join("backend.log");
+        let mut writer = BackendLogWriter::open(log_path.clone(), LogRotationPolicy::new(8, 2))
+            .expect("open backend log");
+
+        writer.write_line(b"12345678\n");
+        writer.write_line(b"next\n");
+
+        assert_eq!(
+            fs::read_to_string(&log_path).expect("read active log"),
+            "next\n"
+        );
+        assert_eq!(
+            fs::read_to_string(temp.path().join("backend.log.1")).expect("read rotated log"),
+            "12345678\n"
+        );
+    }
+
+    #[test]
+    fn log_writer_compresses_rotated_backup_off_the_writing_thread() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let log_path = temp.path().join("backend.log");
+        let policy = LogRotationPolicy {
+            compress_backups: true,
+            ..LogRotationPolicy::new(8, 2)
+        };
+        let mut writer =
+            BackendLogWriter::open(log_path.clone(), policy).expect("open backend log");
+
+        writer.write_line(b"12345678\n");
+        writer.write_line(b"next\n");
+        for _ in 0..200 {
+            if !writer.finishing_rotation.load(Ordering::Acquire) {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let compressed = temp.path().join("backend.log.1.gz");
+        assert_eq!(
+            logging::read_log_file(&compressed).expect("read compressed backup"),
+            b"12345678\n"
+        );
+        assert!(!temp.path().join("backend.log.1").exists());
+    }
+
+    #[test]
+    fn output_buffer_drops_lines_from_previous_generations() {
+        let buffer = BackendOutputBuffer::new(10);
+        let first = buffer.begin_generation();
+        buffer.push(first, line(BackendOutputStream::Stderr, "old crash"));
+
+        let second = buffer.begin_generation();
+        buffer.push(first, line(BackendOutputStream::Stderr, "late old line"));
+        buffer.push(second, line(BackendOutputStream::Stderr, "fresh"));
+
+        assert_eq!(
+            buffer
+                .recent(None, 10)
+                .iter()
+                .map(|line| line.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fresh"]
+        );
+    }
+}
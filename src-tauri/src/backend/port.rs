@@ -0,0 +1,197 @@
+use std::net::{Ipv4Addr, Ipv6Addr, TcpListener};
+
+use url::{Host, Url};
+
+use crate::{
+    append_desktop_log, append_desktop_log_at, backend,
+    backend::host::BackendHost,
+    desktop_events::{self, DesktopEvent},
+    logging::DesktopLogLevel,
+    BackendState,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PortRelocation {
+    pub(crate) from: u16,
+    pub(crate) to: u16,
+}
+
+fn loopback_bind_address(backend_url: &Url) -> Option<(&'static str, u16)> {
+    if backend_url.scheme() != "http" {
+        return None;
+    }
+    let port = backend_url.port_or_known_default()?;
+    match backend_url.host()? {
+        Host::Ipv4(address) if address.is_loopback() => Some(("127.0.0.1", port)),
+        Host::Ipv6(address) if address.is_loopback() => Some(("::1", port)),
+        Host::Domain(domain) if domain.eq_ignore_ascii_case("localhost") => {
+            Some(("127.0.0.1", port))
+        }
+        _ => None,
+    }
+}
+
+fn pick_free_loopback_port(bind_host: &str) -> Result<u16, String> {
+    let listener = match bind_host {
+        "::1" => TcpListener::bind((Ipv6Addr::LOCALHOST, 0)),
+        _ => TcpListener::bind((Ipv4Addr::LOCALHOST, 0)),
+    }
+    .map_err(|error| format!("Failed to reserve a free loopback port: {error}"))?;
+    listener
+        .local_addr()
+        .map(|address| address.port())
+        .map_err(|error| format!("Failed to read reserved loopback port: {error}"))
+}
+
+pub(crate) fn backend_url_with_port(backend_url: &str, port: u16) -> Option<String> {
+    let mut parsed = Url::parse(backend_url).ok()?;
+    parsed.set_port(Some(port)).ok()?;
+    Some(parsed.to_string())
+}
+
+impl BackendState {
+    pub(crate) fn backend_url(&self) -> String {
+        match self.backend_url.lock() {
+            Ok(guard) => guard.clone(),
+            Err(error) => error.into_inner().clone(),
+        }
+    }
+
+    pub(crate) fn set_relocated_backend_url(&self, backend_url: String, port: u16) {
+        match self.backend_url.lock() {
+            Ok(mut guard) => *guard = backend_url,
+            Err(error) => *error.into_inner() = backend_url,
+        }
+        match self.relocated_dashboard_port.lock() {
+            Ok(mut guard) => *guard = Some(port),
+            Err(error) => *error.into_inner() = Some(port),
+        }
+    }
+
+    pub(crate) fn restore_backend_url(&self, backend_url: String, port: Option<u16>) {
+        match self.backend_url.lock() {
+            Ok(mut guard) => *guard = backend_url,
+            Err(error) => *error.into_inner() = backend_url,
+        }
+        match self.relocated_dashboard_port.lock() {
+            Ok(mut guard) => *guard = port,
+            Err(error) => *error.into_inner() = port,
+        }
+    }
+
+    /// Once relocated, later spawns (restarts, crash relaunches) keep the new port.
+    pub(crate) fn relocated_dashboard_port(&self) -> Option<u16> {
+        match self.relocated_dashboard_port.lock() {
+            Ok(guard) => *guard,
+            Err(error) => *error.into_inner(),
+        }
+    }
+
+    /// Moves the dashboard to a free loopback port when something other than an
+    /// AstrBot backend already listens on the configured one.
+    pub(crate) fn relocate_dashboard_port_if_taken(
+        &self,
+        app: &impl BackendHost,
+    ) -> Result<(), String> {
+        if !self.ping_backend(backend::runtime::backend_ping_timeout_ms(
+            append_desktop_log,
+        )) || self.identify_running_backend().is_some()
+        {
+            return Ok(());
+        }
+
+        let backend_url = self.backend_url();
+        let Some((bind_host, from)) = Url::parse(&backend_url)
+            .ok()
+            .as_ref()
+            .and_then(loopback_bind_address)
+        else {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "backend port is held by another process but {backend_url} is not a loopback URL; keeping it"
+                ),
+            );
+            return Ok(());
+        };
+
+        let to = pick_free_loopback_port(bind_host)?;
+        let relocated_url = backend_url_with_port(&backend_url, to)
+            .ok_or_else(|| format!("Failed to rewrite backend URL {backend_url} to port {to}."))?;
+        self.set_relocated_backend_url(relocated_url.clone(), to);
+        append_desktop_log_at(
+            DesktopLogLevel::Warn,
+            &format!(
+                "dashboard port {from} is held by another process; profile={} now uses {relocated_url}",
+                self.profile_id
+            ),
+        );
+        desktop_events::publish(
+            Some(&self.profile_id),
+            DesktopEvent::PortRelocated { from, to },
+        );
+        app.dashboard_port_relocated(PortRelocation { from, to });
+        Ok(())
+    }
+
+    /// The relocated port is only reserved until its probe listener closes, so
+    /// another process can take it before the backend binds. True when something
+    /// other than an AstrBot backend now answers on it.
+    pub(crate) fn relocated_port_taken_by_other_process(&self) -> bool {
+        self.relocated_dashboard_port().is_some()
+            && self.ping_backend(backend::runtime::backend_ping_timeout_ms(
+                append_desktop_log,
+            ))
+            && self.identify_running_backend().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use url::Url;
+
+    use super::{backend_url_with_port, loopback_bind_address, pick_free_loopback_port};
+
+    #[test]
+    fn loopback_bind_address_only_accepts_local_http_urls() {
+        let parse = |raw: &str| Url::parse(raw).expect("parse url");
+        assert_eq!(
+            loopback_bind_address(&parse("http://127.0.0.1:6185/")),
+            Some(("127.0.0.1", 6185))
+        );
+        assert_eq!(
+            loopback_bind_address(&parse("http://localhost/")),
+            Some(("127.0.0.1", 80))
+        );
+        assert_eq!(
+            loopback_bind_address(&parse("http://[::1]:6185/")),
+            Some(("::1", 6185))
+        );
+        assert_eq!(loopback_bind_address(&parse("http://10.0.0.2:6185/")), None);
+        assert_eq!(
+            loopback_bind_address(&parse("https://127.0.0.1:6185/")),
+            None
+        );
+    }
+
+    #[test]
+    fn backend_url_with_port_keeps_host_and_path() {
+        assert_eq!(
+            backend_url_with_port("http://127.0.0.1:6185/", 51234).as_deref(),
+            Some("http://127.0.0.1:51234/")
+        );
+        assert_eq!(
+            backend_url_with_port("http://localhost/dashboard/", 6200).as_deref(),
+            Some("http://localhost:6200/dashboard/")
+        );
+    }
+
+    #[test]
+    fn pick_free_loopback_port_returns_a_bindable_port() {
+        let port = pick_free_loopback_port("127.0.0.1").expect("pick free port");
+        assert_ne!(port, 0);
+        TcpListener::bind(("127.0.0.1", port)).expect("picked port is free");
+    }
+}
@@ -1,38 +1,58 @@
 use std::{
-    path::PathBuf,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use tauri::{AppHandle, Manager};
-
 use crate::{
-    append_desktop_log, logging, process_control, BackendState, BACKEND_LOG_MAX_BYTES,
-    BACKEND_LOG_ROTATION_CHECK_INTERVAL, GRACEFUL_STOP_TIMEOUT_MS, LOG_BACKUP_COUNT,
+    append_desktop_log, append_desktop_log_at, backend, logging::DesktopLogLevel, process_control,
+    BackendState, BACKEND_SHUTDOWN_REQUEST_TIMEOUT_MS, GRACEFUL_STOP_TIMEOUT_MS,
 };
 
+const CHILD_EXIT_POLL_INTERVAL: Duration = Duration::from_millis(120);
+
 impl BackendState {
     pub(crate) fn stop_backend(&self) -> Result<(), String> {
         self.stop_backend_with_timeout(Duration::from_millis(GRACEFUL_STOP_TIMEOUT_MS))
     }
 
     pub(crate) fn stop_backend_with_timeout(&self, timeout: Duration) -> Result<(), String> {
-        self.stop_backend_log_rotation_worker();
-        let mut guard = self
-            .child
-            .lock()
-            .map_err(|_| "Backend process lock poisoned.".to_string())?;
+        self.stop_backend_supervisor();
+        self.stop_liveness_watchdog();
+        self.stop_resource_monitor();
+        let Some(pid) = self.managed_child_pid() else {
+            return Ok(());
+        };
 
+        let previous_phase = self.begin_backend_stop();
+        // Runs without the child lock so status reads are not stuck behind the grace wait.
+        let exited_on_request = self.request_backend_shutdown_and_wait(pid);
+        let signal_timeout =
+            self.wait_for_group_after_requested_exit(pid, exited_on_request, timeout);
+        let mut guard = match self.child.lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                self.finish_backend_stop(previous_phase, false);
+                return Err("Backend process lock poisoned.".to_string());
+            }
+        };
         let Some(child) = guard.as_mut() else {
+            self.finish_backend_stop(previous_phase, true);
             return Ok(());
         };
-
-        if process_control::stop_child_process_gracefully(child, timeout, append_desktop_log) {
+        // On Unix the signal pass also reaps plugin processes left in the backend's group and
+        // returns at once for a child that already exited.
+        let stopped = (exited_on_request && cfg!(target_os = "windows"))
+            || process_control::stop_child_process_gracefully(
+                child,
+                signal_timeout,
+                append_desktop_log_at,
+            );
+        if stopped {
             *guard = None;
+            self.set_backend_pid(None);
+        }
+        self.finish_backend_stop(previous_phase, stopped);
+        if stopped {
             return Ok(());
         }
 
@@ -42,9 +62,114 @@ impl BackendState {
         ))
     }
 
+    /// Lets the backend flush adapters and its database before any signal is sent.
+    fn request_backend_shutdown_and_wait(&self, pid: u32) -> bool {
+        let grace_ms = backend::runtime::backend_shutdown_grace_ms(append_desktop_log);
+        if grace_ms == 0 {
+            return false;
+        }
+        let started = Instant::now();
+        match self.request_backend_shutdown(BACKEND_SHUTDOWN_REQUEST_TIMEOUT_MS) {
+            Some(status) if (200..300).contains(&status) => {}
+            Some(status) => {
+                self.append_desktop_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!(
+                        "backend graceful shutdown request rejected with HTTP status {status}: pid={pid}, falling back to signals"
+                    ),
+                );
+                return false;
+            }
+            None => {
+                self.append_desktop_log(&format!(
+                    "backend graceful shutdown request got no response: pid={pid}, falling back to signals"
+                ));
+                return false;
+            }
+        }
+
+        if self.wait_for_managed_child_exit(pid, Duration::from_millis(grace_ms)) {
+            self.append_desktop_log(&format!(
+                "backend exited after graceful shutdown request: pid={pid}, elapsed_ms={}",
+                started.elapsed().as_millis()
+            ));
+            return true;
+        }
+        self.append_desktop_log_at(
+            DesktopLogLevel::Warn,
+            &format!(
+                "backend did not exit within {grace_ms}ms of graceful shutdown request: pid={pid}, sending signals"
+            ),
+        );
+        false
+    }
+
+    /// Plugin subprocesses wind down on their own once the backend exits on request, so
+    /// they get the stop timeout before any signal; the signal pass gets what is left.
+    #[cfg(unix)]
+    fn wait_for_group_after_requested_exit(
+        &self,
+        pid: u32,
+        exited_on_request: bool,
+        timeout: Duration,
+    ) -> Duration {
+        if !exited_on_request {
+            return timeout;
+        }
+        let started = Instant::now();
+        if !process_control::wait_for_process_group_exit(pid, timeout) {
+            self.append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "backend process group still running {}ms after the backend exited on request: pid={pid}, sending signals",
+                    timeout.as_millis()
+                ),
+            );
+        }
+        timeout.saturating_sub(started.elapsed())
+    }
+
+    #[cfg(not(unix))]
+    fn wait_for_group_after_requested_exit(
+        &self,
+        _pid: u32,
+        _exited_on_request: bool,
+        timeout: Duration,
+    ) -> Duration {
+        timeout
+    }
+
+    /// Polls the child under short lock holds; a child that is gone or was
+    /// already reaped counts as exited.
+    fn wait_for_managed_child_exit(&self, pid: u32, timeout: Duration) -> bool {
+        let started = Instant::now();
+        loop {
+            {
+                let mut guard = match self.child.lock() {
+                    Ok(guard) => guard,
+                    Err(error) => error.into_inner(),
+                };
+                match guard.as_mut() {
+                    Some(child) if child.id() == pid => match child.try_wait() {
+                        Ok(Some(_)) => return true,
+                        Ok(None) => {}
+                        Err(_) => return false,
+                    },
+                    _ => return true,
+                }
+            }
+            if started.elapsed() >= timeout {
+                return false;
+            }
+            thread::sleep(CHILD_EXIT_POLL_INTERVAL);
+        }
+    }
+
     #[cfg(target_os = "windows")]
     pub(crate) fn stop_backend_for_system_shutdown(&self, timeout: Duration) -> Result<(), String> {
-        self.stop_backend_log_rotation_worker();
+        self.stop_backend_supervisor();
+        self.stop_liveness_watchdog();
+        self.stop_resource_monitor();
         let mut guard = self
             .child
             .lock()
@@ -54,12 +179,18 @@ impl BackendState {
             return Ok(());
         };
 
-        if process_control::stop_child_process_for_system_shutdown(
+        let previous_phase = self.begin_backend_stop();
+        let stopped = process_control::stop_child_process_for_system_shutdown(
             child,
             timeout,
-            append_desktop_log,
-        ) {
+            append_desktop_log_at,
+        );
+        if stopped {
             *guard = None;
+            self.set_backend_pid(None);
+        }
+        self.finish_backend_stop(previous_phase, stopped);
+        if stopped {
             return Ok(());
         }
 
@@ -68,100 +199,4 @@ impl BackendState {
             timeout.as_millis()
         ))
     }
-
-    pub(crate) fn stop_backend_log_rotation_worker(&self) {
-        match self.log_rotator_stop.lock() {
-            Ok(mut guard) => {
-                if let Some(flag) = guard.take() {
-                    flag.store(true, Ordering::Relaxed);
-                }
-            }
-            Err(error) => {
-                append_desktop_log(&format!(
-                    "backend log rotator stop flag lock poisoned: {error}"
-                ));
-            }
-        }
-    }
-
-    fn child_matches_pid_and_alive(&self, child_pid: u32) -> bool {
-        let mut guard = match self.child.lock() {
-            Ok(guard) => guard,
-            Err(error) => {
-                append_desktop_log(&format!(
-                    "backend child lock poisoned while checking log rotator worker pid={child_pid}: {error}"
-                ));
-                return false;
-            }
-        };
-
-        let Some(child) = guard.as_mut() else {
-            return false;
-        };
-        if child.id() != child_pid {
-            return false;
-        }
-
-        match child.try_wait() {
-            Ok(None) => true,
-            Ok(Some(status)) => {
-                append_desktop_log(&format!(
-                    "backend process exited, stop log rotator worker: pid={child_pid}, status={status}"
-                ));
-                false
-            }
-            Err(error) => {
-                append_desktop_log(&format!(
-                    "failed to poll backend process status for log rotator worker pid={child_pid}: {error}"
-                ));
-                false
-            }
-        }
-    }
-
-    pub(crate) fn start_backend_log_rotation_worker(
-        &self,
-        app: &AppHandle,
-        log_path: PathBuf,
-        child_pid: u32,
-    ) {
-        self.stop_backend_log_rotation_worker();
-        let stop_flag = Arc::new(AtomicBool::new(false));
-        match self.log_rotator_stop.lock() {
-            Ok(mut guard) => {
-                *guard = Some(stop_flag.clone());
-            }
-            Err(error) => {
-                append_desktop_log(&format!(
-                    "backend log rotator stop flag lock poisoned on start: {error}"
-                ));
-                return;
-            }
-        }
-
-        let app_handle = app.clone();
-        thread::spawn(move || {
-            let log_scope = format!("backend(pid={child_pid})");
-            loop {
-                if stop_flag.load(Ordering::Relaxed) {
-                    break;
-                }
-                thread::sleep(BACKEND_LOG_ROTATION_CHECK_INTERVAL);
-                if stop_flag.load(Ordering::Relaxed) {
-                    break;
-                }
-                let state = app_handle.state::<BackendState>();
-                if !state.child_matches_pid_and_alive(child_pid) {
-                    break;
-                }
-                logging::rotate_log_if_needed(
-                    &log_path,
-                    BACKEND_LOG_MAX_BYTES,
-                    LOG_BACKUP_COUNT,
-                    &log_scope,
-                    true,
-                );
-            }
-        });
-    }
 }
@@ -1,58 +1,141 @@
 use std::{
     env, fs,
     path::Path,
-    thread,
+    sync::atomic::{AtomicBool, Ordering},
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use tauri::AppHandle;
-
 use crate::{
-    append_desktop_log, backend, AtomicFlagGuard, BackendState, BACKEND_TIMEOUT_ENV,
-    PACKAGED_BACKEND_TIMEOUT_FALLBACK_MS,
+    append_desktop_log, append_desktop_log_at,
+    backend::{
+        self,
+        host::BackendHost,
+        readiness_probe::{ReadinessProbeChain, ReadinessProbeContext, ReadinessProbeOutcome},
+        startup_cancel::{sleep_unless_cancelled, BACKEND_STARTUP_CANCELLED},
+        startup_progress::{
+            classify_startup_heartbeat, StartupPhaseReport, StartupProgress,
+            StartupProgressThrottle, StartupStage,
+        },
+    },
+    logging::DesktopLogLevel,
+    BackendState, BACKEND_TIMEOUT_ENV, PACKAGED_BACKEND_TIMEOUT_FALLBACK_MS,
 };
 
 impl BackendState {
-    pub(crate) fn ensure_backend_ready(&self, app: &AppHandle) -> Result<(), String> {
-        if self.ping_backend(backend::runtime::backend_ping_timeout_ms(
-            append_desktop_log,
-        )) {
-            append_desktop_log("backend already reachable, skip spawn");
+    pub(crate) fn ensure_backend_ready(&self, app: &impl BackendHost) -> Result<(), String> {
+        let result = self.attach_or_spawn_backend(app);
+        app.backend_status_changed();
+        result
+    }
+
+    fn attach_or_spawn_backend(&self, app: &impl BackendHost) -> Result<(), String> {
+        let has_managed_child = self.has_live_managed_child()?;
+        if !has_managed_child {
+            if self.reattach_detached_backend(app) {
+                return Ok(());
+            }
+            // Must run before attach: our own orphan would otherwise look like an unmanaged backend.
+            self.cleanup_orphaned_backend(app);
+        }
+        if has_managed_child
+            && self.ping_backend(backend::runtime::backend_ping_timeout_ms(
+                append_desktop_log,
+            ))
+        {
+            self.append_desktop_log("backend already reachable, skip spawn");
+            self.transition_backend_phase(backend::state_machine::BackendPhase::Ready);
             return Ok(());
         }
+        if !has_managed_child {
+            if let Some(attached) = self.identify_running_backend() {
+                self.attach_running_backend(attached);
+                return Ok(());
+            }
+        }
 
         if env::var("ASTRBOT_BACKEND_AUTO_START").unwrap_or_else(|_| "1".to_string()) == "0" {
-            append_desktop_log("backend auto-start disabled by ASTRBOT_BACKEND_AUTO_START=0");
+            self.append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                "backend auto-start disabled by ASTRBOT_BACKEND_AUTO_START=0",
+            );
             return Err(
                 "Backend auto-start is disabled (ASTRBOT_BACKEND_AUTO_START=0).".to_string(),
             );
         }
 
-        let _spawn_guard = AtomicFlagGuard::try_set(&self.is_spawning)
-            .ok_or_else(|| "Backend action already in progress.".to_string())?;
+        let _spawn_guard =
+            self.begin_backend_action(backend::state_machine::BackendPhase::Spawning)?;
+        let cancel_scope = self.begin_startup_cancel_scope();
         let plan = self.resolve_launch_plan(app)?;
-        self.start_backend_process(app, &plan)?;
-        self.wait_for_backend(&plan)
+        self.spawn_and_wait_for_backend(app, &plan, &cancel_scope)?;
+        self.start_liveness_watchdog(app, &plan);
+        Ok(())
     }
 
-    pub(crate) fn wait_for_backend(&self, plan: &crate::LaunchPlan) -> Result<(), String> {
+    pub(crate) fn wait_for_backend(
+        &self,
+        plan: &crate::LaunchPlan,
+        cancel: &AtomicBool,
+    ) -> Result<(), String> {
         let timeout_ms = backend::config::resolve_backend_timeout_ms(
             plan.packaged_mode,
             BACKEND_TIMEOUT_ENV,
             20_000,
             PACKAGED_BACKEND_TIMEOUT_FALLBACK_MS,
         );
-        let readiness = backend::runtime::backend_readiness_config(plan, append_desktop_log);
+        let readiness =
+            backend::runtime::backend_readiness_config(plan, &self.profile_id, append_desktop_log);
         let startup_idle_timeout = Duration::from_millis(readiness.startup_idle_timeout_ms);
         let start_time = Instant::now();
         let mut tcp_ready_logged = false;
         let mut ever_tcp_reachable = false;
         let mut startup_heartbeat_state = StartupHeartbeatTracker::new();
+        let mut progress_throttle = StartupProgressThrottle::default();
+        let mut probe_chain = ReadinessProbeChain::new(&readiness.probes);
+        // Output pumps start after the spawn is recorded, so this skips lines from earlier runs.
+        let output_since_ms = self.lifecycle_snapshot().started_at_ms.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default()
+        });
+        self.append_desktop_log_at(
+            DesktopLogLevel::Debug,
+            &format!(
+                "waiting for backend readiness: probes={}",
+                probe_chain.describe()
+            ),
+        );
 
         loop {
-            let (http_status, tcp_reachable) =
-                self.probe_backend_readiness(&readiness.path, readiness.probe_timeout_ms);
-            if matches!(http_status, Some(status_code) if (200..400).contains(&status_code)) {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(BACKEND_STARTUP_CANCELLED.to_string());
+            }
+            let tcp_reachable = self.ping_backend(
+                readiness
+                    .probe_timeout_ms
+                    .min(crate::BACKEND_READY_TCP_PROBE_TIMEOUT_MAX_MS),
+            );
+            let outcome = self.run_readiness_probes(
+                &mut probe_chain,
+                &ReadinessProbeContext {
+                    default_timeout_ms: readiness.probe_timeout_ms,
+                    heartbeat_seen: startup_heartbeat_state.last_seen_at.is_some(),
+                    heartbeat_phase: startup_heartbeat_state.report.phase.as_deref(),
+                    output_since_ms,
+                },
+            );
+            let http_status = outcome.http_status;
+            if outcome.ready {
+                self.report_startup_progress(StartupProgress {
+                    pid: self.managed_child_pid(),
+                    elapsed_ms: start_time.elapsed().as_millis() as u64,
+                    timeout_ms: timeout_ms.map(|limit| limit.as_millis() as u64),
+                    tcp_reachable,
+                    http_status,
+                    ..StartupProgress::new(StartupStage::Ready)
+                });
+                self.transition_backend_phase(backend::state_machine::BackendPhase::Ready);
                 return Ok(());
             }
             let wall_now = SystemTime::now();
@@ -74,22 +157,45 @@ impl BackendState {
             if tcp_reachable {
                 ever_tcp_reachable = true;
                 if !tcp_ready_logged {
-                    append_desktop_log(
+                    self.append_desktop_log_at(
+                        DesktopLogLevel::Debug,
                         "backend TCP port is reachable but HTTP dashboard is not ready yet; waiting",
                     );
                     tcp_ready_logged = true;
                 }
             }
 
+            let progress = StartupProgress {
+                pid: Some(child_pid),
+                elapsed_ms: start_time.elapsed().as_millis() as u64,
+                timeout_ms: timeout_ms.map(|limit| limit.as_millis() as u64),
+                tcp_reachable,
+                http_status,
+                heartbeat: classify_startup_heartbeat(
+                    readiness.startup_heartbeat_path.is_some(),
+                    startup_heartbeat_state.last_progress_at,
+                    monotonic_now,
+                ),
+                heartbeat_age_ms: startup_heartbeat_state
+                    .last_seen_at
+                    .and_then(|seen_at| wall_now.duration_since(seen_at).ok())
+                    .map(|age| age.as_millis() as u64),
+                report: startup_heartbeat_state.report.clone(),
+                ..StartupProgress::new(StartupStage::WaitingForHttp)
+            };
+            if progress_throttle.should_publish(&progress, monotonic_now) {
+                self.report_startup_progress(progress);
+            }
+
             if let Some(limit) = timeout_ms {
                 if start_time.elapsed() >= limit {
                     self.log_backend_readiness_timeout(
                         limit,
                         &readiness,
                         wall_now,
-                        http_status,
+                        &outcome,
                         ever_tcp_reachable,
-                        startup_heartbeat_state.last_seen_at,
+                        &startup_heartbeat_state,
                     );
                     return Err(format!(
                         "Timed out after {}ms waiting for backend startup.",
@@ -98,22 +204,12 @@ impl BackendState {
                 }
             }
 
-            thread::sleep(Duration::from_millis(readiness.poll_interval_ms));
+            if !sleep_unless_cancelled(cancel, Duration::from_millis(readiness.poll_interval_ms)) {
+                return Err(BACKEND_STARTUP_CANCELLED.to_string());
+            }
         }
     }
 
-    fn probe_backend_readiness(
-        &self,
-        ready_http_path: &str,
-        probe_timeout_ms: u64,
-    ) -> (Option<u16>, bool) {
-        let http_status =
-            self.request_backend_status_code("GET", ready_http_path, probe_timeout_ms, None, None);
-        let tcp_timeout_ms = probe_timeout_ms.min(crate::BACKEND_READY_TCP_PROBE_TIMEOUT_MAX_MS);
-        let tcp_reachable = self.ping_backend(tcp_timeout_ms);
-        (http_status, tcp_reachable)
-    }
-
     fn live_child_pid(&self) -> Result<u32, String> {
         let mut guard = self
             .child
@@ -125,9 +221,14 @@ impl BackendState {
             match child.try_wait() {
                 Ok(Some(status)) => {
                     *guard = None;
-                    Err(format!(
-                        "Backend process exited before becoming reachable: {status}"
-                    ))
+                    self.set_backend_pid(None);
+                    self.record_backend_crashed(status);
+                    let mut message =
+                        format!("Backend process exited before becoming reachable: {status}");
+                    if let Some(stderr_tail) = self.describe_recent_backend_stderr() {
+                        message.push_str(&format!("\nRecent backend stderr:\n{stderr_tail}"));
+                    }
+                    Err(message)
                 }
                 Ok(None) => Ok(pid),
                 Err(error) => Err(format!("Failed to poll backend process status: {error}")),
@@ -142,33 +243,54 @@ impl BackendState {
         timeout: Duration,
         readiness: &backend::config::BackendReadinessConfig,
         now: SystemTime,
-        last_http_status: Option<u16>,
+        last_outcome: &ReadinessProbeOutcome,
         tcp_reachable: bool,
-        last_startup_heartbeat_at: Option<SystemTime>,
+        startup_heartbeat: &StartupHeartbeatTracker,
     ) {
-        let last_http_status_text = last_http_status
+        let last_http_status_text = last_outcome
+            .http_status
             .map(|status| status.to_string())
             .unwrap_or_else(|| "none".to_string());
-        let startup_heartbeat_age_ms = describe_heartbeat_age(last_startup_heartbeat_at, now);
-        append_desktop_log(&format!(
-            "backend HTTP readiness check timed out after {}ms: backend_url={}, path={}, probe_timeout_ms={}, tcp_reachable={}, last_http_status={}, startup_heartbeat_age_ms={}",
+        let startup_heartbeat_age_ms = describe_heartbeat_age(startup_heartbeat.last_seen_at, now);
+        self.append_desktop_log_at(DesktopLogLevel::Error, &format!(
+            "backend HTTP readiness check timed out after {}ms: backend_url={}, path={}, probe_timeout_ms={}, tcp_reachable={}, last_http_status={}, startup_heartbeat_age_ms={}, startup_phase={}, pending_probe={}",
             timeout.as_millis(),
-            self.backend_url,
+            self.backend_url(),
             readiness.path,
             readiness.probe_timeout_ms,
             tcp_reachable,
             last_http_status_text,
-            startup_heartbeat_age_ms
+            startup_heartbeat_age_ms,
+            startup_heartbeat.report.phase.as_deref().unwrap_or("none"),
+            last_outcome.pending.as_deref().unwrap_or("none")
         ));
     }
 }
 
+// Keep in sync with STARTUP_HEARTBEAT_SCHEMA_VERSION in scripts/backend/templates/launch_backend.py.
+const STARTUP_HEARTBEAT_SCHEMA_VERSION: u32 = 2;
+
+/// Version 1 files predate `schema_version` and only carry pid, state and timestamp.
 #[derive(serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 struct StartupHeartbeatFile {
+    #[serde(default = "legacy_startup_heartbeat_schema_version")]
+    schema_version: u32,
     pid: u32,
     state: StartupHeartbeatState,
     updated_at_ms: u64,
+    #[serde(default)]
+    phase: Option<String>,
+    #[serde(default)]
+    progress: Option<f64>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    warnings: Vec<String>,
+}
+
+fn legacy_startup_heartbeat_schema_version() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
@@ -178,12 +300,19 @@ enum StartupHeartbeatState {
     Stopping,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
+struct StartupHeartbeat {
+    updated_at: SystemTime,
+    report: StartupPhaseReport,
+}
+
+#[derive(Debug, Clone)]
 struct StartupHeartbeatTracker {
     last_seen_at: Option<SystemTime>,
     last_progress_at: Option<Instant>,
     consecutive_invalid_reads: u8,
     logged_fresh: bool,
+    report: StartupPhaseReport,
 }
 
 impl StartupHeartbeatTracker {
@@ -193,19 +322,65 @@ impl StartupHeartbeatTracker {
             last_progress_at: None,
             consecutive_invalid_reads: 0,
             logged_fresh: false,
+            report: StartupPhaseReport::default(),
         }
     }
 }
 
 const STARTUP_HEARTBEAT_INVALID_READ_THRESHOLD: u8 = 2;
 
-fn read_startup_heartbeat_updated_at(path: &Path, expected_pid: u32) -> Option<SystemTime> {
+fn read_startup_heartbeat(path: &Path, expected_pid: u32) -> Option<StartupHeartbeat> {
     let payload = fs::read_to_string(path).ok()?;
     let heartbeat: StartupHeartbeatFile = serde_json::from_str(&payload).ok()?;
-    if heartbeat.pid != expected_pid || heartbeat.state != StartupHeartbeatState::Starting {
+    if !(1..=STARTUP_HEARTBEAT_SCHEMA_VERSION).contains(&heartbeat.schema_version)
+        || heartbeat.pid != expected_pid
+        || heartbeat.state != StartupHeartbeatState::Starting
+    {
         return None;
     }
-    UNIX_EPOCH.checked_add(Duration::from_millis(heartbeat.updated_at_ms))
+    Some(StartupHeartbeat {
+        updated_at: UNIX_EPOCH.checked_add(Duration::from_millis(heartbeat.updated_at_ms))?,
+        report: StartupPhaseReport {
+            phase: heartbeat.phase.filter(|phase| !phase.trim().is_empty()),
+            phase_progress: heartbeat
+                .progress
+                .filter(|progress| progress.is_finite())
+                .map(|progress| progress.clamp(0.0, 1.0)),
+            message: heartbeat
+                .message
+                .filter(|message| !message.trim().is_empty()),
+            warnings: heartbeat.warnings,
+        },
+    })
+}
+
+fn startup_phase_report_moved(previous: &StartupPhaseReport, current: &StartupPhaseReport) -> bool {
+    (&previous.phase, previous.phase_progress, &previous.message)
+        != (&current.phase, current.phase_progress, &current.message)
+}
+
+fn log_startup_phase_report(previous: &StartupPhaseReport, current: &StartupPhaseReport) {
+    if startup_phase_report_moved(previous, current) {
+        append_desktop_log(&format!(
+            "backend startup phase: phase={}, progress={}, message={}",
+            current.phase.as_deref().unwrap_or("none"),
+            current
+                .phase_progress
+                .map(|progress| format!("{:.0}%", progress * 100.0))
+                .unwrap_or_else(|| "none".to_string()),
+            current.message.as_deref().unwrap_or("none")
+        ));
+    }
+    for warning in current
+        .warnings
+        .iter()
+        .filter(|warning| !previous.warnings.contains(warning))
+    {
+        append_desktop_log_at(
+            DesktopLogLevel::Warn,
+            &format!("backend startup warning: {warning}"),
+        );
+    }
 }
 
 fn startup_heartbeat_progress_is_fresh(
@@ -244,7 +419,8 @@ fn step_startup_heartbeat(
     state: &mut StartupHeartbeatTracker,
 ) -> Result<(), String> {
     let previous = state.last_seen_at;
-    let current = read_startup_heartbeat_updated_at(heartbeat_path, child_pid);
+    let heartbeat = read_startup_heartbeat(heartbeat_path, child_pid);
+    let current = heartbeat.as_ref().map(|heartbeat| heartbeat.updated_at);
 
     match (previous, current) {
         (Some(previous), None) => {
@@ -254,7 +430,7 @@ fn step_startup_heartbeat(
             }
 
             let heartbeat_age_ms = describe_heartbeat_age(Some(previous), wall_now);
-            append_desktop_log(&format!(
+            append_desktop_log_at(DesktopLogLevel::Warn, &format!(
                 "backend startup heartbeat disappeared or became invalid before HTTP dashboard became ready: last_valid_age_ms={heartbeat_age_ms}"
             ));
             Err(
@@ -274,8 +450,18 @@ fn step_startup_heartbeat(
             };
             state.last_seen_at = Some(updated_at);
 
+            // A new phase, fraction or message counts as progress even if the timestamp stalls.
+            let mut report_moved = false;
+            if let Some(heartbeat) = heartbeat.filter(|heartbeat| heartbeat.report != state.report)
+            {
+                log_startup_phase_report(&state.report, &heartbeat.report);
+                report_moved = startup_phase_report_moved(&state.report, &heartbeat.report);
+                state.report = heartbeat.report;
+            }
+
             if previous.is_none()
                 || Some(updated_at) != previous
+                || report_moved
                 || state.last_progress_at.is_none()
             {
                 state.last_progress_at = Some(monotonic_now);
@@ -287,14 +473,16 @@ fn step_startup_heartbeat(
                 idle_timeout,
             ) {
                 if !state.logged_fresh {
-                    append_desktop_log(
+                    append_desktop_log_at(
+                        DesktopLogLevel::Debug,
                         "backend startup heartbeat is fresh while HTTP dashboard is not ready yet; waiting",
                     );
                     state.logged_fresh = true;
                 }
                 Ok(())
             } else {
-                append_desktop_log(
+                append_desktop_log_at(
+                    DesktopLogLevel::Warn,
                     "backend startup heartbeat went stale before HTTP dashboard became ready",
                 );
                 Err(format!(
@@ -342,7 +530,7 @@ mod tests {
         )
         .expect("write heartbeat file");
 
-        assert_eq!(read_startup_heartbeat_updated_at(&heartbeat_path, 42), None);
+        assert_eq!(read_startup_heartbeat(&heartbeat_path, 42), None);
     }
 
     #[test]
@@ -355,6 +543,7 @@ mod tests {
             last_progress_at: Some(monotonic_now),
             consecutive_invalid_reads: 0,
             logged_fresh: false,
+            report: StartupPhaseReport::default(),
         };
 
         let first_result = step_startup_heartbeat(
@@ -395,6 +584,7 @@ mod tests {
             last_progress_at: Some(monotonic_now),
             consecutive_invalid_reads: 0,
             logged_fresh: false,
+            report: StartupPhaseReport::default(),
         };
 
         let result = step_startup_heartbeat(
@@ -440,11 +630,92 @@ mod tests {
         .expect("write heartbeat file");
 
         assert_eq!(
-            read_startup_heartbeat_updated_at(&heartbeat_path, 42),
+            read_startup_heartbeat(&heartbeat_path, 42).map(|heartbeat| heartbeat.updated_at),
             UNIX_EPOCH.checked_add(Duration::from_millis(u64::MAX))
         );
     }
 
+    #[test]
+    fn read_startup_heartbeat_accepts_v2_report_and_clamps_progress() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let heartbeat_path = temp_dir.path().join("startup-heartbeat.json");
+        std::fs::write(
+            &heartbeat_path,
+            r#"{"schema_version":2,"pid":42,"state":"starting","updated_at_ms":5000,"phase":"loading_plugins","progress":1.5,"message":"Loading plugin demo","warnings":["slow disk"]}"#,
+        )
+        .expect("write heartbeat file");
+
+        assert_eq!(
+            read_startup_heartbeat(&heartbeat_path, 42),
+            Some(StartupHeartbeat {
+                updated_at: UNIX_EPOCH + Duration::from_millis(5000),
+                report: StartupPhaseReport {
+                    phase: Some("loading_plugins".to_string()),
+                    phase_progress: Some(1.0),
+                    message: Some("Loading plugin demo".to_string()),
+                    warnings: vec!["slow disk".to_string()],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn read_startup_heartbeat_rejects_newer_schema_version() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let heartbeat_path = temp_dir.path().join("startup-heartbeat.json");
+        std::fs::write(
+            &heartbeat_path,
+            r#"{"schema_version":3,"pid":42,"state":"starting","updated_at_ms":5000}"#,
+        )
+        .expect("write heartbeat file");
+
+        assert_eq!(read_startup_heartbeat(&heartbeat_path, 42), None);
+    }
+
+    #[test]
+    fn step_startup_heartbeat_treats_phase_change_as_progress() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let heartbeat_path = temp_dir.path().join("startup-heartbeat.json");
+        let monotonic_now = Instant::now();
+        let mut tracker = StartupHeartbeatTracker::new();
+        let write_phase = |phase: &str| {
+            std::fs::write(
+                &heartbeat_path,
+                format!(
+                    r#"{{"schema_version":2,"pid":42,"state":"starting","updated_at_ms":5000,"phase":"{phase}"}}"#
+                ),
+            )
+            .expect("write heartbeat file");
+        };
+
+        write_phase("installing_dependencies");
+        let first = step_startup_heartbeat(
+            &heartbeat_path,
+            42,
+            UNIX_EPOCH + Duration::from_millis(5000),
+            monotonic_now,
+            Duration::from_secs(1),
+            &mut tracker,
+        );
+        write_phase("loading_plugins");
+        let second = step_startup_heartbeat(
+            &heartbeat_path,
+            42,
+            UNIX_EPOCH + Duration::from_millis(6500),
+            monotonic_now + Duration::from_millis(1500),
+            Duration::from_secs(1),
+            &mut tracker,
+        );
+
+        assert_eq!(first, Ok(()));
+        assert_eq!(second, Ok(()));
+        assert_eq!(tracker.report.phase.as_deref(), Some("loading_plugins"));
+        assert_eq!(
+            tracker.last_progress_at,
+            Some(monotonic_now + Duration::from_millis(1500))
+        );
+    }
+
     #[test]
     fn describe_heartbeat_age_distinguishes_future_timestamp_from_missing() {
         assert_eq!(
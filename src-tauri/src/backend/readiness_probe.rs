@@ -0,0 +1,411 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{
+    backend::output::BackendOutputStream, BackendState, BACKEND_READY_PROBE_TIMEOUT_MAX_MS,
+    BACKEND_READY_PROBE_TIMEOUT_MIN_MS,
+};
+
+/// One step of the readiness chain. Every probe must pass, in order, for the backend to
+/// count as ready; `timeoutMs` overrides the global probe timeout for network probes.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    deny_unknown_fields
+)]
+pub(crate) enum ReadinessProbe {
+    Tcp {
+        timeout_ms: Option<u64>,
+    },
+    /// Passes on `expectStatus`, or on any 2xx/3xx status when unset.
+    Http {
+        path: String,
+        expect_status: Option<u16>,
+        timeout_ms: Option<u64>,
+    },
+    /// Passes when the JSON pointer resolves to `equals`, or to any non-null value when unset.
+    HttpJson {
+        path: String,
+        pointer: String,
+        equals: Option<Value>,
+        timeout_ms: Option<u64>,
+    },
+    /// Passes once the startup heartbeat is valid and, if set, reports `phase`.
+    Heartbeat {
+        phase: Option<String>,
+    },
+    /// Passes once a backend output line written during this startup matches `pattern`.
+    LogLine {
+        pattern: String,
+        stream: Option<BackendOutputStream>,
+    },
+}
+
+impl ReadinessProbe {
+    pub(crate) fn default_http(path: &str) -> Self {
+        Self::Http {
+            path: path.to_string(),
+            expect_status: None,
+            timeout_ms: None,
+        }
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Tcp { .. } => "tcp".to_string(),
+            Self::Http {
+                path,
+                expect_status: Some(status),
+                ..
+            } => format!("http {path} == {status}"),
+            Self::Http { path, .. } => format!("http {path}"),
+            Self::HttpJson { path, pointer, .. } => format!("httpJson {path}#{pointer}"),
+            Self::Heartbeat { phase: Some(phase) } => format!("heartbeat phase={phase}"),
+            Self::Heartbeat { phase: None } => "heartbeat".to_string(),
+            Self::LogLine { pattern, .. } => format!("logLine /{pattern}/"),
+        }
+    }
+
+    fn timeout_ms(&self) -> Option<u64> {
+        match self {
+            Self::Tcp { timeout_ms }
+            | Self::Http { timeout_ms, .. }
+            | Self::HttpJson { timeout_ms, .. } => *timeout_ms,
+            Self::Heartbeat { .. } | Self::LogLine { .. } => None,
+        }
+    }
+}
+
+fn normalize_probe(probe: ReadinessProbe) -> Result<ReadinessProbe, String> {
+    let normalize_path = |path: String| {
+        let trimmed = path.trim();
+        if trimmed.starts_with('/') {
+            trimmed.to_string()
+        } else {
+            format!("/{trimmed}")
+        }
+    };
+    let clamp_timeout = |timeout_ms: Option<u64>| {
+        timeout_ms.map(|timeout_ms| {
+            timeout_ms.clamp(
+                BACKEND_READY_PROBE_TIMEOUT_MIN_MS,
+                BACKEND_READY_PROBE_TIMEOUT_MAX_MS,
+            )
+        })
+    };
+    Ok(match probe {
+        ReadinessProbe::Tcp { timeout_ms } => ReadinessProbe::Tcp {
+            timeout_ms: clamp_timeout(timeout_ms),
+        },
+        ReadinessProbe::Http {
+            path,
+            expect_status,
+            timeout_ms,
+        } => ReadinessProbe::Http {
+            path: normalize_path(path),
+            expect_status,
+            timeout_ms: clamp_timeout(timeout_ms),
+        },
+        ReadinessProbe::HttpJson {
+            path,
+            pointer,
+            equals,
+            timeout_ms,
+        } => {
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return Err(format!(
+                    "httpJson pointer '{pointer}' must be empty or start with '/'"
+                ));
+            }
+            ReadinessProbe::HttpJson {
+                path: normalize_path(path),
+                pointer,
+                equals,
+                timeout_ms: clamp_timeout(timeout_ms),
+            }
+        }
+        ReadinessProbe::LogLine { pattern, stream } => {
+            Regex::new(&pattern)
+                .map_err(|error| format!("invalid logLine pattern '{pattern}': {error}"))?;
+            ReadinessProbe::LogLine { pattern, stream }
+        }
+        heartbeat @ ReadinessProbe::Heartbeat { .. } => heartbeat,
+    })
+}
+
+fn parse_readiness_probe_chain(value: Value) -> Result<Vec<ReadinessProbe>, String> {
+    let probes = serde_json::from_value::<Vec<ReadinessProbe>>(value)
+        .map_err(|error| format!("invalid readiness probe chain: {error}"))?;
+    if probes.is_empty() {
+        return Err("readiness probe chain is empty".to_string());
+    }
+    probes.into_iter().map(normalize_probe).collect()
+}
+
+/// Env wins over the `readinessProbes` setting; an invalid source is logged and skipped,
+/// and the default is a single HTTP probe on the legacy ready path.
+pub(crate) fn resolve_readiness_probe_chain<F>(
+    env_name: &str,
+    env_raw: Option<&str>,
+    settings_value: Option<&Value>,
+    default_path: &str,
+    mut log: F,
+) -> Vec<ReadinessProbe>
+where
+    F: FnMut(String),
+{
+    if let Some(raw) = env_raw.map(str::trim).filter(|raw| !raw.is_empty()) {
+        match serde_json::from_str::<Value>(raw)
+            .map_err(|error| format!("invalid JSON: {error}"))
+            .and_then(parse_readiness_probe_chain)
+        {
+            Ok(probes) => return probes,
+            Err(error) => log(format!("ignoring {env_name}: {error}")),
+        }
+    }
+    if let Some(value) = settings_value {
+        match parse_readiness_probe_chain(value.clone()) {
+            Ok(probes) => return probes,
+            Err(error) => log(format!("ignoring readinessProbes setting: {error}")),
+        }
+    }
+    vec![ReadinessProbe::default_http(default_path)]
+}
+
+fn json_pointer_matches(payload: &Value, pointer: &str, expected: Option<&Value>) -> bool {
+    match (payload.pointer(pointer), expected) {
+        (Some(actual), Some(expected)) => actual == expected,
+        (Some(actual), None) => !actual.is_null(),
+        (None, _) => false,
+    }
+}
+
+fn status_matches(status: Option<u16>, expected: Option<u16>) -> bool {
+    match (status, expected) {
+        (Some(status), Some(expected)) => status == expected,
+        (Some(status), None) => (200..400).contains(&status),
+        (None, _) => false,
+    }
+}
+
+/// Inputs the chain reads from the surrounding readiness loop.
+pub(crate) struct ReadinessProbeContext<'a> {
+    pub(crate) default_timeout_ms: u64,
+    pub(crate) heartbeat_seen: bool,
+    pub(crate) heartbeat_phase: Option<&'a str>,
+    pub(crate) output_since_ms: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ReadinessProbeOutcome {
+    pub(crate) ready: bool,
+    pub(crate) http_status: Option<u16>,
+    pub(crate) pending: Option<String>,
+}
+
+/// Compiled form of the configured chain; log-line matches stick for the rest of the wait.
+pub(crate) struct ReadinessProbeChain {
+    probes: Vec<(ReadinessProbe, Option<Regex>)>,
+    log_line_seen: Vec<bool>,
+}
+
+impl ReadinessProbeChain {
+    pub(crate) fn new(probes: &[ReadinessProbe]) -> Self {
+        let probes = probes
+            .iter()
+            .map(|probe| {
+                let regex = match probe {
+                    ReadinessProbe::LogLine { pattern, .. } => Regex::new(pattern).ok(),
+                    _ => None,
+                };
+                (probe.clone(), regex)
+            })
+            .collect::<Vec<_>>();
+        Self {
+            log_line_seen: vec![false; probes.len()],
+            probes,
+        }
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        self.probes
+            .iter()
+            .map(|(probe, _)| probe.describe())
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+impl BackendState {
+    /// Stops at the first failing probe so later, costlier probes only run once earlier ones pass.
+    pub(crate) fn run_readiness_probes(
+        &self,
+        chain: &mut ReadinessProbeChain,
+        context: &ReadinessProbeContext<'_>,
+    ) -> ReadinessProbeOutcome {
+        let mut outcome = ReadinessProbeOutcome::default();
+        for (index, (probe, regex)) in chain.probes.iter().enumerate() {
+            let timeout_ms = probe.timeout_ms().unwrap_or(context.default_timeout_ms);
+            let passed = match probe {
+                ReadinessProbe::Tcp { .. } => self.ping_backend(timeout_ms),
+                ReadinessProbe::Http {
+                    path,
+                    expect_status,
+                    ..
+                } => {
+                    let status =
+                        self.request_backend_status_code("GET", path, timeout_ms, None, None);
+                    outcome.http_status = status.or(outcome.http_status);
+                    status_matches(status, *expect_status)
+                }
+                ReadinessProbe::HttpJson {
+                    path,
+                    pointer,
+                    equals,
+                    ..
+                } => self
+                    .request_backend_json("GET", path, timeout_ms, None, None)
+                    .is_some_and(|payload| {
+                        json_pointer_matches(&payload, pointer, equals.as_ref())
+                    }),
+                ReadinessProbe::Heartbeat { phase } => {
+                    context.heartbeat_seen
+                        && phase
+                            .as_deref()
+                            .is_none_or(|phase| context.heartbeat_phase == Some(phase))
+                }
+                ReadinessProbe::LogLine { stream, .. } => {
+                    if !chain.log_line_seen[index] {
+                        chain.log_line_seen[index] = regex.as_ref().is_some_and(|regex| {
+                            self.backend_output
+                                .recent(*stream, usize::MAX)
+                                .iter()
+                                .filter(|line| line.timestamp_ms >= context.output_since_ms)
+                                .any(|line| regex.is_match(&line.text))
+                        });
+                    }
+                    chain.log_line_seen[index]
+                }
+            };
+            if !passed {
+                outcome.pending = Some(probe.describe());
+                return outcome;
+            }
+        }
+        outcome.ready = true;
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        json_pointer_matches, resolve_readiness_probe_chain, status_matches, ReadinessProbe,
+    };
+    use crate::backend::output::BackendOutputStream;
+
+    fn resolve(env_raw: Option<&str>, settings: Option<serde_json::Value>) -> Vec<ReadinessProbe> {
+        resolve_readiness_probe_chain(
+            "TEST_READY_PROBES",
+            env_raw,
+            settings.as_ref(),
+            "/api/stat/start-time",
+            |_| {},
+        )
+    }
+
+    #[test]
+    fn default_chain_is_the_legacy_http_probe() {
+        assert_eq!(
+            resolve(None, None),
+            vec![ReadinessProbe::default_http("/api/stat/start-time")]
+        );
+    }
+
+    #[test]
+    fn env_chain_overrides_settings_and_normalizes_probes() {
+        let probes = resolve(
+            Some(
+                r#"[{"kind":"tcp","timeoutMs":1},{"kind":"http","path":"healthz","expectStatus":204},{"kind":"logLine","pattern":"Uvicorn running","stream":"stderr"}]"#,
+            ),
+            Some(json!([{ "kind": "heartbeat" }])),
+        );
+
+        assert_eq!(
+            probes,
+            vec![
+                ReadinessProbe::Tcp {
+                    timeout_ms: Some(crate::BACKEND_READY_PROBE_TIMEOUT_MIN_MS)
+                },
+                ReadinessProbe::Http {
+                    path: "/healthz".to_string(),
+                    expect_status: Some(204),
+                    timeout_ms: None,
+                },
+                ReadinessProbe::LogLine {
+                    pattern: "Uvicorn running".to_string(),
+                    stream: Some(BackendOutputStream::Stderr),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_sources_fall_through_with_a_log() {
+        let mut logs = Vec::new();
+        let probes = resolve_readiness_probe_chain(
+            "TEST_READY_PROBES",
+            Some(r#"[{"kind":"logLine","pattern":"("}]"#),
+            Some(&json!([{ "kind": "heartbeat", "phase": "ready" }])),
+            "/api/stat/start-time",
+            |message| logs.push(message),
+        );
+        assert_eq!(
+            probes,
+            vec![ReadinessProbe::Heartbeat {
+                phase: Some("ready".to_string())
+            }]
+        );
+        assert!(logs[0].contains("invalid logLine pattern"));
+
+        let mut logs = Vec::new();
+        let probes = resolve_readiness_probe_chain(
+            "TEST_READY_PROBES",
+            None,
+            Some(&json!([{ "kind": "http", "path": "/x", "unexpected": true }])),
+            "/ready",
+            |message| logs.push(message),
+        );
+        assert_eq!(probes, vec![ReadinessProbe::default_http("/ready")]);
+        assert!(logs[0].contains("readinessProbes"));
+    }
+
+    #[test]
+    fn json_pointer_generalizes_the_start_time_check() {
+        let payload = json!({ "status": "ok", "data": { "start_time": 1_700_000_000 } });
+        assert!(json_pointer_matches(
+            &payload,
+            "/status",
+            Some(&json!("ok"))
+        ));
+        assert!(json_pointer_matches(&payload, "/data/start_time", None));
+        assert!(!json_pointer_matches(&payload, "/data/version", None));
+        assert!(!json_pointer_matches(
+            &payload,
+            "/status",
+            Some(&json!("error"))
+        ));
+    }
+
+    #[test]
+    fn status_match_defaults_to_success_or_redirect() {
+        assert!(status_matches(Some(302), None));
+        assert!(!status_matches(Some(503), None));
+        assert!(status_matches(Some(503), Some(503)));
+        assert!(!status_matches(None, Some(200)));
+    }
+}
@@ -0,0 +1,457 @@
+#[cfg(any(target_os = "linux", test))]
+use std::time::{Duration, Instant};
+
+use crate::BackendState;
+#[cfg(any(target_os = "linux", test))]
+use crate::{
+    backend, desktop_settings::ResourceLimitSettings, BACKEND_RSS_LIMIT_MAX_MB,
+    BACKEND_RSS_LIMIT_MIN_MB, BACKEND_RSS_RESTART_AFTER_MAX_SECS,
+    BACKEND_RSS_RESTART_AFTER_MIN_SECS, DEFAULT_BACKEND_RSS_RESTART_AFTER_SECS,
+};
+
+#[cfg(any(target_os = "linux", test))]
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackendResourceUsage {
+    pub(crate) rss_bytes: u64,
+    /// Percent of one CPU core; `None` until two samples are available.
+    pub(crate) cpu_percent: Option<f64>,
+    pub(crate) process_count: usize,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ResourceLimits {
+    pub(crate) rss_warn_bytes: Option<u64>,
+    pub(crate) rss_restart_bytes: Option<u64>,
+    pub(crate) restart_after: Duration,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ResourceAction {
+    None,
+    Warn,
+    Restart { over_for: Duration },
+}
+
+/// Unset, `0` and invalid values leave the corresponding threshold disabled.
+#[cfg(any(target_os = "linux", test))]
+pub(crate) fn resolve_resource_limits<F>(
+    settings: &ResourceLimitSettings,
+    mut log: F,
+) -> ResourceLimits
+where
+    F: FnMut(String),
+{
+    let mut rss_limit_bytes = |raw: Option<&str>, source: &str| {
+        let raw = raw?;
+        if raw.trim() == "0" {
+            return None;
+        }
+        let mb = backend::config::parse_clamped_u64(
+            raw,
+            source,
+            0,
+            BACKEND_RSS_LIMIT_MIN_MB,
+            BACKEND_RSS_LIMIT_MAX_MB,
+            &mut log,
+        );
+        (mb > 0).then(|| mb.saturating_mul(BYTES_PER_MB))
+    };
+    let rss_warn_bytes =
+        rss_limit_bytes(settings.rss_warn_mb.as_deref(), "resourceLimits.rssWarnMb");
+    let rss_restart_bytes = rss_limit_bytes(
+        settings.rss_restart_mb.as_deref(),
+        "resourceLimits.rssRestartMb",
+    );
+    let restart_after_secs = match settings.rss_restart_after_secs.as_deref() {
+        Some(raw) => backend::config::parse_clamped_u64(
+            raw,
+            "resourceLimits.rssRestartAfterSecs",
+            DEFAULT_BACKEND_RSS_RESTART_AFTER_SECS,
+            BACKEND_RSS_RESTART_AFTER_MIN_SECS,
+            BACKEND_RSS_RESTART_AFTER_MAX_SECS,
+            &mut log,
+        ),
+        None => DEFAULT_BACKEND_RSS_RESTART_AFTER_SECS,
+    };
+
+    ResourceLimits {
+        rss_warn_bytes,
+        rss_restart_bytes,
+        restart_after: Duration::from_secs(restart_after_secs),
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Default)]
+pub(crate) struct RssThresholdTracker {
+    warned: bool,
+    over_restart_since: Option<Instant>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl RssThresholdTracker {
+    /// Warns once per excursion above the warning limit; restarts only after RSS
+    /// has stayed above the restart limit for the whole sustained window.
+    pub(crate) fn observe(
+        &mut self,
+        rss_bytes: u64,
+        now: Instant,
+        limits: &ResourceLimits,
+    ) -> ResourceAction {
+        match limits.rss_restart_bytes {
+            Some(limit) if rss_bytes >= limit => {
+                let since = *self.over_restart_since.get_or_insert(now);
+                let over_for = now.saturating_duration_since(since);
+                if over_for >= limits.restart_after {
+                    self.over_restart_since = None;
+                    return ResourceAction::Restart { over_for };
+                }
+            }
+            _ => self.over_restart_since = None,
+        }
+
+        match limits.rss_warn_bytes {
+            Some(limit) if rss_bytes >= limit => {
+                if !self.warned {
+                    self.warned = true;
+                    return ResourceAction::Warn;
+                }
+            }
+            _ => self.warned = false,
+        }
+        ResourceAction::None
+    }
+
+    pub(crate) fn reset_restart_window(&mut self) {
+        self.over_restart_since = None;
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+pub(crate) fn compute_cpu_percent(
+    previous_ticks: u64,
+    ticks: u64,
+    elapsed: Duration,
+    ticks_per_second: u64,
+) -> Option<f64> {
+    let elapsed_secs = elapsed.as_secs_f64();
+    if elapsed_secs <= 0.0 || ticks_per_second == 0 {
+        return None;
+    }
+    // Descendants that exit take their CPU time with them, so the sum can shrink.
+    let busy_secs = ticks.saturating_sub(previous_ticks) as f64 / ticks_per_second as f64;
+    Some((busy_secs / elapsed_secs * 1000.0).round() / 10.0)
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Instant,
+    };
+
+    use super::{
+        compute_cpu_percent, resolve_resource_limits, BackendResourceUsage, ResourceAction,
+        RssThresholdTracker, BYTES_PER_MB,
+    };
+    use crate::{
+        backend::{host::BackendHost, watchdog},
+        desktop_settings, desktop_state,
+        logging::DesktopLogLevel,
+        process_control, restart_backend_flow, runtime_paths, BackendState, LaunchPlan,
+        BACKEND_RESOURCE_SAMPLE_INTERVAL,
+    };
+
+    impl BackendState {
+        fn register_resource_monitor_stop_flag(&self) -> Option<Arc<AtomicBool>> {
+            self.stop_resource_monitor();
+            let stop_flag = Arc::new(AtomicBool::new(false));
+            match self.resource_monitor_stop.lock() {
+                Ok(mut guard) => {
+                    *guard = Some(stop_flag.clone());
+                    Some(stop_flag)
+                }
+                Err(error) => {
+                    self.append_desktop_log(&format!(
+                        "backend resource monitor stop flag lock poisoned on start: {error}"
+                    ));
+                    None
+                }
+            }
+        }
+
+        /// Limits are read from the profile's `desktop_state.json` once per spawn, so
+        /// edits apply from the next backend start.
+        pub(crate) fn start_resource_monitor(
+            &self,
+            app: &impl BackendHost,
+            plan: &LaunchPlan,
+            child_pid: u32,
+        ) {
+            let Some(stop_flag) = self.register_resource_monitor_stop_flag() else {
+                return;
+            };
+            let limits = resolve_resource_limits(
+                &desktop_settings::read_resource_limit_settings(
+                    desktop_state::resolve_profile_desktop_state_path(
+                        &self.profile_id,
+                        plan.root_dir.as_deref(),
+                        runtime_paths::default_packaged_root_dir().as_deref(),
+                    )
+                    .as_deref(),
+                ),
+                |message| self.append_desktop_log(&message),
+            );
+
+            let app_handle = app.clone();
+            let profile_id = self.profile_id.clone();
+            thread::spawn(move || {
+                let Some(state) = app_handle.profile_state(&profile_id) else {
+                    return;
+                };
+                let ticks_per_second = process_control::clock_ticks_per_second();
+                let mut tracker = RssThresholdTracker::default();
+                let mut previous: Option<(u64, Instant)> = None;
+                loop {
+                    if !watchdog::sleep_unless_stopped(
+                        &state,
+                        &stop_flag,
+                        BACKEND_RESOURCE_SAMPLE_INTERVAL,
+                    ) || state.managed_child_pid() != Some(child_pid)
+                    {
+                        return;
+                    }
+                    let Some(sample) = process_control::sample_process_tree(child_pid) else {
+                        continue;
+                    };
+                    let now = Instant::now();
+                    let cpu_percent = previous.and_then(|(ticks, sampled_at)| {
+                        compute_cpu_percent(
+                            ticks,
+                            sample.cpu_ticks,
+                            now.saturating_duration_since(sampled_at),
+                            ticks_per_second,
+                        )
+                    });
+                    previous = Some((sample.cpu_ticks, now));
+                    state.set_resource_usage(Some(BackendResourceUsage {
+                        rss_bytes: sample.rss_bytes,
+                        cpu_percent,
+                        process_count: sample.process_count,
+                    }));
+
+                    if restart_backend_flow::is_backend_action_in_progress(&state) {
+                        tracker.reset_restart_window();
+                        continue;
+                    }
+                    let rss_mb = sample.rss_bytes / BYTES_PER_MB;
+                    match tracker.observe(sample.rss_bytes, now, &limits) {
+                        ResourceAction::None => {}
+                        ResourceAction::Warn => {
+                            let message = format!(
+                                "backend memory above warning limit: pid={child_pid}, rss_mb={rss_mb}, warn_mb={}, processes={}",
+                                limits.rss_warn_bytes.unwrap_or_default() / BYTES_PER_MB,
+                                sample.process_count
+                            );
+                            state.append_desktop_log_at(DesktopLogLevel::Warn, &message);
+                            state.append_restart_log(&message);
+                        }
+                        ResourceAction::Restart { over_for } => {
+                            state.append_restart_log(&format!(
+                                "backend memory stayed above restart limit for {}s, restarting: pid={child_pid}, rss_mb={rss_mb}, restart_mb={}, processes={}",
+                                over_for.as_secs(),
+                                limits.rss_restart_bytes.unwrap_or_default() / BYTES_PER_MB,
+                                sample.process_count
+                            ));
+                            match state.restart_backend(&app_handle, None) {
+                                Ok(()) => state.append_restart_log(
+                                    "backend restart after memory limit completed",
+                                ),
+                                Err(error) => state.append_restart_log(&format!(
+                                    "backend restart after memory limit failed: {error}"
+                                )),
+                            }
+                            app_handle.backend_status_changed();
+                        }
+                    }
+                }
+            });
+        }
+
+        pub(crate) fn stop_resource_monitor_flag(&self) {
+            match self.resource_monitor_stop.lock() {
+                Ok(mut guard) => {
+                    if let Some(flag) = guard.take() {
+                        flag.store(true, Ordering::Relaxed);
+                    }
+                }
+                Err(error) => {
+                    self.append_desktop_log(&format!(
+                        "backend resource monitor stop flag lock poisoned: {error}"
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl BackendState {
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn start_resource_monitor(
+        &self,
+        _app: &impl crate::backend::host::BackendHost,
+        _plan: &crate::LaunchPlan,
+        _child_pid: u32,
+    ) {
+    }
+
+    pub(crate) fn stop_resource_monitor(&self) {
+        #[cfg(target_os = "linux")]
+        self.stop_resource_monitor_flag();
+        self.set_resource_usage(None);
+    }
+
+    pub(crate) fn resource_usage(&self) -> Option<BackendResourceUsage> {
+        match self.resource_usage.lock() {
+            Ok(guard) => *guard,
+            Err(error) => *error.into_inner(),
+        }
+    }
+
+    fn set_resource_usage(&self, usage: Option<BackendResourceUsage>) {
+        match self.resource_usage.lock() {
+            Ok(mut guard) => *guard = usage,
+            Err(error) => {
+                self.append_desktop_log(&format!("backend resource usage lock poisoned: {error}"));
+                *error.into_inner() = usage;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{
+        compute_cpu_percent, resolve_resource_limits, ResourceAction, ResourceLimits,
+        RssThresholdTracker,
+    };
+    use crate::{desktop_settings::ResourceLimitSettings, DEFAULT_BACKEND_RSS_RESTART_AFTER_SECS};
+
+    const MB: u64 = 1024 * 1024;
+
+    fn limits() -> ResourceLimits {
+        ResourceLimits {
+            rss_warn_bytes: Some(1024 * MB),
+            rss_restart_bytes: Some(2048 * MB),
+            restart_after: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn resolve_resource_limits_disables_unset_zero_and_invalid_values() {
+        let resolved = resolve_resource_limits(&ResourceLimitSettings::default(), |_| {});
+        assert_eq!(resolved.rss_warn_bytes, None);
+        assert_eq!(resolved.rss_restart_bytes, None);
+        assert_eq!(
+            resolved.restart_after,
+            Duration::from_secs(DEFAULT_BACKEND_RSS_RESTART_AFTER_SECS)
+        );
+
+        let mut logs = Vec::new();
+        let resolved = resolve_resource_limits(
+            &ResourceLimitSettings {
+                rss_warn_mb: Some("0".to_string()),
+                rss_restart_mb: Some("lots".to_string()),
+                rss_restart_after_secs: Some("120".to_string()),
+            },
+            |message| logs.push(message),
+        );
+        assert_eq!(resolved.rss_warn_bytes, None);
+        assert_eq!(resolved.rss_restart_bytes, None);
+        assert_eq!(resolved.restart_after, Duration::from_secs(120));
+        assert!(logs
+            .iter()
+            .any(|line| line.contains("resourceLimits.rssRestartMb")));
+
+        let resolved = resolve_resource_limits(
+            &ResourceLimitSettings {
+                rss_warn_mb: Some("1".to_string()),
+                rss_restart_mb: Some("4096".to_string()),
+                rss_restart_after_secs: None,
+            },
+            |_| {},
+        );
+        assert_eq!(resolved.rss_warn_bytes, Some(64 * MB));
+        assert_eq!(resolved.rss_restart_bytes, Some(4096 * MB));
+    }
+
+    #[test]
+    fn rss_tracker_warns_once_per_excursion() {
+        let mut tracker = RssThresholdTracker::default();
+        let now = Instant::now();
+        assert_eq!(
+            tracker.observe(1500 * MB, now, &limits()),
+            ResourceAction::Warn
+        );
+        assert_eq!(
+            tracker.observe(1600 * MB, now, &limits()),
+            ResourceAction::None
+        );
+        assert_eq!(
+            tracker.observe(512 * MB, now, &limits()),
+            ResourceAction::None
+        );
+        assert_eq!(
+            tracker.observe(1500 * MB, now, &limits()),
+            ResourceAction::Warn
+        );
+    }
+
+    #[test]
+    fn rss_tracker_restarts_only_after_sustained_excursion() {
+        let mut tracker = RssThresholdTracker::default();
+        let start = Instant::now();
+        tracker.observe(3000 * MB, start, &limits());
+        assert_eq!(
+            tracker.observe(3000 * MB, start + Duration::from_secs(30), &limits()),
+            ResourceAction::None
+        );
+        // Dipping below the limit restarts the window.
+        tracker.observe(1500 * MB, start + Duration::from_secs(40), &limits());
+        tracker.observe(3000 * MB, start + Duration::from_secs(50), &limits());
+        assert_eq!(
+            tracker.observe(3000 * MB, start + Duration::from_secs(100), &limits()),
+            ResourceAction::None
+        );
+        assert_eq!(
+            tracker.observe(3000 * MB, start + Duration::from_secs(110), &limits()),
+            ResourceAction::Restart {
+                over_for: Duration::from_secs(60)
+            }
+        );
+    }
+
+    #[test]
+    fn compute_cpu_percent_scales_ticks_by_elapsed_time() {
+        assert_eq!(
+            compute_cpu_percent(100, 250, Duration::from_secs(1), 100),
+            Some(150.0)
+        );
+        assert_eq!(
+            compute_cpu_percent(250, 100, Duration::from_secs(5), 100),
+            Some(0.0)
+        );
+        assert_eq!(compute_cpu_percent(0, 10, Duration::ZERO, 100), None);
+    }
+}
@@ -1,14 +1,12 @@
 use std::{
-    sync::atomic::Ordering,
     thread,
     time::{Duration, Instant},
 };
 
-use tauri::AppHandle;
-
 use crate::{
-    append_desktop_log, append_restart_log, backend, AtomicFlagGuard, BackendBridgeState,
-    BackendState, LaunchPlan, GRACEFUL_RESTART_POLL_INTERVAL_MS,
+    append_desktop_log, append_desktop_log_at, append_restart_log, append_restart_log_at, backend,
+    backend::host::BackendHost, backend::state_machine::BackendPhase, logging::DesktopLogLevel,
+    BackendBridgeState, BackendState, LaunchPlan, GRACEFUL_RESTART_POLL_INTERVAL_MS,
     GRACEFUL_RESTART_REQUEST_TIMEOUT_MS,
 };
 
@@ -29,9 +27,10 @@ impl BackendState {
         match self.restart_auth_token.lock() {
             Ok(guard) => guard.clone(),
             Err(error) => {
-                append_restart_log(&format!(
-                    "restart auth token lock poisoned when reading: {error}"
-                ));
+                append_restart_log_at(
+                    DesktopLogLevel::Error,
+                    &format!("restart auth token lock poisoned when reading: {error}"),
+                );
                 None
             }
         }
@@ -43,9 +42,10 @@ impl BackendState {
             Ok(mut guard) => {
                 *guard = normalized;
             }
-            Err(error) => append_restart_log(&format!(
-                "restart auth token lock poisoned when writing: {error}"
-            )),
+            Err(error) => append_restart_log_at(
+                DesktopLogLevel::Error,
+                &format!("restart auth token lock poisoned when writing: {error}"),
+            ),
         }
     }
 
@@ -60,9 +60,10 @@ impl BackendState {
         match status_code {
             Some(code) if (200..300).contains(&code) => true,
             Some(code) => {
-                append_restart_log(&format!(
-                    "graceful restart request rejected with HTTP status {code}"
-                ));
+                append_restart_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!("graceful restart request rejected with HTTP status {code}"),
+                );
                 false
             }
             None => {
@@ -118,10 +119,18 @@ impl BackendState {
         if has_managed_child {
             return self.stop_backend();
         }
+        if self.reattached_backend().is_some() {
+            return self.stop_reattached_backend();
+        }
 
         if self.ping_backend(backend::runtime::backend_ping_timeout_ms(
             append_desktop_log,
         )) {
+            if self.attached_backend().is_some() {
+                return Err(
+                    "Backend is attached (unmanaged); stop it where it was started.".to_string(),
+                );
+            }
             return Err("Backend is running but not managed by desktop process.".to_string());
         }
         Ok(())
@@ -135,7 +144,7 @@ impl BackendState {
                 let message = format!(
                     "backend child lock poisoned while resolving restart strategy: {error}"
                 );
-                append_desktop_log(&message);
+                append_desktop_log_at(DesktopLogLevel::Error, &message);
                 message
             })
     }
@@ -205,16 +214,20 @@ impl BackendState {
                 backend::restart_strategy::RestartStrategy::ManagedWithGracefulFallback,
                 backend::restart_strategy::GracefulRestartOutcome::WaitFailed(error),
             ) => {
-                append_restart_log(&format!(
-                    "graceful restart did not complete, fallback to managed restart: {error}"
-                ));
+                append_restart_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!(
+                        "graceful restart did not complete, fallback to managed restart: {error}"
+                    ),
+                );
                 self.stop_backend_for_restart_flow()
             }
             (
                 backend::restart_strategy::RestartStrategy::ManagedWithGracefulFallback,
                 backend::restart_strategy::GracefulRestartOutcome::RequestRejected,
             ) => {
-                append_restart_log(
+                append_restart_log_at(
+                    DesktopLogLevel::Warn,
                     "graceful restart request was rejected, fallback to managed restart",
                 );
                 self.stop_backend_for_restart_flow()
@@ -223,7 +236,7 @@ impl BackendState {
                 backend::restart_strategy::RestartStrategy::UnmanagedWithGracefulProbe,
                 backend::restart_strategy::GracefulRestartOutcome::WaitFailed(error),
             ) => {
-                append_restart_log(&format!(
+                append_restart_log_at(DesktopLogLevel::Warn, &format!(
                     "graceful restart did not complete for unmanaged backend, bootstrap managed restart: {error}"
                 ));
                 self.stop_backend_for_restart_flow()
@@ -244,23 +257,24 @@ impl BackendState {
 
     fn launch_backend_after_restart(
         &self,
-        app: &AppHandle,
+        app: &impl BackendHost,
         plan: &LaunchPlan,
     ) -> Result<(), String> {
-        let _spawn_guard = AtomicFlagGuard::set(&self.is_spawning);
-        self.start_backend_process(app, plan)?;
-        self.wait_for_backend(plan)
+        let cancel_scope = self.begin_startup_cancel_scope();
+        self.transition_backend_phase(BackendPhase::Spawning);
+        self.spawn_and_wait_for_backend(app, plan, &cancel_scope)?;
+        self.start_liveness_watchdog(app, plan);
+        Ok(())
     }
 
     pub(crate) fn restart_backend(
         &self,
-        app: &AppHandle,
+        app: &impl BackendHost,
         auth_token: Option<&str>,
     ) -> Result<(), String> {
         append_restart_log("backend restart requested");
 
-        let _restart_guard = AtomicFlagGuard::try_set(&self.is_restarting)
-            .ok_or_else(|| "Backend action already in progress.".to_string())?;
+        let _restart_guard = self.begin_backend_action(BackendPhase::Restarting)?;
         let plan = self.resolve_launch_plan(app)?;
         let has_managed_child = self.has_managed_child()?;
         let strategy = self.restart_strategy(&plan, has_managed_child);
@@ -279,6 +293,7 @@ impl BackendState {
             Ok(())
                 if strategy != backend::restart_strategy::RestartStrategy::ManagedSkipGraceful =>
             {
+                self.transition_backend_phase(BackendPhase::Ready);
                 return Ok(());
             }
             Ok(()) => {}
@@ -288,25 +303,48 @@ impl BackendState {
         self.launch_backend_after_restart(app, &plan)
     }
 
-    pub(crate) fn bridge_state(&self, app: &AppHandle) -> BackendBridgeState {
+    pub(crate) fn relaunch_backend_after_crash(
+        &self,
+        app: &impl BackendHost,
+    ) -> Result<(), String> {
+        append_restart_log("backend relaunch after crash requested");
+
+        let _restart_guard = self.begin_backend_action(BackendPhase::Restarting)?;
+        let plan = self.resolve_launch_plan(app)?;
+        // The crashed process is already reaped, so skip the graceful restart request.
+        self.launch_backend_after_restart(app, &plan)
+    }
+
+    pub(crate) fn bridge_state(&self, app: &impl BackendHost) -> BackendBridgeState {
         let has_managed_child = self
             .child
             .lock()
             .map(|guard| guard.is_some())
             .unwrap_or_else(|error| {
-                append_desktop_log(&format!(
-                    "backend bridge: child process mutex poisoned in bridge_state: {error}"
-                ));
+                append_desktop_log_at(
+                    DesktopLogLevel::Error,
+                    &format!(
+                        "backend bridge: child process mutex poisoned in bridge_state: {error}"
+                    ),
+                );
                 false
             });
         let can_manage = has_managed_child || self.resolve_launch_plan(app).is_ok();
+        let running = self.ping_backend(backend::runtime::bridge_backend_ping_timeout_ms(
+            append_desktop_log,
+        ));
+        let lifecycle = self.lifecycle_snapshot();
         BackendBridgeState {
-            running: self.ping_backend(backend::runtime::bridge_backend_ping_timeout_ms(
-                append_desktop_log,
-            )),
-            spawning: self.is_spawning.load(Ordering::Relaxed),
-            restarting: self.is_restarting.load(Ordering::Relaxed),
+            running,
+            spawning: matches!(
+                lifecycle.phase,
+                BackendPhase::Spawning | BackendPhase::WaitingForReady
+            ),
+            restarting: lifecycle.restarting,
             can_manage,
+            attached: running && !has_managed_child && self.attached_backend().is_some(),
+            resources: self.resource_usage().filter(|_| has_managed_child),
+            lifecycle,
         }
     }
 }
@@ -341,6 +379,8 @@ mod tests {
             root_dir: None,
             webui_dir: None,
             startup_heartbeat_path: None,
+            dashboard_port: None,
+            runtime_dir: None,
             packaged_mode: true,
         };
         let state = BackendState::default();
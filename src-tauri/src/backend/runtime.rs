@@ -1,9 +1,10 @@
 use std::{env, sync::OnceLock, time::Duration};
 
-use crate::backend;
+use crate::{backend, desktop_settings, desktop_state, runtime_paths};
 
 static BACKEND_PING_TIMEOUT_MS: OnceLock<u64> = OnceLock::new();
 static BRIDGE_BACKEND_PING_TIMEOUT_MS: OnceLock<u64> = OnceLock::new();
+static BACKEND_SHUTDOWN_GRACE_MS: OnceLock<u64> = OnceLock::new();
 
 pub fn backend_wait_timeout(packaged_mode: bool) -> Duration {
     backend::config::resolve_backend_timeout_ms(
@@ -17,6 +18,7 @@ pub fn backend_wait_timeout(packaged_mode: bool) -> Duration {
 
 pub fn backend_readiness_config<F>(
     plan: &crate::LaunchPlan,
+    profile_id: &str,
     log: F,
 ) -> backend::config::BackendReadinessConfig
 where
@@ -48,6 +50,21 @@ where
         Err(_) => crate::DEFAULT_BACKEND_STARTUP_IDLE_TIMEOUT_MS,
     };
     readiness.startup_heartbeat_path = plan.startup_heartbeat_path.clone();
+    readiness.probes = backend::readiness_probe::resolve_readiness_probe_chain(
+        crate::BACKEND_READY_PROBES_ENV,
+        env::var(crate::BACKEND_READY_PROBES_ENV).ok().as_deref(),
+        desktop_settings::read_readiness_probe_settings(
+            desktop_state::resolve_profile_desktop_state_path(
+                profile_id,
+                plan.root_dir.as_deref(),
+                runtime_paths::default_packaged_root_dir().as_deref(),
+            )
+            .as_deref(),
+        )
+        .as_ref(),
+        &readiness.path,
+        |message| log(&message),
+    );
     readiness
 }
 
@@ -68,6 +85,23 @@ where
     })
 }
 
+pub fn backend_shutdown_grace_ms<F>(log: F) -> u64
+where
+    F: Fn(&str) + Copy,
+{
+    *BACKEND_SHUTDOWN_GRACE_MS.get_or_init(|| match env::var(crate::BACKEND_SHUTDOWN_GRACE_ENV) {
+        Ok(raw) => backend::config::resolve_backend_shutdown_grace_ms(
+            &raw,
+            crate::BACKEND_SHUTDOWN_GRACE_ENV,
+            crate::DEFAULT_BACKEND_SHUTDOWN_GRACE_MS,
+            crate::BACKEND_SHUTDOWN_GRACE_MIN_MS,
+            crate::BACKEND_SHUTDOWN_GRACE_MAX_MS,
+            |message| log(&message),
+        ),
+        Err(_) => crate::DEFAULT_BACKEND_SHUTDOWN_GRACE_MS,
+    })
+}
+
 pub fn bridge_backend_ping_timeout_ms<F>(log: F) -> u64
 where
     F: Fn(&str) + Copy,
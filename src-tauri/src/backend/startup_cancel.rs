@@ -0,0 +1,195 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    append_desktop_log, append_desktop_log_at,
+    backend::{
+        host::BackendHost,
+        startup_progress::{StartupProgress, StartupStage},
+    },
+    logging::DesktopLogLevel,
+    BackendState,
+};
+
+pub(crate) const BACKEND_STARTUP_CANCELLED: &str = "Backend startup cancelled.";
+const CANCEL_SLEEP_SLICE: Duration = Duration::from_millis(50);
+
+/// Returns false as soon as the token is cancelled.
+pub(crate) fn sleep_unless_cancelled(token: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if token.load(Ordering::Relaxed) {
+            return false;
+        }
+        thread::sleep(CANCEL_SLEEP_SLICE.min(deadline - Instant::now()));
+    }
+    !token.load(Ordering::Relaxed)
+}
+
+/// Keeps the startup token registered on the state while a spawn + readiness wait runs.
+pub(crate) struct StartupCancelScope<'a> {
+    state: &'a BackendState,
+    token: Arc<AtomicBool>,
+}
+
+impl StartupCancelScope<'_> {
+    pub(crate) fn token(&self) -> &AtomicBool {
+        &self.token
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.token.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err(BACKEND_STARTUP_CANCELLED.to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for StartupCancelScope<'_> {
+    fn drop(&mut self) {
+        let mut guard = match self.state.startup_cancel.lock() {
+            Ok(guard) => guard,
+            Err(error) => error.into_inner(),
+        };
+        if guard
+            .as_ref()
+            .is_some_and(|token| Arc::ptr_eq(token, &self.token))
+        {
+            *guard = None;
+        }
+    }
+}
+
+impl BackendState {
+    pub(crate) fn begin_startup_cancel_scope(&self) -> StartupCancelScope<'_> {
+        let token = Arc::new(AtomicBool::new(false));
+        match self.startup_cancel.lock() {
+            Ok(mut guard) => *guard = Some(token.clone()),
+            Err(error) => *error.into_inner() = Some(token.clone()),
+        }
+        StartupCancelScope { state: self, token }
+    }
+
+    /// Returns whether a startup was in progress and has been told to abort.
+    pub(crate) fn cancel_backend_startup(&self, reason: &str) -> bool {
+        let token = match self.startup_cancel.lock() {
+            Ok(guard) => guard.clone(),
+            Err(error) => error.into_inner().clone(),
+        };
+        let Some(token) = token else {
+            return false;
+        };
+        if !token.swap(true, Ordering::Relaxed) {
+            append_desktop_log(&format!(
+                "cancelling backend startup (profile {}): {reason}",
+                self.profile_id
+            ));
+        }
+        true
+    }
+
+    /// Runs the cancellable part of a startup and stops a half-started child on cancellation.
+    pub(crate) fn spawn_and_wait_for_backend(
+        &self,
+        app: &impl BackendHost,
+        plan: &crate::LaunchPlan,
+        scope: &StartupCancelScope<'_>,
+    ) -> Result<(), String> {
+        let mut result = self.spawn_and_wait_for_backend_once(app, plan, scope);
+        // A failed spawn only counts when the child is gone; a live one may still hold the port.
+        let spawn_settled = result.is_ok() || self.managed_child_pid().is_none();
+        if !scope.is_cancelled() && spawn_settled && self.relocated_port_taken_by_other_process() {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "dashboard port {} was taken before the backend bound it; relocating once more",
+                    self.relocated_dashboard_port().unwrap_or_default()
+                ),
+            );
+            if let Err(error) = self.stop_backend() {
+                append_desktop_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!("failed to stop backend before port relocation retry: {error}"),
+                );
+            }
+            result = self.spawn_and_wait_for_backend_once(app, plan, scope);
+        }
+        if result.is_ok() || !scope.is_cancelled() {
+            return result;
+        }
+
+        append_desktop_log("backend startup cancelled, stopping half-started backend");
+        if let Err(error) = self.stop_backend() {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!("failed to stop backend after cancelled startup: {error}"),
+            );
+        }
+        Err(BACKEND_STARTUP_CANCELLED.to_string())
+    }
+
+    fn spawn_and_wait_for_backend_once(
+        &self,
+        app: &impl BackendHost,
+        plan: &crate::LaunchPlan,
+        scope: &StartupCancelScope<'_>,
+    ) -> Result<(), String> {
+        scope.check()?;
+        self.report_startup_progress(StartupProgress::new(StartupStage::LaunchPlanResolved));
+        self.start_backend_process(app, plan)
+            .and_then(|()| scope.check())
+            .inspect(|()| {
+                self.report_startup_progress(StartupProgress {
+                    pid: self.managed_child_pid(),
+                    ..StartupProgress::new(StartupStage::Spawned)
+                })
+            })
+            .and_then(|()| self.wait_for_backend(plan, scope.token()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use super::{sleep_unless_cancelled, BACKEND_STARTUP_CANCELLED};
+    use crate::BackendState;
+
+    #[test]
+    fn cancel_only_reaches_the_registered_startup() {
+        let state = BackendState::default();
+        assert!(!state.cancel_backend_startup("test"));
+
+        let scope = state.begin_startup_cancel_scope();
+        assert_eq!(scope.check(), Ok(()));
+        assert!(state.cancel_backend_startup("test"));
+        assert_eq!(scope.check(), Err(BACKEND_STARTUP_CANCELLED.to_string()));
+
+        drop(scope);
+        assert!(!state.cancel_backend_startup("test"));
+    }
+
+    #[test]
+    fn sleep_returns_early_when_cancelled() {
+        let token = AtomicBool::new(false);
+        assert!(sleep_unless_cancelled(&token, Duration::from_millis(1)));
+
+        token.store(true, Ordering::Relaxed);
+        let started = Instant::now();
+        assert!(!sleep_unless_cancelled(&token, Duration::from_secs(5)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    desktop_events::{self, DesktopEvent},
+    BackendState,
+};
+
+const STARTUP_PROGRESS_MIN_INTERVAL: Duration = Duration::from_secs(1);
+// The launcher rewrites the heartbeat every 2s; three missed beats reads as stale.
+const STARTUP_HEARTBEAT_STALE_AFTER: Duration = Duration::from_secs(6);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StartupStage {
+    LaunchPlanResolved,
+    Spawned,
+    WaitingForHttp,
+    Ready,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StartupHeartbeatStatus {
+    Disabled,
+    Missing,
+    Fresh,
+    Stale,
+}
+
+/// What the backend itself says it is doing, taken from the v2 startup heartbeat.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartupPhaseReport {
+    pub(crate) phase: Option<String>,
+    pub(crate) phase_progress: Option<f64>,
+    pub(crate) message: Option<String>,
+    pub(crate) warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartupProgress {
+    pub(crate) stage: StartupStage,
+    pub(crate) pid: Option<u32>,
+    pub(crate) elapsed_ms: u64,
+    pub(crate) timeout_ms: Option<u64>,
+    pub(crate) tcp_reachable: bool,
+    pub(crate) http_status: Option<u16>,
+    pub(crate) heartbeat: StartupHeartbeatStatus,
+    pub(crate) heartbeat_age_ms: Option<u64>,
+    #[serde(flatten)]
+    pub(crate) report: StartupPhaseReport,
+}
+
+impl StartupProgress {
+    pub(crate) fn new(stage: StartupStage) -> Self {
+        Self {
+            stage,
+            pid: None,
+            elapsed_ms: 0,
+            timeout_ms: None,
+            tcp_reachable: false,
+            http_status: None,
+            heartbeat: StartupHeartbeatStatus::Disabled,
+            heartbeat_age_ms: None,
+            report: StartupPhaseReport::default(),
+        }
+    }
+}
+
+pub(crate) fn classify_startup_heartbeat(
+    enabled: bool,
+    last_progress_at: Option<Instant>,
+    now: Instant,
+) -> StartupHeartbeatStatus {
+    if !enabled {
+        return StartupHeartbeatStatus::Disabled;
+    }
+    match last_progress_at {
+        None => StartupHeartbeatStatus::Missing,
+        Some(progress_at)
+            if now.saturating_duration_since(progress_at) > STARTUP_HEARTBEAT_STALE_AFTER =>
+        {
+            StartupHeartbeatStatus::Stale
+        }
+        Some(_) => StartupHeartbeatStatus::Fresh,
+    }
+}
+
+/// Publishes readiness polls on change, otherwise at most once per second so the
+/// elapsed counter keeps moving without flooding the webview.
+#[derive(Debug, Default)]
+pub(crate) struct StartupProgressThrottle {
+    last_signature: Option<(
+        StartupStage,
+        bool,
+        Option<u16>,
+        StartupHeartbeatStatus,
+        StartupPhaseReport,
+    )>,
+    last_published_at: Option<Instant>,
+}
+
+impl StartupProgressThrottle {
+    pub(crate) fn should_publish(&mut self, progress: &StartupProgress, now: Instant) -> bool {
+        let signature = (
+            progress.stage,
+            progress.tcp_reachable,
+            progress.http_status,
+            progress.heartbeat,
+            progress.report.clone(),
+        );
+        let changed = self.last_signature.as_ref() != Some(&signature);
+        let due = self.last_published_at.is_none_or(|published_at| {
+            now.saturating_duration_since(published_at) >= STARTUP_PROGRESS_MIN_INTERVAL
+        });
+        if !changed && !due {
+            return false;
+        }
+        self.last_signature = Some(signature);
+        self.last_published_at = Some(now);
+        true
+    }
+}
+
+impl BackendState {
+    pub(crate) fn report_startup_progress(&self, progress: StartupProgress) {
+        match self.startup_progress.lock() {
+            Ok(mut guard) => *guard = Some(progress.clone()),
+            Err(error) => *error.into_inner() = Some(progress.clone()),
+        }
+        desktop_events::publish(
+            Some(&self.profile_id),
+            DesktopEvent::StartupProgress(progress),
+        );
+    }
+
+    /// Lets a freshly loaded startup page catch up on progress it missed.
+    pub(crate) fn last_startup_progress(&self) -> Option<StartupProgress> {
+        match self.startup_progress.lock() {
+            Ok(guard) => guard.clone(),
+            Err(error) => error.into_inner().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{
+        classify_startup_heartbeat, StartupHeartbeatStatus, StartupPhaseReport, StartupProgress,
+        StartupProgressThrottle, StartupStage,
+    };
+
+    #[test]
+    fn heartbeat_turns_stale_after_missed_beats() {
+        let now = Instant::now();
+        assert_eq!(
+            classify_startup_heartbeat(false, Some(now), now),
+            StartupHeartbeatStatus::Disabled
+        );
+        assert_eq!(
+            classify_startup_heartbeat(true, None, now),
+            StartupHeartbeatStatus::Missing
+        );
+        assert_eq!(
+            classify_startup_heartbeat(true, Some(now), now + Duration::from_secs(2)),
+            StartupHeartbeatStatus::Fresh
+        );
+        assert_eq!(
+            classify_startup_heartbeat(true, Some(now), now + Duration::from_secs(7)),
+            StartupHeartbeatStatus::Stale
+        );
+    }
+
+    #[test]
+    fn throttle_publishes_changes_immediately_and_repeats_once_per_second() {
+        let now = Instant::now();
+        let mut throttle = StartupProgressThrottle::default();
+        let mut progress = StartupProgress::new(StartupStage::WaitingForHttp);
+
+        assert!(throttle.should_publish(&progress, now));
+        assert!(!throttle.should_publish(&progress, now + Duration::from_millis(300)));
+
+        progress.tcp_reachable = true;
+        assert!(throttle.should_publish(&progress, now + Duration::from_millis(400)));
+        assert!(!throttle.should_publish(&progress, now + Duration::from_millis(900)));
+        assert!(throttle.should_publish(&progress, now + Duration::from_millis(1_400)));
+
+        progress.report.phase_progress = Some(0.25);
+        assert!(throttle.should_publish(&progress, now + Duration::from_millis(1_500)));
+    }
+
+    #[test]
+    fn progress_event_flattens_into_desktop_event() {
+        let mut progress = StartupProgress::new(StartupStage::WaitingForHttp);
+        progress.pid = Some(42);
+        progress.elapsed_ms = 1_500;
+        progress.timeout_ms = Some(900_000);
+        progress.http_status = Some(503);
+        progress.heartbeat = StartupHeartbeatStatus::Fresh;
+        progress.heartbeat_age_ms = Some(800);
+        progress.report = StartupPhaseReport {
+            phase: Some("installing_dependencies".to_string()),
+            phase_progress: Some(0.25),
+            message: Some("Installing dependencies 3/12".to_string()),
+            warnings: vec!["pip mirror unreachable".to_string()],
+        };
+
+        assert_eq!(
+            serde_json::to_value(crate::desktop_events::DesktopEvent::StartupProgress(
+                progress
+            ))
+            .expect("serialize progress"),
+            serde_json::json!({
+                "kind": "startupProgress",
+                "stage": "waitingForHttp",
+                "pid": 42,
+                "elapsedMs": 1_500,
+                "timeoutMs": 900_000,
+                "tcpReachable": false,
+                "httpStatus": 503,
+                "heartbeat": "fresh",
+                "heartbeatAgeMs": 800,
+                "phase": "installing_dependencies",
+                "phaseProgress": 0.25,
+                "message": "Installing dependencies 3/12",
+                "warnings": ["pip mirror unreachable"],
+            })
+        );
+    }
+}
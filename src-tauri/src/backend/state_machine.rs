@@ -0,0 +1,501 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    append_desktop_log_at,
+    desktop_events::{self, DesktopEvent},
+    logging::DesktopLogLevel,
+    BackendState,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum BackendPhase {
+    #[default]
+    Stopped,
+    Spawning,
+    WaitingForReady,
+    Ready,
+    Stopping,
+    Restarting,
+    Crashed {
+        #[serde(rename = "exitCode")]
+        exit_code: Option<i32>,
+    },
+    Exiting,
+}
+
+impl BackendPhase {
+    /// Transient phases belong to the flow that entered them; nothing else may
+    /// start a backend action until that flow settles the machine.
+    pub(crate) fn is_busy(self) -> bool {
+        matches!(
+            self,
+            Self::Spawning | Self::WaitingForReady | Self::Stopping | Self::Restarting
+        )
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Stopped => "stopped",
+            Self::Spawning => "spawning",
+            Self::WaitingForReady => "waitingForReady",
+            Self::Ready => "ready",
+            Self::Stopping => "stopping",
+            Self::Restarting => "restarting",
+            Self::Crashed { .. } => "crashed",
+            Self::Exiting => "exiting",
+        }
+    }
+}
+
+pub(crate) fn is_valid_transition(from: BackendPhase, to: BackendPhase) -> bool {
+    use BackendPhase::*;
+
+    match (from, to) {
+        (Exiting, _) => false,
+        (_, Exiting) => true,
+        (Stopped | Ready | Crashed { .. }, Spawning | Restarting) => true,
+        (Restarting, Spawning) => true,
+        (Spawning, WaitingForReady) => true,
+        // Attaching to an unmanaged backend, or finding the managed one already reachable.
+        (Stopped | Crashed { .. } | WaitingForReady | Restarting, Ready) => true,
+        (Ready | WaitingForReady | Crashed { .. }, Stopping) => true,
+        (Stopping, Stopped) => true,
+        // A stop that timed out leaves the backend where it was.
+        (Stopping, Ready | Crashed { .. }) => true,
+        (Spawning | WaitingForReady | Restarting | Crashed { .. }, Stopped) => true,
+        (Ready | Spawning | WaitingForReady | Restarting | Crashed { .. }, Crashed { .. }) => true,
+        _ => false,
+    }
+}
+
+fn unix_time_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackendLifecycleSnapshot {
+    pub(crate) phase: BackendPhase,
+    pub(crate) since_ms: u64,
+    pub(crate) pid: Option<u32>,
+    pub(crate) started_at_ms: Option<u64>,
+    pub(crate) uptime_ms: Option<u64>,
+    pub(crate) last_exit_status: Option<String>,
+    pub(crate) restarting: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct BackendLifecycle {
+    phase: BackendPhase,
+    since: SystemTime,
+    pid: Option<u32>,
+    started: Option<(SystemTime, Instant)>,
+    last_exit_status: Option<String>,
+    // Spawning/WaitingForReady entered from Restarting still count as a restart.
+    restart_cycle: bool,
+}
+
+impl Default for BackendLifecycle {
+    fn default() -> Self {
+        Self {
+            phase: BackendPhase::Stopped,
+            since: SystemTime::now(),
+            pid: None,
+            started: None,
+            last_exit_status: None,
+            restart_cycle: false,
+        }
+    }
+}
+
+impl BackendLifecycle {
+    pub(crate) fn phase(&self) -> BackendPhase {
+        self.phase
+    }
+
+    pub(crate) fn transition(&mut self, to: BackendPhase) -> Result<BackendPhase, String> {
+        let from = self.phase;
+        if from == to {
+            return Ok(from);
+        }
+        if !is_valid_transition(from, to) {
+            return Err(format!(
+                "invalid backend lifecycle transition: {} -> {}",
+                from.name(),
+                to.name()
+            ));
+        }
+        match to {
+            BackendPhase::Restarting => self.restart_cycle = true,
+            BackendPhase::Spawning | BackendPhase::WaitingForReady | BackendPhase::Stopping => {}
+            _ => self.restart_cycle = false,
+        }
+        self.phase = to;
+        self.since = SystemTime::now();
+        Ok(from)
+    }
+
+    /// Enters a transient phase unless another backend action already owns the machine.
+    pub(crate) fn try_begin(&mut self, to: BackendPhase) -> Result<BackendPhase, String> {
+        if self.phase.is_busy() {
+            return Err("Backend action already in progress.".to_string());
+        }
+        self.transition(to)
+    }
+
+    pub(crate) fn record_spawned(&mut self, pid: u32) {
+        self.pid = Some(pid);
+        self.started = Some((SystemTime::now(), Instant::now()));
+    }
+
+    pub(crate) fn record_exit(&mut self, exit_status: Option<String>) {
+        self.pid = None;
+        self.started = None;
+        if exit_status.is_some() {
+            self.last_exit_status = exit_status;
+        }
+    }
+
+    /// Push events implied by a completed `from -> to` transition.
+    fn transition_events(
+        &self,
+        from: BackendPhase,
+        to: BackendPhase,
+        was_restarting: bool,
+    ) -> Vec<DesktopEvent> {
+        let mut events = Vec::new();
+        match to {
+            BackendPhase::WaitingForReady => {
+                if let Some(pid) = self.pid {
+                    events.push(DesktopEvent::BackendSpawned { pid });
+                }
+            }
+            BackendPhase::Restarting => events.push(DesktopEvent::RestartStarted),
+            BackendPhase::Ready => events.push(DesktopEvent::BackendReady { pid: self.pid }),
+            BackendPhase::Crashed { exit_code } => events.push(DesktopEvent::BackendCrashed {
+                exit_code,
+                exit_status: self.last_exit_status.clone(),
+            }),
+            BackendPhase::Stopped if from == BackendPhase::Stopping => {
+                events.push(DesktopEvent::BackendStopped)
+            }
+            _ => {}
+        }
+        if was_restarting && !self.restart_cycle {
+            events.push(DesktopEvent::RestartFinished {
+                ok: to == BackendPhase::Ready,
+            });
+        }
+        events
+    }
+
+    pub(crate) fn snapshot(&self) -> BackendLifecycleSnapshot {
+        BackendLifecycleSnapshot {
+            phase: self.phase,
+            since_ms: unix_time_ms(self.since),
+            pid: self.pid,
+            started_at_ms: self.started.map(|(started_at, _)| unix_time_ms(started_at)),
+            uptime_ms: self
+                .started
+                .map(|(_, started)| started.elapsed().as_millis() as u64),
+            last_exit_status: self.last_exit_status.clone(),
+            restarting: self.restart_cycle,
+        }
+    }
+}
+
+/// Settles the machine when the flow that owns a transient phase returns early.
+pub(crate) struct BackendActionGuard<'a> {
+    state: &'a BackendState,
+}
+
+impl Drop for BackendActionGuard<'_> {
+    fn drop(&mut self) {
+        self.state.settle_interrupted_backend_action();
+    }
+}
+
+impl BackendState {
+    fn with_lifecycle<T>(&self, action: impl FnOnce(&mut BackendLifecycle) -> T) -> T {
+        match self.lifecycle.lock() {
+            Ok(mut guard) => action(&mut guard),
+            Err(error) => action(&mut error.into_inner()),
+        }
+    }
+
+    pub(crate) fn backend_phase(&self) -> BackendPhase {
+        self.with_lifecycle(|lifecycle| lifecycle.phase())
+    }
+
+    pub(crate) fn lifecycle_snapshot(&self) -> BackendLifecycleSnapshot {
+        self.with_lifecycle(|lifecycle| lifecycle.snapshot())
+    }
+
+    fn apply_backend_transition(&self, to: BackendPhase, begin: bool) -> Result<(), String> {
+        let (from, events) = self.with_lifecycle(|lifecycle| {
+            let was_restarting = lifecycle.restart_cycle;
+            let from = if begin {
+                lifecycle.try_begin(to)?
+            } else {
+                lifecycle.transition(to)?
+            };
+            let events = if from == to {
+                Vec::new()
+            } else {
+                lifecycle.transition_events(from, to, was_restarting)
+            };
+            Ok::<_, String>((from, events))
+        })?;
+        if from == to {
+            return Ok(());
+        }
+        append_desktop_log_at(
+            DesktopLogLevel::Debug,
+            &format!(
+                "backend lifecycle (profile {}): {} -> {}",
+                self.profile_id,
+                from.name(),
+                to.name()
+            ),
+        );
+        for event in events {
+            desktop_events::publish(Some(&self.profile_id), event);
+        }
+        Ok(())
+    }
+
+    /// Invalid transitions are logged and ignored so a late observer (for
+    /// example a stop during exit) cannot corrupt the state.
+    pub(crate) fn transition_backend_phase(&self, to: BackendPhase) -> bool {
+        match self.apply_backend_transition(to, false) {
+            Ok(()) => true,
+            Err(error) => {
+                append_desktop_log_at(
+                    DesktopLogLevel::Debug,
+                    &format!("{error} (profile {})", self.profile_id),
+                );
+                false
+            }
+        }
+    }
+
+    pub(crate) fn begin_backend_action(
+        &self,
+        to: BackendPhase,
+    ) -> Result<BackendActionGuard<'_>, String> {
+        self.apply_backend_transition(to, true)?;
+        Ok(BackendActionGuard { state: self })
+    }
+
+    pub(crate) fn record_backend_spawned(&self, pid: u32) {
+        self.with_lifecycle(|lifecycle| lifecycle.record_spawned(pid));
+        self.transition_backend_phase(BackendPhase::WaitingForReady);
+    }
+
+    pub(crate) fn record_backend_crashed(&self, status: std::process::ExitStatus) {
+        self.with_lifecycle(|lifecycle| lifecycle.record_exit(Some(status.to_string())));
+        self.transition_backend_phase(BackendPhase::Crashed {
+            exit_code: status.code(),
+        });
+    }
+
+    pub(crate) fn record_backend_stopped(&self) {
+        self.with_lifecycle(|lifecycle| lifecycle.record_exit(None));
+    }
+
+    pub(crate) fn mark_backend_exiting(&self) {
+        self.transition_backend_phase(BackendPhase::Exiting);
+    }
+
+    /// Returns the phase to restore when the stop fails, or `None` when the stop
+    /// belongs to a flow (restart, exit) that already owns the machine.
+    pub(crate) fn begin_backend_stop(&self) -> Option<BackendPhase> {
+        let previous = self.backend_phase();
+        let tracked = matches!(
+            previous,
+            BackendPhase::Ready | BackendPhase::WaitingForReady | BackendPhase::Crashed { .. }
+        ) && self.transition_backend_phase(BackendPhase::Stopping);
+        tracked.then_some(previous)
+    }
+
+    pub(crate) fn finish_backend_stop(&self, previous: Option<BackendPhase>, stopped: bool) {
+        if stopped {
+            self.record_backend_stopped();
+        }
+        let Some(previous) = previous else {
+            return;
+        };
+        if stopped {
+            self.transition_backend_phase(BackendPhase::Stopped);
+        } else if previous == BackendPhase::Ready {
+            self.transition_backend_phase(BackendPhase::Ready);
+        } else {
+            self.transition_backend_phase(BackendPhase::Crashed { exit_code: None });
+        }
+    }
+
+    fn settle_interrupted_backend_action(&self) {
+        if !self.backend_phase().is_busy() {
+            return;
+        }
+        // A child that is still alive failed readiness; report it as crashed
+        // rather than leaving the machine stuck in a transient phase.
+        let settled = if self.has_live_managed_child().unwrap_or(false) {
+            BackendPhase::Crashed { exit_code: None }
+        } else if self.attached_backend().is_some() {
+            BackendPhase::Ready
+        } else {
+            self.record_backend_stopped();
+            BackendPhase::Stopped
+        };
+        self.transition_backend_phase(settled);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_transition, BackendLifecycle, BackendPhase};
+    use crate::desktop_events::DesktopEvent;
+
+    #[test]
+    fn lifecycle_walks_through_spawn_restart_and_crash() {
+        let mut lifecycle = BackendLifecycle::default();
+        assert!(lifecycle.try_begin(BackendPhase::Spawning).is_ok());
+        lifecycle.record_spawned(42);
+        lifecycle
+            .transition(BackendPhase::WaitingForReady)
+            .expect("waiting");
+        assert_eq!(
+            lifecycle.try_begin(BackendPhase::Restarting),
+            Err("Backend action already in progress.".to_string())
+        );
+        lifecycle.transition(BackendPhase::Ready).expect("ready");
+
+        lifecycle
+            .try_begin(BackendPhase::Restarting)
+            .expect("restart");
+        lifecycle
+            .transition(BackendPhase::Spawning)
+            .expect("respawn");
+        assert!(lifecycle.snapshot().restarting);
+        lifecycle
+            .transition(BackendPhase::WaitingForReady)
+            .expect("waiting");
+        lifecycle.transition(BackendPhase::Ready).expect("ready");
+        assert!(!lifecycle.snapshot().restarting);
+
+        lifecycle.record_exit(Some("exit status: 3".to_string()));
+        lifecycle
+            .transition(BackendPhase::Crashed { exit_code: Some(3) })
+            .expect("crash");
+        let snapshot = lifecycle.snapshot();
+        assert_eq!(snapshot.phase, BackendPhase::Crashed { exit_code: Some(3) });
+        assert_eq!(snapshot.pid, None);
+        assert_eq!(snapshot.uptime_ms, None);
+        assert_eq!(snapshot.last_exit_status.as_deref(), Some("exit status: 3"));
+    }
+
+    #[test]
+    fn lifecycle_rejects_invalid_transitions() {
+        assert!(!is_valid_transition(
+            BackendPhase::Stopped,
+            BackendPhase::WaitingForReady
+        ));
+        assert!(!is_valid_transition(
+            BackendPhase::Stopped,
+            BackendPhase::Stopping
+        ));
+        assert!(!is_valid_transition(
+            BackendPhase::Exiting,
+            BackendPhase::Stopped
+        ));
+        assert!(is_valid_transition(
+            BackendPhase::Ready,
+            BackendPhase::Exiting
+        ));
+
+        let mut lifecycle = BackendLifecycle::default();
+        assert_eq!(
+            lifecycle.transition(BackendPhase::Ready),
+            Ok(BackendPhase::Stopped)
+        );
+        assert_eq!(
+            lifecycle.transition(BackendPhase::WaitingForReady),
+            Err("invalid backend lifecycle transition: ready -> waitingForReady".to_string())
+        );
+        assert_eq!(lifecycle.phase(), BackendPhase::Ready);
+    }
+
+    #[test]
+    fn snapshot_reports_pid_uptime_and_tagged_phase() {
+        let mut lifecycle = BackendLifecycle::default();
+        lifecycle
+            .try_begin(BackendPhase::Spawning)
+            .expect("spawning");
+        lifecycle.record_spawned(4242);
+        let snapshot = lifecycle.snapshot();
+        assert_eq!(snapshot.pid, Some(4242));
+        assert!(snapshot.started_at_ms.is_some());
+        assert!(snapshot.uptime_ms.is_some());
+
+        let payload = serde_json::to_value(BackendPhase::Crashed { exit_code: Some(1) })
+            .expect("serialize phase");
+        assert_eq!(
+            payload,
+            serde_json::json!({"kind": "crashed", "exitCode": 1})
+        );
+        assert_eq!(
+            serde_json::to_value(BackendPhase::WaitingForReady).expect("serialize phase"),
+            serde_json::json!({"kind": "waitingForReady"})
+        );
+    }
+
+    #[test]
+    fn transition_events_report_spawn_and_restart_outcome() {
+        let mut lifecycle = BackendLifecycle::default();
+        lifecycle.transition(BackendPhase::Ready).expect("ready");
+        lifecycle
+            .try_begin(BackendPhase::Restarting)
+            .expect("restart");
+        assert_eq!(
+            lifecycle.transition_events(BackendPhase::Ready, BackendPhase::Restarting, false),
+            vec![DesktopEvent::RestartStarted]
+        );
+
+        lifecycle.transition(BackendPhase::Spawning).expect("spawn");
+        lifecycle.record_spawned(7);
+        lifecycle
+            .transition(BackendPhase::WaitingForReady)
+            .expect("waiting");
+        assert_eq!(
+            lifecycle.transition_events(
+                BackendPhase::Spawning,
+                BackendPhase::WaitingForReady,
+                true
+            ),
+            vec![DesktopEvent::BackendSpawned { pid: 7 }]
+        );
+
+        lifecycle.transition(BackendPhase::Ready).expect("ready");
+        assert_eq!(
+            lifecycle.transition_events(BackendPhase::WaitingForReady, BackendPhase::Ready, true),
+            vec![
+                DesktopEvent::BackendReady { pid: Some(7) },
+                DesktopEvent::RestartFinished { ok: true },
+            ]
+        );
+
+        lifecycle.try_begin(BackendPhase::Stopping).expect("stop");
+        lifecycle.record_exit(None);
+        lifecycle
+            .transition(BackendPhase::Stopped)
+            .expect("stopped");
+        assert_eq!(
+            lifecycle.transition_events(BackendPhase::Stopping, BackendPhase::Stopped, false),
+            vec![DesktopEvent::BackendStopped]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    collections::VecDeque,
+    process::ExitStatus,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    backend::host::BackendHost, restart_backend_flow, BackendState,
+    BACKEND_CRASH_LOOP_MAX_RESTARTS, BACKEND_CRASH_LOOP_WINDOW,
+    BACKEND_CRASH_RESTART_INITIAL_BACKOFF_MS, BACKEND_CRASH_RESTART_MAX_BACKOFF_MS,
+    BACKEND_SUPERVISOR_POLL_INTERVAL,
+};
+
+const BACKOFF_SLEEP_SLICE: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CrashRestartDecision {
+    Restart { attempt: usize, delay: Duration },
+    GiveUp { crashes: usize },
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CrashRestartTracker {
+    recent_crashes: VecDeque<(Instant, String)>,
+}
+
+impl CrashRestartTracker {
+    pub(crate) fn record_crash(
+        &mut self,
+        now: Instant,
+        exit_status: String,
+    ) -> CrashRestartDecision {
+        while let Some((crashed_at, _)) = self.recent_crashes.front() {
+            if now.saturating_duration_since(*crashed_at) < BACKEND_CRASH_LOOP_WINDOW {
+                break;
+            }
+            self.recent_crashes.pop_front();
+        }
+        self.recent_crashes.push_back((now, exit_status));
+
+        let crashes = self.recent_crashes.len();
+        if crashes > BACKEND_CRASH_LOOP_MAX_RESTARTS {
+            return CrashRestartDecision::GiveUp { crashes };
+        }
+        CrashRestartDecision::Restart {
+            attempt: crashes,
+            delay: compute_crash_restart_backoff(crashes),
+        }
+    }
+
+    pub(crate) fn describe_recent_exit_statuses(&self) -> String {
+        self.recent_crashes
+            .iter()
+            .map(|(_, status)| status.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+pub(crate) fn compute_crash_restart_backoff(attempt: usize) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16) as u32;
+    let delay_ms = BACKEND_CRASH_RESTART_INITIAL_BACKOFF_MS
+        .saturating_mul(1_u64 << exponent)
+        .min(BACKEND_CRASH_RESTART_MAX_BACKOFF_MS);
+    Duration::from_millis(delay_ms)
+}
+
+enum SupervisedChildStatus {
+    Alive,
+    Exited(ExitStatus),
+    Detached,
+}
+
+fn should_stop_supervising(state: &BackendState, stop_flag: &AtomicBool) -> bool {
+    stop_flag.load(Ordering::Relaxed) || state.is_quitting()
+}
+
+impl BackendState {
+    pub(crate) fn stop_backend_supervisor(&self) {
+        match self.supervisor_stop.lock() {
+            Ok(mut guard) => {
+                if let Some(flag) = guard.take() {
+                    flag.store(true, Ordering::Relaxed);
+                }
+            }
+            Err(error) => {
+                self.append_desktop_log(&format!(
+                    "backend supervisor stop flag lock poisoned: {error}"
+                ));
+            }
+        }
+    }
+
+    fn poll_supervised_child(&self, child_pid: u32) -> SupervisedChildStatus {
+        let mut guard = match self.child.lock() {
+            Ok(guard) => guard,
+            Err(error) => {
+                self.append_desktop_log(&format!(
+                    "backend child lock poisoned while supervising pid={child_pid}: {error}"
+                ));
+                return SupervisedChildStatus::Detached;
+            }
+        };
+
+        let Some(child) = guard.as_mut() else {
+            return SupervisedChildStatus::Detached;
+        };
+        if child.id() != child_pid {
+            return SupervisedChildStatus::Detached;
+        }
+
+        match child.try_wait() {
+            Ok(None) => SupervisedChildStatus::Alive,
+            Ok(Some(status)) => {
+                *guard = None;
+                self.set_backend_pid(None);
+                self.record_backend_crashed(status);
+                SupervisedChildStatus::Exited(status)
+            }
+            Err(error) => {
+                self.append_desktop_log(&format!(
+                    "failed to poll backend process status for supervisor pid={child_pid}: {error}"
+                ));
+                SupervisedChildStatus::Detached
+            }
+        }
+    }
+
+    fn record_backend_crash(&self, exit_status: String) -> CrashRestartDecision {
+        match self.crash_restarts.lock() {
+            Ok(mut guard) => {
+                let decision = guard.record_crash(Instant::now(), exit_status);
+                if let CrashRestartDecision::GiveUp { crashes } = decision {
+                    self.append_restart_log(&format!(
+                        "backend crashed {crashes} times within {}s, giving up automatic restart: {}",
+                        BACKEND_CRASH_LOOP_WINDOW.as_secs(),
+                        guard.describe_recent_exit_statuses()
+                    ));
+                }
+                decision
+            }
+            Err(error) => {
+                self.append_restart_log(&format!(
+                    "backend crash tracker lock poisoned, skip automatic restart: {error}"
+                ));
+                CrashRestartDecision::GiveUp { crashes: 0 }
+            }
+        }
+    }
+
+    fn register_supervisor_stop_flag(&self) -> Option<Arc<AtomicBool>> {
+        self.stop_backend_supervisor();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        match self.supervisor_stop.lock() {
+            Ok(mut guard) => {
+                *guard = Some(stop_flag.clone());
+                Some(stop_flag)
+            }
+            Err(error) => {
+                self.append_desktop_log(&format!(
+                    "backend supervisor stop flag lock poisoned on start: {error}"
+                ));
+                None
+            }
+        }
+    }
+
+    pub(crate) fn start_backend_supervisor(&self, app: &impl BackendHost, child_pid: u32) {
+        let Some(stop_flag) = self.register_supervisor_stop_flag() else {
+            return;
+        };
+
+        let app_handle = app.clone();
+        let profile_id = self.profile_id.clone();
+        thread::spawn(move || {
+            let Some(state) = app_handle.profile_state(&profile_id) else {
+                return;
+            };
+            let exit_status = loop {
+                thread::sleep(BACKEND_SUPERVISOR_POLL_INTERVAL);
+                if should_stop_supervising(&state, &stop_flag) {
+                    return;
+                }
+                // Startup and restart flows own the child while they run; let them
+                // report failures instead of racing them for the exit status.
+                if restart_backend_flow::is_backend_action_in_progress(&state) {
+                    continue;
+                }
+                match state.poll_supervised_child(child_pid) {
+                    SupervisedChildStatus::Alive => {}
+                    SupervisedChildStatus::Exited(status) => break status,
+                    SupervisedChildStatus::Detached => return,
+                }
+            };
+
+            state.append_restart_log(&format!(
+                "backend process exited unexpectedly: pid={child_pid}, status={exit_status}"
+            ));
+            if let Some(stderr_tail) = state.describe_recent_backend_stderr() {
+                state.append_restart_log(&format!("recent backend stderr:\n{stderr_tail}"));
+            }
+            state.restart_crashed_backend(&app_handle, stop_flag, exit_status.to_string());
+        });
+    }
+
+    fn restart_crashed_backend(
+        &self,
+        app: &impl BackendHost,
+        mut stop_flag: Arc<AtomicBool>,
+        mut exit_status: String,
+    ) {
+        loop {
+            let (attempt, delay) = match self.record_backend_crash(exit_status) {
+                CrashRestartDecision::Restart { attempt, delay } => (attempt, delay),
+                CrashRestartDecision::GiveUp { .. } => {
+                    app.backend_supervision_gave_up(&self.profile_id);
+                    return;
+                }
+            };
+            self.append_restart_log(&format!(
+                "scheduling automatic backend restart: attempt={attempt}, delay_ms={}",
+                delay.as_millis()
+            ));
+
+            let deadline = Instant::now() + delay;
+            while Instant::now() < deadline {
+                if should_stop_supervising(self, &stop_flag) {
+                    self.append_restart_log("automatic backend restart cancelled");
+                    return;
+                }
+                thread::sleep(BACKOFF_SLEEP_SLICE.min(deadline - Instant::now()));
+            }
+            if should_stop_supervising(self, &stop_flag) {
+                self.append_restart_log("automatic backend restart cancelled");
+                return;
+            }
+
+            match self.relaunch_backend_after_crash(app) {
+                Ok(()) => {
+                    self.append_restart_log("automatic backend restart completed");
+                    return;
+                }
+                Err(error) => {
+                    self.append_restart_log(&format!("automatic backend restart failed: {error}"));
+                    // A spawned child is watched by its own supervisor from here on, and a
+                    // concurrent user action owns the backend until it finishes.
+                    if restart_backend_flow::is_backend_action_in_progress(self) {
+                        return;
+                    }
+                    match self.child.lock() {
+                        Ok(guard) if guard.is_none() => {}
+                        _ => return,
+                    }
+                    // Launching re-arms the supervisor, so keep our own stop flag for
+                    // the next backoff window.
+                    let Some(next_stop_flag) = self.register_supervisor_stop_flag() else {
+                        return;
+                    };
+                    stop_flag = next_stop_flag;
+                    exit_status = format!("restart failed: {error}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{compute_crash_restart_backoff, CrashRestartDecision, CrashRestartTracker};
+    use crate::{
+        BACKEND_CRASH_LOOP_MAX_RESTARTS, BACKEND_CRASH_LOOP_WINDOW,
+        BACKEND_CRASH_RESTART_INITIAL_BACKOFF_MS, BACKEND_CRASH_RESTART_MAX_BACKOFF_MS,
+    };
+
+    #[test]
+    fn compute_crash_restart_backoff_doubles_until_cap() {
+        assert_eq!(
+            compute_crash_restart_backoff(1),
+            Duration::from_millis(BACKEND_CRASH_RESTART_INITIAL_BACKOFF_MS)
+        );
+        assert_eq!(
+            compute_crash_restart_backoff(2),
+            Duration::from_millis(BACKEND_CRASH_RESTART_INITIAL_BACKOFF_MS * 2)
+        );
+        assert_eq!(
+            compute_crash_restart_backoff(64),
+            Duration::from_millis(BACKEND_CRASH_RESTART_MAX_BACKOFF_MS)
+        );
+    }
+
+    #[test]
+    fn record_crash_gives_up_after_crash_loop_limit() {
+        let mut tracker = CrashRestartTracker::default();
+        let now = Instant::now();
+        for attempt in 1..=BACKEND_CRASH_LOOP_MAX_RESTARTS {
+            assert_eq!(
+                tracker.record_crash(now, format!("exit status: {attempt}")),
+                CrashRestartDecision::Restart {
+                    attempt,
+                    delay: compute_crash_restart_backoff(attempt),
+                }
+            );
+        }
+
+        assert_eq!(
+            tracker.record_crash(now, "exit status: 1".to_string()),
+            CrashRestartDecision::GiveUp {
+                crashes: BACKEND_CRASH_LOOP_MAX_RESTARTS + 1
+            }
+        );
+    }
+
+    #[test]
+    fn record_crash_forgets_crashes_outside_window() {
+        let mut tracker = CrashRestartTracker::default();
+        let start = Instant::now();
+        for _ in 0..BACKEND_CRASH_LOOP_MAX_RESTARTS {
+            tracker.record_crash(start, "exit status: 1".to_string());
+        }
+
+        let later = start + BACKEND_CRASH_LOOP_WINDOW;
+        assert_eq!(
+            tracker.record_crash(later, "exit status: 1".to_string()),
+            CrashRestartDecision::Restart {
+                attempt: 1,
+                delay: compute_crash_restart_backoff(1),
+            }
+        );
+        assert_eq!(tracker.describe_recent_exit_statuses(), "exit status: 1");
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    env,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    append_desktop_log, backend, backend::host::BackendHost, logging::DesktopLogLevel,
+    restart_backend_flow, BackendState, LaunchPlan, BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV,
+    BACKEND_LIVENESS_FAILURE_THRESHOLD_MAX, BACKEND_LIVENESS_FAILURE_THRESHOLD_MIN,
+    BACKEND_LIVENESS_HTTP_PATH_ENV, BACKEND_LIVENESS_INTERVAL_ENV,
+    BACKEND_LIVENESS_INTERVAL_MAX_MS, BACKEND_LIVENESS_INTERVAL_MIN_MS,
+    DEFAULT_BACKEND_LIVENESS_FAILURE_THRESHOLD, DEFAULT_BACKEND_LIVENESS_INTERVAL_MS,
+};
+
+const WATCHDOG_SLEEP_SLICE: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LivenessWatchdogConfig {
+    pub(crate) path: String,
+    pub(crate) interval_ms: u64,
+    pub(crate) probe_timeout_ms: u64,
+    pub(crate) failure_threshold: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LivenessVerdict {
+    Healthy,
+    Failing { failures: u32 },
+    Hung { failures: u32 },
+}
+
+#[derive(Debug)]
+pub(crate) struct LivenessTracker {
+    consecutive_failures: u32,
+    failure_threshold: u32,
+}
+
+impl LivenessTracker {
+    pub(crate) fn new(failure_threshold: u32) -> Self {
+        Self {
+            consecutive_failures: 0,
+            failure_threshold: failure_threshold.max(1),
+        }
+    }
+
+    pub(crate) fn record(&mut self, healthy: bool) -> LivenessVerdict {
+        if healthy {
+            self.consecutive_failures = 0;
+            return LivenessVerdict::Healthy;
+        }
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        let failures = self.consecutive_failures;
+        if failures >= self.failure_threshold {
+            LivenessVerdict::Hung { failures }
+        } else {
+            LivenessVerdict::Failing { failures }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.consecutive_failures = 0;
+    }
+}
+
+/// `ASTRBOT_BACKEND_LIVENESS_INTERVAL_MS=0` turns the watchdog off.
+pub(crate) fn resolve_liveness_watchdog_config<F>(
+    readiness: &backend::config::BackendReadinessConfig,
+    mut log: F,
+) -> Option<LivenessWatchdogConfig>
+where
+    F: FnMut(String),
+{
+    let interval_ms = match env::var(BACKEND_LIVENESS_INTERVAL_ENV) {
+        Ok(raw) if raw.trim() == "0" => return None,
+        Ok(raw) => backend::config::parse_clamped_timeout_env(
+            &raw,
+            BACKEND_LIVENESS_INTERVAL_ENV,
+            DEFAULT_BACKEND_LIVENESS_INTERVAL_MS,
+            BACKEND_LIVENESS_INTERVAL_MIN_MS,
+            BACKEND_LIVENESS_INTERVAL_MAX_MS,
+            &mut log,
+        ),
+        Err(_) => DEFAULT_BACKEND_LIVENESS_INTERVAL_MS,
+    };
+    let failure_threshold = match env::var(BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV) {
+        Ok(raw) => backend::config::parse_clamped_u64(
+            &raw,
+            BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV,
+            DEFAULT_BACKEND_LIVENESS_FAILURE_THRESHOLD,
+            BACKEND_LIVENESS_FAILURE_THRESHOLD_MIN,
+            BACKEND_LIVENESS_FAILURE_THRESHOLD_MAX,
+            &mut log,
+        ),
+        Err(_) => DEFAULT_BACKEND_LIVENESS_FAILURE_THRESHOLD,
+    };
+    let path = backend::config::resolve_backend_ready_http_path(
+        BACKEND_LIVENESS_HTTP_PATH_ENV,
+        &readiness.path,
+        &mut log,
+    );
+
+    Some(LivenessWatchdogConfig {
+        path,
+        interval_ms,
+        probe_timeout_ms: readiness.probe_timeout_ms,
+        failure_threshold: u32::try_from(failure_threshold).unwrap_or(u32::MAX),
+    })
+}
+
+fn should_stop_watching(state: &BackendState, stop_flag: &AtomicBool) -> bool {
+    stop_flag.load(Ordering::Relaxed) || state.is_quitting()
+}
+
+pub(crate) fn sleep_unless_stopped(
+    state: &BackendState,
+    stop_flag: &AtomicBool,
+    duration: Duration,
+) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if should_stop_watching(state, stop_flag) {
+            return false;
+        }
+        thread::sleep(WATCHDOG_SLEEP_SLICE.min(deadline - Instant::now()));
+    }
+    !should_stop_watching(state, stop_flag)
+}
+
+impl BackendState {
+    pub(crate) fn stop_liveness_watchdog(&self) {
+        match self.liveness_watchdog_stop.lock() {
+            Ok(mut guard) => {
+                if let Some(flag) = guard.take() {
+                    flag.store(true, Ordering::Relaxed);
+                }
+            }
+            Err(error) => {
+                self.append_desktop_log(&format!(
+                    "backend liveness watchdog stop flag lock poisoned: {error}"
+                ));
+            }
+        }
+    }
+
+    fn register_liveness_watchdog_stop_flag(&self) -> Option<Arc<AtomicBool>> {
+        self.stop_liveness_watchdog();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        match self.liveness_watchdog_stop.lock() {
+            Ok(mut guard) => {
+                *guard = Some(stop_flag.clone());
+                Some(stop_flag)
+            }
+            Err(error) => {
+                self.append_desktop_log(&format!(
+                    "backend liveness watchdog stop flag lock poisoned on start: {error}"
+                ));
+                None
+            }
+        }
+    }
+
+    /// Starts probing once the managed backend has passed readiness; attached
+    /// backends are not ours to restart.
+    pub(crate) fn start_liveness_watchdog(&self, app: &impl BackendHost, plan: &LaunchPlan) {
+        let Some(child_pid) = self.managed_child_pid() else {
+            return;
+        };
+        let readiness =
+            backend::runtime::backend_readiness_config(plan, &self.profile_id, append_desktop_log);
+        let Some(config) = resolve_liveness_watchdog_config(&readiness, |message| {
+            self.append_desktop_log(&message)
+        }) else {
+            self.stop_liveness_watchdog();
+            self.append_desktop_log("backend liveness watchdog disabled by configuration");
+            return;
+        };
+        let Some(stop_flag) = self.register_liveness_watchdog_stop_flag() else {
+            return;
+        };
+
+        self.append_desktop_log_at(
+            DesktopLogLevel::Debug,
+            &format!(
+                "backend liveness watchdog started: pid={child_pid}, path={}, interval_ms={}, failure_threshold={}",
+                config.path, config.interval_ms, config.failure_threshold
+            ),
+        );
+        let app_handle = app.clone();
+        let profile_id = self.profile_id.clone();
+        thread::spawn(move || {
+            let Some(state) = app_handle.profile_state(&profile_id) else {
+                return;
+            };
+            state.run_liveness_watchdog(&app_handle, &stop_flag, child_pid, &config);
+        });
+    }
+
+    fn run_liveness_watchdog(
+        &self,
+        app: &impl BackendHost,
+        stop_flag: &AtomicBool,
+        child_pid: u32,
+        config: &LivenessWatchdogConfig,
+    ) {
+        let mut tracker = LivenessTracker::new(config.failure_threshold);
+        let interval = Duration::from_millis(config.interval_ms);
+        loop {
+            if !sleep_unless_stopped(self, stop_flag, interval) {
+                return;
+            }
+            if restart_backend_flow::is_backend_action_in_progress(self) {
+                tracker.reset();
+                continue;
+            }
+            if self.managed_child_pid() != Some(child_pid) {
+                return;
+            }
+
+            let http_status = self.request_backend_status_code(
+                "GET",
+                &config.path,
+                config.probe_timeout_ms,
+                None,
+                None,
+            );
+            let healthy = matches!(http_status, Some(status) if (200..400).contains(&status));
+            match tracker.record(healthy) {
+                LivenessVerdict::Healthy => {}
+                LivenessVerdict::Failing { failures } => self.append_desktop_log_at(
+                    DesktopLogLevel::Warn,
+                    &format!(
+                        "backend liveness probe failed: pid={child_pid}, path={}, status={}, consecutive_failures={failures}/{}",
+                        config.path,
+                        describe_http_status(http_status),
+                        config.failure_threshold
+                    ),
+                ),
+                LivenessVerdict::Hung { failures } => {
+                    // A restart may have started while the last probe was in flight.
+                    if restart_backend_flow::is_backend_action_in_progress(self) {
+                        tracker.reset();
+                        continue;
+                    }
+                    self.restart_hung_backend(app, child_pid, config, failures, http_status);
+                    tracker.reset();
+                }
+            }
+        }
+    }
+
+    fn restart_hung_backend(
+        &self,
+        app: &impl BackendHost,
+        child_pid: u32,
+        config: &LivenessWatchdogConfig,
+        failures: u32,
+        last_http_status: Option<u16>,
+    ) {
+        let tcp_reachable = self.ping_backend(
+            config
+                .probe_timeout_ms
+                .min(crate::BACKEND_READY_TCP_PROBE_TIMEOUT_MAX_MS),
+        );
+        self.append_restart_log(&format!(
+            "backend considered hung after {failures} failed liveness probes: pid={child_pid}, backend_url={}, path={}, probe_timeout_ms={}, last_http_status={}, tcp_reachable={tcp_reachable}",
+            self.backend_url(),
+            config.path,
+            config.probe_timeout_ms,
+            describe_http_status(last_http_status)
+        ));
+        if let Some(stderr_tail) = self.describe_recent_backend_stderr() {
+            self.append_restart_log(&format!("recent backend stderr:\n{stderr_tail}"));
+        }
+
+        match self.restart_backend(app, None) {
+            Ok(()) => {
+                self.append_restart_log("backend restart after failed liveness probes completed")
+            }
+            Err(error) => self.append_restart_log(&format!(
+                "backend restart after failed liveness probes failed: {error}"
+            )),
+        }
+        app.backend_status_changed();
+    }
+}
+
+fn describe_http_status(status: Option<u16>) -> String {
+    status
+        .map(|status| status.to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::{resolve_liveness_watchdog_config, LivenessTracker, LivenessVerdict};
+    use crate::{
+        backend::config::BackendReadinessConfig, BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV,
+        BACKEND_LIVENESS_HTTP_PATH_ENV, BACKEND_LIVENESS_INTERVAL_ENV,
+        DEFAULT_BACKEND_LIVENESS_FAILURE_THRESHOLD, DEFAULT_BACKEND_LIVENESS_INTERVAL_MS,
+    };
+
+    fn readiness() -> BackendReadinessConfig {
+        BackendReadinessConfig {
+            path: "/api/stat/start-time".to_string(),
+            probe_timeout_ms: 800,
+            poll_interval_ms: 300,
+            startup_idle_timeout_ms: 0,
+            startup_heartbeat_path: None,
+            probes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn liveness_tracker_reports_hung_after_threshold_and_resets_on_success() {
+        let mut tracker = LivenessTracker::new(3);
+        assert_eq!(
+            tracker.record(false),
+            LivenessVerdict::Failing { failures: 1 }
+        );
+        assert_eq!(tracker.record(true), LivenessVerdict::Healthy);
+        assert_eq!(
+            tracker.record(false),
+            LivenessVerdict::Failing { failures: 1 }
+        );
+        assert_eq!(
+            tracker.record(false),
+            LivenessVerdict::Failing { failures: 2 }
+        );
+        assert_eq!(tracker.record(false), LivenessVerdict::Hung { failures: 3 });
+
+        tracker.reset();
+        assert_eq!(
+            tracker.record(false),
+            LivenessVerdict::Failing { failures: 1 }
+        );
+    }
+
+    #[test]
+    fn resolve_liveness_watchdog_config_follows_readiness_and_env() {
+        env::remove_var(BACKEND_LIVENESS_INTERVAL_ENV);
+        env::remove_var(BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV);
+        env::remove_var(BACKEND_LIVENESS_HTTP_PATH_ENV);
+        let config = resolve_liveness_watchdog_config(&readiness(), |_| {})
+            .expect("watchdog enabled by default");
+        assert_eq!(config.path, "/api/stat/start-time");
+        assert_eq!(config.interval_ms, DEFAULT_BACKEND_LIVENESS_INTERVAL_MS);
+        assert_eq!(config.probe_timeout_ms, 800);
+        assert_eq!(
+            u64::from(config.failure_threshold),
+            DEFAULT_BACKEND_LIVENESS_FAILURE_THRESHOLD
+        );
+
+        env::set_var(BACKEND_LIVENESS_HTTP_PATH_ENV, "api/health");
+        env::set_var(BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV, "5");
+        let config =
+            resolve_liveness_watchdog_config(&readiness(), |_| {}).expect("watchdog still enabled");
+        assert_eq!(config.path, "/api/health");
+        assert_eq!(config.failure_threshold, 5);
+
+        env::set_var(BACKEND_LIVENESS_INTERVAL_ENV, "0");
+        assert_eq!(resolve_liveness_watchdog_config(&readiness(), |_| {}), None);
+
+        env::remove_var(BACKEND_LIVENESS_INTERVAL_ENV);
+        env::remove_var(BACKEND_LIVENESS_FAILURE_THRESHOLD_ENV);
+        env::remove_var(BACKEND_LIVENESS_HTTP_PATH_ENV);
+    }
+}
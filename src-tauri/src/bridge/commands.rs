@@ -13,10 +13,16 @@ use crate::bridge::updater_types::{
     map_update_channel_ok, map_update_check_error, map_update_install_error, map_update_install_ok,
     DesktopAppUpdateChannelResult, DesktopAppUpdateCheckResult, DesktopAppUpdateResult,
 };
+use crate::desktop_events::{self, DesktopEvent};
+use crate::diagnostics::DiagnosticBundleResult;
+use crate::log_query::{self, LogQuery, LogQueryResult, LogSource};
+use crate::profiles::registry::{DesktopProfileSummary, ProfileRegistry};
 use crate::{
-    append_desktop_log, restart_backend_flow, runtime_paths, shell_locale, tray, update_channel,
-    BackendBridgeResult, BackendBridgeState, BackendState, DesktopAuthBridgeResult,
-    DEFAULT_SHELL_LOCALE,
+    active_backend_state, append_desktop_log, backend_log_rotation_policy,
+    desktop_log_rotation_policy, logging, profiles, restart_backend_flow, runtime_paths, tray,
+    update_channel, window, BackendBridgeResult, BackendBridgeState, BackendState,
+    DesktopAuthBridgeResult, DEFAULT_LOG_QUERY_LIMIT, DEFAULT_SHELL_LOCALE, DESKTOP_LOG_FILE,
+    MAX_LOG_QUERY_LIMIT,
 };
 
 fn resolve_update_channel(app_handle: &AppHandle) -> update_channel::UpdateChannel {
@@ -139,10 +145,10 @@ fn build_restart_backend_after_failed_install(
     restart_plan: crate::LaunchPlan,
 ) -> impl FnOnce() -> Result<(), String> {
     move || {
-        let state = app_handle.state::<BackendState>();
+        let state = active_backend_state(&app_handle);
         append_desktop_log("update install failed before exit, restarting managed backend");
-        state.start_backend_process(&app_handle, &restart_plan)?;
-        state.wait_for_backend(&restart_plan)
+        let cancel_scope = state.begin_startup_cancel_scope();
+        state.spawn_and_wait_for_backend(&app_handle, &restart_plan, &cancel_scope)
     }
 }
 
@@ -209,7 +215,7 @@ pub(crate) fn desktop_bridge_is_desktop_runtime() -> bool {
 
 #[tauri::command]
 pub(crate) fn desktop_bridge_get_backend_state(app_handle: AppHandle) -> BackendBridgeState {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(&app_handle);
     state.bridge_state(&app_handle)
 }
 
@@ -218,7 +224,7 @@ pub(crate) async fn desktop_bridge_get_auth_token(
     app_handle: AppHandle,
 ) -> DesktopAuthBridgeResult {
     let task_result = tauri::async_runtime::spawn_blocking(move || {
-        let state = app_handle.state::<BackendState>();
+        let state = active_backend_state(&app_handle);
         state.request_desktop_auth_session()
     })
     .await;
@@ -259,7 +265,7 @@ pub(crate) fn desktop_bridge_set_auth_token(
     app_handle: AppHandle,
     auth_token: Option<String>,
 ) -> BackendBridgeResult {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(&app_handle);
     state.set_restart_auth_token(auth_token.as_deref());
     BackendBridgeResult {
         ok: true,
@@ -272,7 +278,7 @@ pub(crate) async fn desktop_bridge_restart_backend(
     app_handle: AppHandle,
     auth_token: Option<String>,
 ) -> BackendBridgeResult {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(&app_handle);
     if restart_backend_flow::is_backend_action_in_progress(&state) {
         return BackendBridgeResult {
             ok: false,
@@ -285,15 +291,45 @@ pub(crate) async fn desktop_bridge_restart_backend(
 
 #[tauri::command]
 pub(crate) fn desktop_bridge_stop_backend(app_handle: AppHandle) -> BackendBridgeResult {
-    let state = app_handle.state::<BackendState>();
-    if restart_backend_flow::is_backend_action_in_progress(&state) {
+    let state = active_backend_state(&app_handle);
+    if restart_backend_flow::is_backend_action_in_progress(&state)
+        && !state.cancel_backend_startup("stop requested from bridge")
+    {
         return BackendBridgeResult {
             ok: false,
             reason: Some("Backend action already in progress.".to_string()),
         };
     }
 
-    match state.stop_backend_for_bridge() {
+    let result = state.stop_backend_for_bridge();
+    tray::labels::update_backend_status_label(
+        &app_handle,
+        DEFAULT_SHELL_LOCALE,
+        append_desktop_log,
+    );
+    match result {
+        Ok(()) => BackendBridgeResult {
+            ok: true,
+            reason: None,
+        },
+        Err(error) => BackendBridgeResult {
+            ok: false,
+            reason: Some(error),
+        },
+    }
+}
+
+#[tauri::command]
+pub(crate) fn desktop_bridge_list_profiles(app_handle: AppHandle) -> Vec<DesktopProfileSummary> {
+    app_handle.state::<ProfileRegistry>().summaries()
+}
+
+#[tauri::command]
+pub(crate) fn desktop_bridge_switch_profile(
+    app_handle: AppHandle,
+    profile_id: String,
+) -> BackendBridgeResult {
+    match profiles::runtime::switch_active_profile(&app_handle, profile_id.trim()) {
         Ok(()) => BackendBridgeResult {
             ok: true,
             reason: None,
@@ -329,24 +365,89 @@ pub(crate) fn desktop_bridge_open_external_url(url: String) -> BackendBridgeResu
     }
 }
 
+fn resolve_log_source_path(app_handle: &AppHandle, source: LogSource) -> std::path::PathBuf {
+    let packaged_root_dir = runtime_paths::default_packaged_root_dir();
+    match source {
+        LogSource::Desktop => {
+            logging::resolve_desktop_log_path(packaged_root_dir, DESKTOP_LOG_FILE)
+        }
+        LogSource::Backend => {
+            let state = active_backend_state(app_handle);
+            let root_dir = state
+                .resolve_launch_plan(app_handle)
+                .ok()
+                .and_then(|plan| plan.root_dir);
+            logging::resolve_backend_log_path(root_dir.as_deref(), packaged_root_dir)
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn desktop_bridge_query_logs(
+    app_handle: AppHandle,
+    source: String,
+    query: Option<LogQuery>,
+) -> LogQueryResult {
+    let Some(source) = LogSource::parse(&source) else {
+        return LogQueryResult::error("Invalid log source. Expected 'desktop' or 'backend'.");
+    };
+
+    let task_result = tauri::async_runtime::spawn_blocking(move || {
+        let path = resolve_log_source_path(&app_handle, source);
+        let rotation = match source {
+            LogSource::Desktop => desktop_log_rotation_policy(),
+            LogSource::Backend => backend_log_rotation_policy(),
+        };
+        log_query::query_log_lines(
+            source,
+            &path,
+            rotation.backup_count,
+            &query.unwrap_or_default(),
+            DEFAULT_LOG_QUERY_LIMIT,
+            MAX_LOG_QUERY_LIMIT,
+        )
+    })
+    .await;
+
+    match task_result {
+        Ok(result) => result,
+        Err(error) => LogQueryResult::error(format!("Log query task failed: {error}")),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn desktop_bridge_export_diagnostics(
+    app_handle: AppHandle,
+) -> DiagnosticBundleResult {
+    let task_result = tauri::async_runtime::spawn_blocking(move || {
+        let state = active_backend_state(&app_handle);
+        state.export_diagnostic_bundle(&app_handle)
+    })
+    .await;
+
+    match task_result {
+        Ok(result) => DiagnosticBundleResult::from_export(result),
+        Err(error) => DiagnosticBundleResult::from_export(Err(format!(
+            "Diagnostic bundle task failed: {error}"
+        ))),
+    }
+}
+
 #[tauri::command]
 pub(crate) fn desktop_bridge_set_shell_locale(
     app_handle: AppHandle,
     locale: Option<String>,
 ) -> BackendBridgeResult {
-    let packaged_root_dir = runtime_paths::default_packaged_root_dir();
-    match shell_locale::write_cached_shell_locale(locale.as_deref(), packaged_root_dir.as_deref()) {
-        Ok(()) => {
-            tray::labels::update_tray_menu_labels(
-                &app_handle,
-                DEFAULT_SHELL_LOCALE,
-                append_desktop_log,
-            );
-            BackendBridgeResult {
-                ok: true,
-                reason: None,
-            }
-        }
+    match window::actions::set_shell_locale(
+        &app_handle,
+        locale.as_deref(),
+        DEFAULT_SHELL_LOCALE,
+        append_desktop_log,
+    ) {
+        Ok(()) => BackendBridgeResult {
+            ok: true,
+            reason: None,
+        },
         Err(error) => {
             append_desktop_log(&format!("failed to persist shell locale: {error}"));
             BackendBridgeResult {
@@ -378,6 +479,12 @@ pub(crate) fn desktop_bridge_set_app_update_channel(
         Ok(()) => {
             append_desktop_log(&format!("update channel set to {:?}", channel));
             let _ = app_handle;
+            desktop_events::publish(
+                None,
+                DesktopEvent::SettingsChanged {
+                    key: "updateChannel",
+                },
+            );
             map_update_channel_ok(channel)
         }
         Err(error) => {
@@ -406,14 +513,23 @@ pub(crate) async fn desktop_bridge_check_app_update(
     };
 
     match updater.check().await {
-        Ok(Some(update)) => match update_mode {
-            DesktopUpdateMode::ManualDownload => map_manual_download_update_available_result(
-                &current_version,
-                &update.version,
-                desktop_manual_download_reason(),
-            ),
-            _ => map_update_available_result(&current_version, &update.version),
-        },
+        Ok(Some(update)) => {
+            desktop_events::publish(
+                None,
+                DesktopEvent::UpdateAvailable {
+                    current_version: current_version.clone(),
+                    latest_version: update.version.clone(),
+                },
+            );
+            match update_mode {
+                DesktopUpdateMode::ManualDownload => map_manual_download_update_available_result(
+                    &current_version,
+                    &update.version,
+                    desktop_manual_download_reason(),
+                ),
+                _ => map_update_available_result(&current_version, &update.version),
+            }
+        }
         Ok(None) => match update_mode {
             DesktopUpdateMode::ManualDownload => map_manual_download_no_update_result(
                 &current_version,
@@ -456,7 +572,7 @@ pub(crate) async fn desktop_bridge_install_app_update(
         }
     };
 
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(&app_handle);
     let stop_managed_backend = cfg!(target_os = "windows") && has_managed_backend_child(&state);
     let restart_backend_after_failed_install = if stop_managed_backend {
         let restart_plan = match state.resolve_launch_plan(&app_handle) {
@@ -3,7 +3,7 @@ use std::sync::OnceLock;
 use serde::Deserialize;
 use url::Url;
 
-use crate::{bridge::origin_policy, TRAY_RESTART_BACKEND_EVENT};
+use crate::{bridge::origin_policy, DESKTOP_EVENT_NAME, TRAY_RESTART_BACKEND_EVENT};
 
 static DESKTOP_BRIDGE_BOOTSTRAP_TEMPLATE: &str = include_str!("../bridge_bootstrap.js");
 static DESKTOP_BRIDGE_CHAT_TRANSPORT_CONTRACT_TEMPLATE: &str =
@@ -44,6 +44,7 @@ fn desktop_bridge_bootstrap_script() -> &'static str {
             let contract = desktop_bridge_chat_transport_contract();
             DESKTOP_BRIDGE_BOOTSTRAP_TEMPLATE
                 .replace("{TRAY_RESTART_BACKEND_EVENT}", TRAY_RESTART_BACKEND_EVENT)
+                .replace("{DESKTOP_EVENT}", DESKTOP_EVENT_NAME)
                 .replace("{CHAT_TRANSPORT_MODE_STORAGE_KEY}", &contract.storage_key)
                 .replace("{CHAT_TRANSPORT_MODE_WEBSOCKET}", &contract.websocket_value)
         })
@@ -26,11 +26,17 @@
     SET_APP_UPDATE_CHANNEL: 'desktop_bridge_set_app_update_channel',
     RESTART_BACKEND: 'desktop_bridge_restart_backend',
     STOP_BACKEND: 'desktop_bridge_stop_backend',
+    LIST_PROFILES: 'desktop_bridge_list_profiles',
+    SWITCH_PROFILE: 'desktop_bridge_switch_profile',
     OPEN_EXTERNAL_URL: 'desktop_bridge_open_external_url',
+    QUERY_LOGS: 'desktop_bridge_query_logs',
+    EXPORT_DIAGNOSTICS: 'desktop_bridge_export_diagnostics',
     CHECK_APP_UPDATE: 'desktop_bridge_check_app_update',
     INSTALL_APP_UPDATE: 'desktop_bridge_install_app_update',
   });
   const TRAY_RESTART_BACKEND_EVENT = '{TRAY_RESTART_BACKEND_EVENT}';
+  const DESKTOP_EVENT = '{DESKTOP_EVENT}';
+  const DESKTOP_EVENT_WILDCARD = '*';
 
   const invokeBridge = async (command, payload = {}) => {
     try {
@@ -149,6 +155,57 @@
     }
   };
 
+  const desktopEventState =
+    window.__astrbotDesktopEventState ||
+    (window.__astrbotDesktopEventState = {
+      handlers: new Map(),
+      unlistenDesktopEvent: null
+    });
+
+  const dispatchDesktopEvent = (payload) => {
+    if (!payload || typeof payload !== 'object' || typeof payload.kind !== 'string') return;
+    for (const eventKind of [payload.kind, DESKTOP_EVENT_WILDCARD]) {
+      const handlers = desktopEventState.handlers.get(eventKind);
+      if (!handlers) continue;
+      for (const handler of handlers) {
+        try {
+          handler(payload);
+        } catch {}
+      }
+    }
+  };
+
+  // `eventKind` is a payload `kind` such as 'backendReady', or '*' for every event.
+  const onDesktopEvent = (eventKind, callback) => {
+    if (typeof eventKind !== 'string' || !eventKind || typeof callback !== 'function') {
+      return () => {};
+    }
+    let handlers = desktopEventState.handlers.get(eventKind);
+    if (!handlers) {
+      handlers = new Set();
+      desktopEventState.handlers.set(eventKind, handlers);
+    }
+    const handler = (payload) => callback(payload);
+    handlers.add(handler);
+    return () => {
+      handlers.delete(handler);
+    };
+  };
+
+  const listenToDesktopEvents = async () => {
+    if (typeof desktopEventState.unlistenDesktopEvent === 'function') return;
+    try {
+      const unlisten = await createEventListener(DESKTOP_EVENT, (event) => {
+        dispatchDesktopEvent(event?.payload);
+      });
+      if (typeof unlisten === 'function') {
+        desktopEventState.unlistenDesktopEvent = unlisten;
+      }
+    } catch (error) {
+      console.warn('Failed to listen for desktop events', error);
+    }
+  };
+
   const TOKEN_STORAGE_KEY = 'token';
   const USER_STORAGE_KEY = 'user';
   const SHELL_LOCALE_STORAGE_KEY = 'astrbot-locale';
@@ -830,6 +887,11 @@
       });
     },
     stopBackend: () => invokeBridge(BRIDGE_COMMANDS.STOP_BACKEND),
+    listProfiles: () => invokeBridge(BRIDGE_COMMANDS.LIST_PROFILES),
+    switchProfile: (profileId) =>
+      invokeBridge(BRIDGE_COMMANDS.SWITCH_PROFILE, {
+        profileId: typeof profileId === 'string' ? profileId : String(profileId ?? ''),
+      }),
     openExternalUrl: (url) => {
       const rawUrl = typeof url === 'string' ? url : String(url ?? '');
       if (!rawUrl.trim()) {
@@ -842,7 +904,14 @@
         url: rawUrl,
       });
     },
+    queryLogs: (source, query = {}) =>
+      invokeBridge(BRIDGE_COMMANDS.QUERY_LOGS, {
+        source: typeof source === 'string' ? source : String(source ?? ''),
+        query: query && typeof query === 'object' ? query : {},
+      }),
+    exportDiagnostics: () => invokeBridge(BRIDGE_COMMANDS.EXPORT_DIAGNOSTICS),
     onTrayRestartBackend,
+    on: onDesktopEvent,
   };
 
   window.astrbotAppUpdater = {
@@ -857,6 +926,7 @@
 
   installNavigationBridges();
   void listenToTrayRestartBackendEvent();
+  void listenToDesktopEvents();
   patchLocalStorageBridgeSync();
   ensureDefaultChatTransportMode();
   void syncAuthToken();
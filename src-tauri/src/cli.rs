@@ -0,0 +1,266 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use serde_json::json;
+
+use crate::{
+    diagnostics, profiles, shell_locale, startup_mode, ASTRBOT_ROOT_ENV, BACKEND_CMD_ENV,
+    HEADLESS_ARG, HEADLESS_ENV, STARTUP_MODE_ENV,
+};
+
+const USAGE: &str = "\
+Usage: astrbot-desktop [OPTIONS]
+
+Options:
+  --root <DIR>            AstrBot root directory (ASTRBOT_ROOT)
+  --backend-cmd <CMD>     Custom backend launch command (ASTRBOT_BACKEND_CMD)
+  --startup-mode <MODE>   Startup page mode: loading or panel-update (ASTRBOT_DESKTOP_STARTUP_MODE)
+  --locale <LOCALE>       Shell locale to persist: zh-CN or en-US
+  --silent                Keep the main window hidden for this launch
+  --headless              Run the backend without webview or tray (ASTRBOT_DESKTOP_HEADLESS)
+  --print-launch-plan     Print the resolved backend launch plan as JSON and exit
+  -h, --help              Print this help and exit
+  -V, --version           Print the version and exit
+
+Options given to a second launch are forwarded to the running instance, which
+applies --locale and --silent; the others need a restart.";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct CliArgs {
+    pub(crate) root: Option<PathBuf>,
+    pub(crate) backend_cmd: Option<String>,
+    pub(crate) startup_mode: Option<String>,
+    pub(crate) locale: Option<&'static str>,
+    pub(crate) silent: bool,
+    pub(crate) headless: bool,
+    pub(crate) print_launch_plan: bool,
+    pub(crate) help: bool,
+    pub(crate) version: bool,
+    pub(crate) unrecognized: Vec<String>,
+}
+
+impl CliArgs {
+    /// Env vars that carry the same settings, so CLI values flow through the
+    /// existing env-based config paths and take precedence over the environment.
+    pub(crate) fn env_overrides(&self) -> Vec<(&'static str, String)> {
+        let mut overrides = Vec::new();
+        if let Some(root) = &self.root {
+            overrides.push((ASTRBOT_ROOT_ENV, root.to_string_lossy().to_string()));
+        }
+        if let Some(backend_cmd) = &self.backend_cmd {
+            overrides.push((BACKEND_CMD_ENV, backend_cmd.clone()));
+        }
+        if let Some(startup_mode) = &self.startup_mode {
+            overrides.push((STARTUP_MODE_ENV, startup_mode.clone()));
+        }
+        if self.headless {
+            overrides.push((HEADLESS_ENV, "1".to_string()));
+        }
+        overrides
+    }
+
+    /// Options a running instance cannot pick up without a restart. `--headless`
+    /// and `--print-launch-plan` never reach a running instance: both are handled
+    /// before the single-instance plugin starts.
+    pub(crate) fn restart_only_options(&self) -> Vec<&'static str> {
+        [
+            (self.root.is_some(), "--root"),
+            (self.backend_cmd.is_some(), "--backend-cmd"),
+            (self.startup_mode.is_some(), "--startup-mode"),
+        ]
+        .into_iter()
+        .filter_map(|(given, name)| given.then_some(name))
+        .collect()
+    }
+}
+
+pub(crate) fn usage() -> &'static str {
+    USAGE
+}
+
+/// Release builds on Windows use the GUI subsystem and start without a console,
+/// so borrow the launching terminal's console before printing to stdout/stderr.
+/// Output still lands after the terminal has printed its next prompt.
+#[cfg(all(target_os = "windows", not(debug_assertions)))]
+pub(crate) fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // Fails when started without a parent console (e.g. from Explorer); there is
+    // nowhere to print then anyway.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(all(target_os = "windows", not(debug_assertions))))]
+pub(crate) fn attach_parent_console() {}
+
+fn split_option(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('=') {
+        Some((name, value)) if name.starts_with("--") => (name, Some(value)),
+        _ => (arg, None),
+    }
+}
+
+fn non_empty_value(name: &str, value: Option<String>) -> Result<String, String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("{name} requires a value"))
+}
+
+/// Parses the program arguments, skipping the program name. Unknown arguments
+/// are collected instead of rejected because the OS may append its own.
+pub(crate) fn parse_cli_args<I, S>(args: I) -> Result<CliArgs, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut parsed = CliArgs::default();
+    let mut args = args
+        .into_iter()
+        .skip(1)
+        .map(|arg| arg.as_ref().to_string_lossy().to_string());
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = split_option(&arg);
+        let mut value = || inline_value.map(str::to_string).or_else(|| args.next());
+        match name {
+            "--root" => parsed.root = Some(PathBuf::from(non_empty_value(name, value())?)),
+            "--backend-cmd" => parsed.backend_cmd = Some(non_empty_value(name, value())?),
+            "--startup-mode" => {
+                let mode = non_empty_value(name, value())?;
+                if !mode.eq_ignore_ascii_case(startup_mode::STARTUP_MODE_LOADING)
+                    && !mode.eq_ignore_ascii_case(startup_mode::STARTUP_MODE_PANEL_UPDATE)
+                {
+                    return Err(format!(
+                        "invalid --startup-mode '{mode}': expected {} or {}",
+                        startup_mode::STARTUP_MODE_LOADING,
+                        startup_mode::STARTUP_MODE_PANEL_UPDATE
+                    ));
+                }
+                parsed.startup_mode = Some(mode.to_ascii_lowercase());
+            }
+            "--locale" => {
+                let locale = non_empty_value(name, value())?;
+                parsed.locale = Some(
+                    shell_locale::normalize_shell_locale(&locale)
+                        .ok_or_else(|| format!("unsupported --locale '{locale}'"))?,
+                );
+            }
+            "--silent" if inline_value.is_none() => parsed.silent = true,
+            HEADLESS_ARG if inline_value.is_none() => parsed.headless = true,
+            "--print-launch-plan" if inline_value.is_none() => parsed.print_launch_plan = true,
+            "-h" | "--help" if inline_value.is_none() => parsed.help = true,
+            "-V" | "--version" if inline_value.is_none() => parsed.version = true,
+            _ => parsed.unrecognized.push(arg.clone()),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Resolves the active profile's launch plan the way startup does and prints it
+/// as JSON. Returns the process exit code.
+pub(crate) fn print_launch_plan(resource_dir: Option<&Path>) -> i32 {
+    let registry = profiles::runtime::load_profile_registry();
+    let state = registry.active_state();
+    match state.resolve_launch_plan_with_resource_dir(resource_dir) {
+        Ok(plan) => {
+            let mut summary = diagnostics::launch_plan_summary(&plan, str::to_string);
+            summary["profile"] = json!(state.profile_id);
+            println!("{summary:#}");
+            0
+        }
+        Err(error) => {
+            eprintln!("failed to resolve launch plan: {error}");
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_cli_args, CliArgs};
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        parse_cli_args(std::iter::once("astrbot-desktop").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn parse_cli_args_reads_separate_and_inline_values() {
+        let parsed = parse(&[
+            "--root",
+            "/srv/astrbot",
+            "--backend-cmd=python main.py",
+            "--startup-mode",
+            "Panel-Update",
+            "--locale=en",
+            "--silent",
+        ])
+        .expect("parse args");
+
+        assert_eq!(parsed.root, Some(PathBuf::from("/srv/astrbot")));
+        assert_eq!(parsed.backend_cmd.as_deref(), Some("python main.py"));
+        assert_eq!(parsed.startup_mode.as_deref(), Some("panel-update"));
+        assert_eq!(parsed.locale, Some("en-US"));
+        assert!(parsed.silent);
+        assert!(!parsed.headless);
+    }
+
+    #[test]
+    fn parse_cli_args_rejects_missing_and_invalid_values() {
+        assert_eq!(parse(&["--root"]).unwrap_err(), "--root requires a value");
+        assert_eq!(
+            parse(&["--backend-cmd="]).unwrap_err(),
+            "--backend-cmd requires a value"
+        );
+        assert!(parse(&["--startup-mode", "fast"]).is_err());
+        assert!(parse(&["--locale", "fr-FR"]).is_err());
+    }
+
+    #[test]
+    fn parse_cli_args_collects_unknown_arguments() {
+        let parsed = parse(&["-psn_0_12345", "--silent=yes", "--headless"]).expect("parse args");
+
+        assert_eq!(parsed.unrecognized, vec!["-psn_0_12345", "--silent=yes"]);
+        assert!(!parsed.silent);
+        assert!(parsed.headless);
+    }
+
+    #[test]
+    fn env_overrides_map_onto_existing_env_vars() {
+        let parsed = parse(&[
+            "--root",
+            "/srv/astrbot",
+            "--backend-cmd",
+            "python main.py",
+            "--startup-mode",
+            "loading",
+            "--headless",
+            "--locale",
+            "zh",
+        ])
+        .expect("parse args");
+
+        assert_eq!(
+            parsed.env_overrides(),
+            vec![
+                ("ASTRBOT_ROOT", "/srv/astrbot".to_string()),
+                ("ASTRBOT_BACKEND_CMD", "python main.py".to_string()),
+                ("ASTRBOT_DESKTOP_STARTUP_MODE", "loading".to_string()),
+                ("ASTRBOT_DESKTOP_HEADLESS", "1".to_string()),
+            ]
+        );
+        assert_eq!(
+            parsed.restart_only_options(),
+            vec!["--root", "--backend-cmd", "--startup-mode"]
+        );
+        assert!(parse(&["--headless", "--print-launch-plan"])
+            .expect("parse args")
+            .restart_only_options()
+            .is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt;
 pub(crate) const DESKTOP_SESSION_SECRET_ENV: &str = "ASTRBOT_DESKTOP_SESSION_SECRET";
 pub(crate) const DESKTOP_SESSION_HEADER: &str = "X-AstrBot-Desktop-Session";
 pub(crate) const DESKTOP_SESSION_ENDPOINT: &str = "/api/v1/auth/desktop-session";
+pub(crate) const DESKTOP_SHUTDOWN_ENDPOINT: &str = "/api/v1/desktop/shutdown";
 
 pub(crate) struct DesktopSessionSecret(String);
 
@@ -19,6 +20,14 @@ impl DesktopSessionSecret {
         Ok(Self(secret))
     }
 
+    pub(crate) fn from_persisted(value: &str) -> Option<Self> {
+        let valid = value.len() == 64
+            && value
+                .bytes()
+                .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte));
+        valid.then(|| Self(value.to_string()))
+    }
+
     pub(crate) fn as_str(&self) -> &str {
         &self.0
     }
@@ -50,6 +59,17 @@ mod tests {
             .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte)));
     }
 
+    #[test]
+    fn persisted_secret_must_look_generated() {
+        let secret = DesktopSessionSecret::generate().expect("secret generation should succeed");
+        assert_eq!(
+            DesktopSessionSecret::from_persisted(secret.as_str()).map(|s| s.0),
+            Some(secret.as_str().to_string())
+        );
+        assert!(DesktopSessionSecret::from_persisted("abc123").is_none());
+        assert!(DesktopSessionSecret::from_persisted(&"AB".repeat(32)).is_none());
+    }
+
     #[test]
     fn debug_output_does_not_reveal_secret() {
         let secret = DesktopSessionSecret::generate().expect("secret generation should succeed");
@@ -0,0 +1,161 @@
+use std::{
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    append_desktop_log_at, logging::DesktopLogLevel, DESKTOP_EVENT_NAME,
+    DESKTOP_EVENT_SCHEMA_VERSION,
+};
+
+static EVENT_APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Every push event shares one Tauri channel; WebUI listeners filter on `kind`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum DesktopEvent {
+    BackendSpawned {
+        pid: u32,
+    },
+    BackendReady {
+        pid: Option<u32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    BackendCrashed {
+        exit_code: Option<i32>,
+        exit_status: Option<String>,
+    },
+    RestartStarted,
+    RestartFinished {
+        ok: bool,
+    },
+    BackendStopped,
+    #[serde(rename_all = "camelCase")]
+    UpdateAvailable {
+        current_version: String,
+        latest_version: String,
+    },
+    SettingsChanged {
+        key: &'static str,
+    },
+    ExitRequested {
+        trigger: &'static str,
+    },
+    StartupProgress(crate::backend::startup_progress::StartupProgress),
+    PortRelocated {
+        from: u16,
+        to: u16,
+    },
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DesktopEventEnvelope<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    event: &'a DesktopEvent,
+    profile_id: Option<&'a str>,
+    timestamp_ms: u64,
+}
+
+fn build_envelope<'a>(
+    event: &'a DesktopEvent,
+    profile_id: Option<&'a str>,
+    now: SystemTime,
+) -> DesktopEventEnvelope<'a> {
+    DesktopEventEnvelope {
+        schema_version: DESKTOP_EVENT_SCHEMA_VERSION,
+        event,
+        profile_id,
+        timestamp_ms: now
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default(),
+    }
+}
+
+pub(crate) fn install(app_handle: &AppHandle) {
+    let _ = EVENT_APP_HANDLE.set(app_handle.clone());
+}
+
+/// Events published before `install` (or in tests) are dropped.
+pub(crate) fn publish(profile_id: Option<&str>, event: DesktopEvent) {
+    let Some(app_handle) = EVENT_APP_HANDLE.get() else {
+        return;
+    };
+    let envelope = build_envelope(&event, profile_id, SystemTime::now());
+    if let Err(error) = app_handle.emit(DESKTOP_EVENT_NAME, &envelope) {
+        append_desktop_log_at(
+            DesktopLogLevel::Warn,
+            &format!("failed to emit desktop event {event:?}: {error}"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{build_envelope, DesktopEvent};
+
+    #[test]
+    fn envelope_flattens_event_kind_and_fields() {
+        let event = DesktopEvent::BackendCrashed {
+            exit_code: Some(1),
+            exit_status: Some("exit status: 1".to_string()),
+        };
+        let envelope = build_envelope(
+            &event,
+            Some("default"),
+            UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&envelope).expect("serialize envelope"),
+            serde_json::json!({
+                "schemaVersion": 1,
+                "kind": "backendCrashed",
+                "exitCode": 1,
+                "exitStatus": "exit status: 1",
+                "profileId": "default",
+                "timestampMs": 1_700_000_000_123u64,
+            })
+        );
+    }
+
+    #[test]
+    fn port_relocated_carries_both_ports() {
+        let event = DesktopEvent::PortRelocated {
+            from: 6185,
+            to: 51234,
+        };
+        let envelope = build_envelope(&event, Some("default"), UNIX_EPOCH);
+        assert_eq!(
+            serde_json::to_value(&envelope).expect("serialize envelope"),
+            serde_json::json!({
+                "schemaVersion": 1,
+                "kind": "portRelocated",
+                "from": 6185,
+                "to": 51234,
+                "profileId": "default",
+                "timestampMs": 0,
+            })
+        );
+    }
+
+    #[test]
+    fn unit_events_serialize_with_kind_only() {
+        let envelope = build_envelope(&DesktopEvent::RestartStarted, None, UNIX_EPOCH);
+        assert_eq!(
+            serde_json::to_value(&envelope).expect("serialize envelope"),
+            serde_json::json!({
+                "schemaVersion": 1,
+                "kind": "restartStarted",
+                "profileId": null,
+                "timestampMs": 0,
+            })
+        );
+    }
+}
@@ -32,6 +32,7 @@ pub(crate) enum DesktopSettingKey {
     LaunchAtLogin,
     SilentLaunch,
     CloseToTray,
+    DetachOnQuit,
 }
 
 fn default_launch_at_login() -> bool {
@@ -46,6 +47,10 @@ fn default_close_to_tray() -> bool {
     true
 }
 
+fn default_detach_on_quit() -> bool {
+    false
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct DesktopSettings {
     #[serde(rename = "launchAtLogin", default = "default_launch_at_login")]
@@ -54,6 +59,8 @@ pub(crate) struct DesktopSettings {
     pub(crate) silent_launch: bool,
     #[serde(rename = "closeToTray", default = "default_close_to_tray")]
     pub(crate) close_to_tray: bool,
+    #[serde(rename = "detachOnQuit", default = "default_detach_on_quit")]
+    pub(crate) detach_on_quit: bool,
     #[serde(flatten)]
     other: Map<String, Value>,
 }
@@ -64,17 +71,30 @@ impl Default for DesktopSettings {
             launch_at_login: default_launch_at_login(),
             silent_launch: default_silent_launch(),
             close_to_tray: default_close_to_tray(),
+            detach_on_quit: default_detach_on_quit(),
             other: Map::new(),
         }
     }
 }
 
+impl DesktopSettingKey {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::LaunchAtLogin => "launchAtLogin",
+            Self::SilentLaunch => "silentLaunch",
+            Self::CloseToTray => "closeToTray",
+            Self::DetachOnQuit => "detachOnQuit",
+        }
+    }
+}
+
 impl DesktopSettings {
     fn set(&mut self, key: DesktopSettingKey, value: bool) {
         match key {
             DesktopSettingKey::LaunchAtLogin => self.launch_at_login = value,
             DesktopSettingKey::SilentLaunch => self.silent_launch = value,
             DesktopSettingKey::CloseToTray => self.close_to_tray = value,
+            DesktopSettingKey::DetachOnQuit => self.detach_on_quit = value,
         }
     }
 }
@@ -182,6 +202,120 @@ pub(crate) fn read_desktop_settings(packaged_root_dir: Option<&Path>) -> Desktop
     }
 }
 
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct LoggingSettings {
+    pub(crate) format: Option<String>,
+    pub(crate) level_spec: Option<String>,
+    pub(crate) rotation: LogRotationSettings,
+}
+
+/// Raw `logRotation` values; bounds are validated by the logger like env overrides.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct LogRotationSettings {
+    pub(crate) desktop_max_bytes: Option<String>,
+    pub(crate) backend_max_bytes: Option<String>,
+    pub(crate) backup_count: Option<String>,
+    pub(crate) compress_backups: Option<String>,
+    pub(crate) max_age_days: Option<String>,
+}
+
+fn setting_value_to_raw(value: &Value) -> Option<String> {
+    match value {
+        Value::String(raw) => Some(raw.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn read_log_rotation_settings(value: Option<&Value>) -> LogRotationSettings {
+    let field = |name: &str| value?.get(name).and_then(setting_value_to_raw);
+    LogRotationSettings {
+        desktop_max_bytes: field("desktopMaxBytes"),
+        backend_max_bytes: field("backendMaxBytes"),
+        backup_count: field("backupCount"),
+        compress_backups: field("compressBackups"),
+        max_age_days: field("maxAgeDays"),
+    }
+}
+
+fn log_levels_to_spec(value: &Value) -> Option<String> {
+    match value {
+        Value::String(spec) => Some(spec.clone()),
+        Value::Object(levels) => {
+            // Apply the default first so per-category entries override it.
+            let mut entries = levels
+                .iter()
+                .filter_map(|(scope, level)| Some((scope, level.as_str()?)))
+                .collect::<Vec<_>>();
+            entries.sort_by_key(|(scope, _)| !scope.eq_ignore_ascii_case("default"));
+            Some(
+                entries
+                    .into_iter()
+                    .map(|(scope, level)| format!("{scope}={level}"))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Reads `logFormat` / `logLevels` / `logRotation` without going through `load_state`, which logs
+/// (and may reset the file) on errors and therefore cannot run while the logger
+/// initializes.
+pub(crate) fn read_logging_settings(packaged_root_dir: Option<&Path>) -> LoggingSettings {
+    let parsed = crate::desktop_state::resolve_desktop_state_path(packaged_root_dir)
+        .and_then(|state_path| fs::read_to_string(state_path).ok())
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok());
+    let Some(parsed) = parsed else {
+        return LoggingSettings::default();
+    };
+
+    LoggingSettings {
+        format: parsed
+            .get("logFormat")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        level_spec: parsed.get("logLevels").and_then(log_levels_to_spec),
+        rotation: read_log_rotation_settings(parsed.get("logRotation")),
+    }
+}
+
+/// Raw `resourceLimits` values; bounds are validated by the backend resource monitor.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ResourceLimitSettings {
+    pub(crate) rss_warn_mb: Option<String>,
+    pub(crate) rss_restart_mb: Option<String>,
+    pub(crate) rss_restart_after_secs: Option<String>,
+}
+
+/// Reads a profile's own desktop state (see `resolve_profile_desktop_state_path`).
+pub(crate) fn read_resource_limit_settings(state_path: Option<&Path>) -> ResourceLimitSettings {
+    let parsed = state_path
+        .and_then(|state_path| fs::read_to_string(state_path).ok())
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok());
+    let value = parsed
+        .as_ref()
+        .and_then(|parsed| parsed.get("resourceLimits"));
+    let field = |name: &str| value?.get(name).and_then(setting_value_to_raw);
+    ResourceLimitSettings {
+        rss_warn_mb: field("rssWarnMb"),
+        rss_restart_mb: field("rssRestartMb"),
+        rss_restart_after_secs: field("rssRestartAfterSecs"),
+    }
+}
+
+/// Raw `readinessProbes` array from a profile's own desktop state; validated by
+/// the backend readiness probe chain.
+pub(crate) fn read_readiness_probe_settings(state_path: Option<&Path>) -> Option<Value> {
+    state_path
+        .and_then(|state_path| fs::read_to_string(state_path).ok())
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())?
+        .get("readinessProbes")
+        .cloned()
+}
+
 pub(crate) fn write_desktop_setting(
     packaged_root_dir: Option<&Path>,
     key: DesktopSettingKey,
@@ -363,6 +497,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn read_logging_settings_accepts_level_spec_string_or_object() {
+        let _root_guard = EnvVarGuard::clear(crate::ASTRBOT_ROOT_ENV);
+        let root = create_temp_case_dir("logging");
+        let path = state_path(&root);
+        fs::create_dir_all(path.parent().expect("state parent")).expect("create state parent");
+
+        fs::write(
+            &path,
+            r#"{"logFormat":"json","logLevels":{"startup":"debug","default":"warn"}}"#,
+        )
+        .expect("write state");
+        assert_eq!(
+            read_logging_settings(Some(&root)),
+            LoggingSettings {
+                format: Some("json".to_string()),
+                level_spec: Some("default=warn,startup=debug".to_string()),
+                ..LoggingSettings::default()
+            }
+        );
+
+        fs::write(&path, r#"{"logLevels":"restart=trace"}"#).expect("write state");
+        assert_eq!(
+            read_logging_settings(Some(&root)),
+            LoggingSettings {
+                format: None,
+                level_spec: Some("restart=trace".to_string()),
+                ..LoggingSettings::default()
+            }
+        );
+    }
+
+    #[test]
+    fn read_logging_settings_reads_rotation_values_as_raw_strings() {
+        let _root_guard = EnvVarGuard::clear(crate::ASTRBOT_ROOT_ENV);
+        let root = create_temp_case_dir("logging-rotation");
+        let path = state_path(&root);
+        fs::create_dir_all(path.parent().expect("state parent")).expect("create state parent");
+        fs::write(
+            &path,
+            r#"{"logRotation":{"backendMaxBytes":104857600,"backupCount":"10","compressBackups":true,"maxAgeDays":null}}"#,
+        )
+        .expect("write state");
+
+        assert_eq!(
+            read_logging_settings(Some(&root)).rotation,
+            LogRotationSettings {
+                desktop_max_bytes: None,
+                backend_max_bytes: Some("104857600".to_string()),
+                backup_count: Some("10".to_string()),
+                compress_backups: Some("true".to_string()),
+                max_age_days: None,
+            }
+        );
+    }
+
+    #[test]
+    fn read_resource_limit_settings_reads_values_as_raw_strings() {
+        let root = create_temp_case_dir("resource-limits");
+        let path = state_path(&root);
+        fs::create_dir_all(path.parent().expect("state parent")).expect("create state parent");
+        fs::write(
+            &path,
+            r#"{"resourceLimits":{"rssWarnMb":2048,"rssRestartMb":"4096"}}"#,
+        )
+        .expect("write state");
+
+        assert_eq!(
+            read_resource_limit_settings(Some(&path)),
+            ResourceLimitSettings {
+                rss_warn_mb: Some("2048".to_string()),
+                rss_restart_mb: Some("4096".to_string()),
+                rss_restart_after_secs: None,
+            }
+        );
+    }
+
     #[test]
     fn invalid_state_is_rewritten_to_defaults_on_read() {
         let _root_guard = EnvVarGuard::clear(crate::ASTRBOT_ROOT_ENV);
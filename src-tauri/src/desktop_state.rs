@@ -3,6 +3,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::profiles::config::DEFAULT_PROFILE_ID;
+
+const DESKTOP_STATE_RELATIVE_PATH: &str = "data/desktop_state.json";
+
 pub(crate) fn resolve_desktop_state_path(packaged_root_dir: Option<&Path>) -> Option<PathBuf> {
     resolve_desktop_state_path_with_root(
         env::var(crate::ASTRBOT_ROOT_ENV).ok().as_deref(),
@@ -17,21 +21,99 @@ pub(crate) fn resolve_desktop_state_path_with_root(
     if let Some(root_override) = root_override {
         let root_override = root_override.trim();
         if !root_override.is_empty() {
-            return Some(
-                PathBuf::from(root_override)
-                    .join("data")
-                    .join("desktop_state.json"),
-            );
+            return Some(PathBuf::from(root_override).join(DESKTOP_STATE_RELATIVE_PATH));
         }
     }
 
-    packaged_root_dir.map(|root| root.join("data").join("desktop_state.json"))
+    packaged_root_dir.map(|root| root.join(DESKTOP_STATE_RELATIVE_PATH))
+}
+
+/// The profile's own `desktop_state.json` under its root, which holds the
+/// backend-facing settings (`readinessProbes`, `resourceLimits`). The shell-wide
+/// file keeps the profile list, locale, update channel and tray toggles. The
+/// default profile keeps reading the shell-wide file until it has its own, so
+/// settings written before profiles existed still apply.
+pub(crate) fn resolve_profile_desktop_state_path(
+    profile_id: &str,
+    profile_root_dir: Option<&Path>,
+    packaged_root_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    resolve_profile_desktop_state_path_with_shell_state(profile_id, profile_root_dir, || {
+        resolve_desktop_state_path(packaged_root_dir)
+    })
+}
+
+fn resolve_profile_desktop_state_path_with_shell_state(
+    profile_id: &str,
+    profile_root_dir: Option<&Path>,
+    shell_state_path: impl FnOnce() -> Option<PathBuf>,
+) -> Option<PathBuf> {
+    let own = profile_root_dir.map(|root| root.join(DESKTOP_STATE_RELATIVE_PATH));
+    if profile_id != DEFAULT_PROFILE_ID {
+        return own;
+    }
+    match own {
+        Some(path) if path.is_file() => Some(path),
+        _ => shell_state_path(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_desktop_state_path_with_root;
-    use std::path::PathBuf;
+    use super::{
+        resolve_desktop_state_path_with_root, resolve_profile_desktop_state_path_with_shell_state,
+    };
+    use std::{fs, path::PathBuf};
+
+    #[test]
+    fn named_profiles_read_desktop_state_from_their_own_root() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let profile_root = temp.path().join("profiles").join("customer-a");
+
+        let shell_state = || Some(temp.path().join("data/desktop_state.json"));
+
+        assert_eq!(
+            resolve_profile_desktop_state_path_with_shell_state(
+                "customer-a",
+                Some(profile_root.as_path()),
+                shell_state
+            ),
+            Some(profile_root.join("data/desktop_state.json"))
+        );
+        assert_eq!(
+            resolve_profile_desktop_state_path_with_shell_state("customer-a", None, shell_state),
+            None
+        );
+    }
+
+    #[test]
+    fn default_profile_falls_back_to_shell_desktop_state_until_it_has_its_own() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let packaged_root = temp.path().join("packaged");
+        let profile_root = temp.path().join("default-root");
+        let shell_state = packaged_root.join("data/desktop_state.json");
+        let own_state = profile_root.join("data/desktop_state.json");
+
+        assert_eq!(
+            resolve_profile_desktop_state_path_with_shell_state(
+                "default",
+                Some(profile_root.as_path()),
+                || Some(shell_state.clone())
+            ),
+            Some(shell_state.clone())
+        );
+
+        fs::create_dir_all(own_state.parent().expect("state parent")).expect("create parent");
+        fs::write(&own_state, "{}").expect("write state");
+        assert_eq!(
+            resolve_profile_desktop_state_path_with_shell_state(
+                "default",
+                Some(profile_root.as_path()),
+                || Some(shell_state.clone())
+            ),
+            Some(own_state)
+        );
+    }
 
     #[test]
     fn astrbot_root_overrides_packaged_root_fallback_for_desktop_state_path() {
@@ -0,0 +1,580 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+
+use crate::{
+    append_desktop_log, backend, backend::host::BackendHost, backend_log_rotation_policy,
+    build_debug_command, desktop_log_rotation_policy, launch_plan, logging, runtime_paths,
+    BackendState, LaunchPlan, DESKTOP_LOG_FILE,
+};
+
+const DIAGNOSTIC_BUNDLE_PREFIX: &str = "astrbot-diagnostics";
+const REDACTED_PLACEHOLDER: &str = "[REDACTED]";
+const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "apikey",
+    "api_key",
+    "access_key",
+    "authorization",
+    "cookie",
+    "credential",
+];
+const SENSITIVE_KEY_NAMES: &[&str] = &["key", "keys"];
+const HONOURED_ENV_VARS: &[&str] = &[
+    "ASTRBOT_BACKEND_URL",
+    "ASTRBOT_BACKEND_AUTO_START",
+    "ASTRBOT_BACKEND_TIMEOUT_MS",
+    "ASTRBOT_BACKEND_STARTUP_IDLE_TIMEOUT_MS",
+    "ASTRBOT_BACKEND_READY_HTTP_PATH",
+    "ASTRBOT_BACKEND_READY_PROBE_TIMEOUT_MS",
+    "ASTRBOT_BACKEND_READY_POLL_INTERVAL_MS",
+    "ASTRBOT_BACKEND_LIVENESS_INTERVAL_MS",
+    "ASTRBOT_BACKEND_LIVENESS_FAILURE_THRESHOLD",
+    "ASTRBOT_BACKEND_LIVENESS_HTTP_PATH",
+    "ASTRBOT_BACKEND_PING_TIMEOUT_MS",
+    "ASTRBOT_BRIDGE_BACKEND_PING_TIMEOUT_MS",
+    "ASTRBOT_BACKEND_CMD",
+    "ASTRBOT_BACKEND_CWD",
+    "ASTRBOT_WEBUI_DIR",
+    "ASTRBOT_ROOT",
+    "ASTRBOT_SOURCE_DIR",
+    "ASTRBOT_DASHBOARD_HOST",
+    "ASTRBOT_DASHBOARD_PORT",
+    "DASHBOARD_HOST",
+    "DASHBOARD_PORT",
+    "ASTRBOT_DESKTOP_EXTRA_PATH",
+    "ASTRBOT_DESKTOP_LOCALE",
+    "ASTRBOT_DESKTOP_PROFILE",
+    "ASTRBOT_DESKTOP_LOG_PATH",
+    "ASTRBOT_DESKTOP_LOG_FORMAT",
+    "ASTRBOT_DESKTOP_LOG_LEVEL",
+    "ASTRBOT_DESKTOP_LOG_MAX_BYTES",
+    "ASTRBOT_BACKEND_LOG_MAX_BYTES",
+    "ASTRBOT_LOG_BACKUP_COUNT",
+    "ASTRBOT_LOG_COMPRESS_BACKUPS",
+    "ASTRBOT_LOG_MAX_AGE_DAYS",
+    "ASTRBOT_DESKTOP_MANUAL_DOWNLOAD_URL",
+    "ASTRBOT_DESKTOP_STARTUP_MODE",
+    "ASTRBOT_DESKTOP_UPDATER_STABLE_ENDPOINT",
+    "ASTRBOT_DESKTOP_UPDATER_NIGHTLY_ENDPOINT",
+    "ASTRBOT_DESKTOP_SESSION_SECRET",
+    "PYTHONIOENCODING",
+    "WAYLAND_DISPLAY",
+    "WEBKIT_DISABLE_DMABUF_RENDERER",
+];
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiagnosticBundleResult {
+    pub(crate) ok: bool,
+    pub(crate) path: Option<String>,
+    pub(crate) reason: Option<String>,
+}
+
+impl DiagnosticBundleResult {
+    pub(crate) fn from_export(result: Result<PathBuf, String>) -> Self {
+        match result {
+            Ok(path) => Self {
+                ok: true,
+                path: Some(path.display().to_string()),
+                reason: None,
+            },
+            Err(reason) => Self {
+                ok: false,
+                path: None,
+                reason: Some(reason),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiagnosticBundleEntry {
+    name: String,
+    contents: Vec<u8>,
+}
+
+impl DiagnosticBundleEntry {
+    fn text(name: impl Into<String>, text: String) -> Self {
+        Self {
+            name: name.into(),
+            contents: text.into_bytes(),
+        }
+    }
+
+    fn json(name: impl Into<String>, value: &Value) -> Self {
+        let text = serde_json::to_string_pretty(value)
+            .unwrap_or_else(|error| format!("failed to serialize: {error}"));
+        Self::text(name, text)
+    }
+}
+
+/// Replaces every known secret value with a placeholder.
+struct SecretRedactor {
+    secrets: Vec<String>,
+}
+
+impl SecretRedactor {
+    fn new(secrets: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            secrets: secrets
+                .into_iter()
+                .filter(|secret| !secret.trim().is_empty())
+                .collect(),
+        }
+    }
+
+    fn redact_text(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        for secret in &self.secrets {
+            redacted = redacted.replace(secret.as_str(), REDACTED_PLACEHOLDER);
+        }
+        redacted
+    }
+
+    fn redact_file(&self, path: &Path) -> Option<String> {
+        let bytes = logging::read_log_file(path).ok()?;
+        Some(self.redact_text(&String::from_utf8_lossy(&bytes)))
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let lowered = key.trim().to_ascii_lowercase();
+    SENSITIVE_KEY_NAMES.contains(&lowered.as_str())
+        || SENSITIVE_KEY_FRAGMENTS
+            .iter()
+            .any(|fragment| lowered.contains(fragment))
+}
+
+fn redact_sensitive_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if is_sensitive_key(key) && !child.is_null() {
+                    *child = Value::String(REDACTED_PLACEHOLDER.to_string());
+                } else {
+                    redact_sensitive_json(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_sensitive_json),
+        _ => {}
+    }
+}
+
+fn redacted_json_file_entry(
+    name: &str,
+    path: &Path,
+    redactor: &SecretRedactor,
+) -> Option<DiagnosticBundleEntry> {
+    let raw = redactor.redact_file(path)?;
+    match serde_json::from_str::<Value>(&raw) {
+        Ok(mut value) => {
+            redact_sensitive_json(&mut value);
+            Some(DiagnosticBundleEntry::json(name, &value))
+        }
+        // Unparsable JSON cannot be redacted by key, so only record why it was skipped.
+        Err(error) => Some(DiagnosticBundleEntry::text(
+            name,
+            format!("failed to parse {}: {error}", path.display()),
+        )),
+    }
+}
+
+fn collect_log_entries(
+    entry_prefix: &str,
+    path: &Path,
+    backup_count: usize,
+    redactor: &SecretRedactor,
+    entries: &mut Vec<DiagnosticBundleEntry>,
+) {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("{entry_prefix}.log"));
+    if let Some(text) = redactor.redact_file(path) {
+        entries.push(DiagnosticBundleEntry::text(
+            format!("logs/{entry_prefix}/{file_name}"),
+            text,
+        ));
+    }
+    for index in 1..=backup_count {
+        let Some(rotated) = logging::existing_rotated_log_path(path, index) else {
+            continue;
+        };
+        if let Some(text) = redactor.redact_file(&rotated) {
+            entries.push(DiagnosticBundleEntry::text(
+                format!("logs/{entry_prefix}/{file_name}.{index}"),
+                text,
+            ));
+        }
+    }
+}
+
+fn collect_environment<F>(read_var: F) -> Value
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut values = Map::new();
+    for name in HONOURED_ENV_VARS {
+        let Some(value) = read_var(name) else {
+            continue;
+        };
+        let value = if is_sensitive_key(name) {
+            REDACTED_PLACEHOLDER.to_string()
+        } else {
+            value
+        };
+        values.insert((*name).to_string(), Value::String(value));
+    }
+    Value::Object(values)
+}
+
+fn resolve_diagnostic_bundle_dir(
+    root_dir: Option<&Path>,
+    packaged_root: Option<PathBuf>,
+) -> PathBuf {
+    if let Some(root) = root_dir {
+        return root.to_path_buf();
+    }
+    if let Ok(root) = env::var(crate::ASTRBOT_ROOT_ENV) {
+        let path = PathBuf::from(root.trim());
+        if !path.as_os_str().is_empty() {
+            return path;
+        }
+    }
+    packaged_root.unwrap_or_else(|| env::temp_dir().join("astrbot"))
+}
+
+fn write_diagnostic_bundle(
+    output_path: &Path,
+    entries: &[DiagnosticBundleEntry],
+) -> io::Result<()> {
+    let bundle_dir = output_path
+        .file_stem()
+        .and_then(|stem| Path::new(stem).file_stem())
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| DIAGNOSTIC_BUNDLE_PREFIX.to_string());
+    let mtime = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let file = File::create(output_path)?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(entry.contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(
+            &mut header,
+            format!("{bundle_dir}/{}", entry.name),
+            entry.contents.as_slice(),
+        )?;
+    }
+    builder.into_inner()?.finish()?.flush()
+}
+
+/// Launch plan fields shared by the diagnostic bundle and `--print-launch-plan`.
+pub(crate) fn launch_plan_summary(plan: &LaunchPlan, redact: impl Fn(&str) -> String) -> Value {
+    let command = build_debug_command(plan)
+        .iter()
+        .map(|part| redact(part))
+        .collect::<Vec<_>>();
+    json!({
+        "command": command,
+        "cwd": plan.cwd,
+        "rootDir": plan.root_dir,
+        "webuiDir": plan.webui_dir,
+        "startupHeartbeatPath": plan.startup_heartbeat_path,
+        "packagedMode": plan.packaged_mode,
+    })
+}
+
+impl BackendState {
+    fn collect_diagnostic_entries(
+        &self,
+        app: &AppHandle,
+        packaged_root_dir: Option<PathBuf>,
+    ) -> (Vec<DiagnosticBundleEntry>, PathBuf) {
+        let mut secrets = vec![self.desktop_session_secret.as_str().to_string()];
+        if let Ok(guard) = self.restart_auth_token.lock() {
+            secrets.extend(guard.clone());
+        }
+        let redactor = SecretRedactor::new(secrets);
+        let mut entries = Vec::new();
+
+        let plan = self.resolve_launch_plan(app);
+        let root_dir = plan.as_ref().ok().and_then(|plan| plan.root_dir.clone());
+        let launch_plan_value = match &plan {
+            Ok(plan) => {
+                let readiness = backend::runtime::backend_readiness_config(
+                    plan,
+                    &self.profile_id,
+                    append_desktop_log,
+                );
+                entries.push(DiagnosticBundleEntry::json(
+                    "readiness-config.json",
+                    &serde_json::to_value(&readiness).unwrap_or(Value::Null),
+                ));
+                if let Some(heartbeat_path) = &plan.startup_heartbeat_path {
+                    if let Some(text) = redactor.redact_file(heartbeat_path) {
+                        entries.push(DiagnosticBundleEntry::text("startup-heartbeat.json", text));
+                    }
+                }
+                launch_plan_summary(plan, |part| redactor.redact_text(part))
+            }
+            Err(error) => json!({ "error": error }),
+        };
+        entries.push(DiagnosticBundleEntry::json(
+            "launch-plan.json",
+            &launch_plan_value,
+        ));
+
+        if let Some(manifest_path) = launch_plan::resolve_packaged_manifest_path(
+            app.resource_dir().as_deref(),
+            append_desktop_log,
+        ) {
+            if let Some(entry) =
+                redacted_json_file_entry("runtime-manifest.json", &manifest_path, &redactor)
+            {
+                entries.push(entry);
+            }
+        }
+
+        let shell_state_path =
+            crate::desktop_state::resolve_desktop_state_path(packaged_root_dir.as_deref());
+        if let Some(state_path) = &shell_state_path {
+            if let Some(entry) =
+                redacted_json_file_entry("data/desktop_state.json", state_path, &redactor)
+            {
+                entries.push(entry);
+            }
+        }
+        let profile_state_path = crate::desktop_state::resolve_profile_desktop_state_path(
+            &self.profile_id,
+            root_dir.as_deref(),
+            packaged_root_dir.as_deref(),
+        );
+        if let Some(state_path) =
+            profile_state_path.filter(|path| Some(path) != shell_state_path.as_ref())
+        {
+            if let Some(entry) =
+                redacted_json_file_entry("profile/desktop_state.json", &state_path, &redactor)
+            {
+                entries.push(entry);
+            }
+        }
+
+        let bundle_dir =
+            resolve_diagnostic_bundle_dir(root_dir.as_deref(), packaged_root_dir.clone());
+        let cmd_config_path = bundle_dir.join("data").join("cmd_config.json");
+        if let Some(entry) =
+            redacted_json_file_entry("data/cmd_config.json", &cmd_config_path, &redactor)
+        {
+            entries.push(entry);
+        }
+
+        collect_log_entries(
+            "desktop",
+            &logging::resolve_desktop_log_path(packaged_root_dir.clone(), DESKTOP_LOG_FILE),
+            desktop_log_rotation_policy().backup_count,
+            &redactor,
+            &mut entries,
+        );
+        collect_log_entries(
+            "backend",
+            &logging::resolve_backend_log_path(root_dir.as_deref(), packaged_root_dir),
+            backend_log_rotation_policy().backup_count,
+            &redactor,
+            &mut entries,
+        );
+
+        entries.push(DiagnosticBundleEntry::json(
+            "environment.json",
+            &collect_environment(|name| env::var(name).ok()),
+        ));
+        entries.push(DiagnosticBundleEntry::json(
+            "summary.json",
+            &json!({
+                "appVersion": app.package_info().version.to_string(),
+                "os": env::consts::OS,
+                "arch": env::consts::ARCH,
+                "createdAt": chrono::Local::now().to_rfc3339(),
+                "profile": self.profile_id,
+                "backendUrl": redactor.redact_text(&self.backend_url()),
+                "backendState": self.bridge_state(app),
+            }),
+        ));
+
+        (entries, bundle_dir)
+    }
+
+    pub(crate) fn export_diagnostic_bundle(&self, app: &AppHandle) -> Result<PathBuf, String> {
+        let packaged_root_dir = runtime_paths::default_packaged_root_dir();
+        let (entries, bundle_dir) = self.collect_diagnostic_entries(app, packaged_root_dir);
+        fs::create_dir_all(&bundle_dir).map_err(|error| {
+            format!(
+                "Failed to create diagnostic bundle directory {}: {}",
+                bundle_dir.display(),
+                error
+            )
+        })?;
+
+        let output_path = bundle_dir.join(format!(
+            "{DIAGNOSTIC_BUNDLE_PREFIX}-{}.tar.gz",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        if let Err(error) = write_diagnostic_bundle(&output_path, &entries) {
+            let _ = fs::remove_file(&output_path);
+            return Err(format!(
+                "Failed to write diagnostic bundle {}: {}",
+                output_path.display(),
+                error
+            ));
+        }
+        append_desktop_log(&format!(
+            "diagnostic bundle exported: {} ({} entries)",
+            output_path.display(),
+            entries.len()
+        ));
+        Ok(output_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fs::File, io::Read};
+
+    use flate2::read::GzDecoder;
+    use serde_json::json;
+
+    use super::{
+        collect_environment, is_sensitive_key, redact_sensitive_json, write_diagnostic_bundle,
+        DiagnosticBundleEntry, SecretRedactor, REDACTED_PLACEHOLDER,
+    };
+
+    #[test]
+    fn is_sensitive_key_matches_credentials_but_not_plain_settings() {
+        for key in [
+            "jwt_secret",
+            "password",
+            "access_token",
+            "ASTRBOT_DESKTOP_SESSION_SECRET",
+            "key",
+            "api_key",
+        ] {
+            assert!(is_sensitive_key(key), "{key} should be sensitive");
+        }
+        for key in ["host", "port", "keyword", "provider_id"] {
+            assert!(!is_sensitive_key(key), "{key} should not be sensitive");
+        }
+    }
+
+    #[test]
+    fn redact_sensitive_json_replaces_nested_credentials() {
+        let mut value = json!({
+            "dashboard": { "username": "astrbot", "password": "hash", "jwt_secret": "abc" },
+            "provider": [{ "id": "openai", "key": ["sk-1", "sk-2"], "api_base": "https://x" }],
+            "token": null,
+        });
+
+        redact_sensitive_json(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "dashboard": {
+                    "username": "astrbot",
+                    "password": REDACTED_PLACEHOLDER,
+                    "jwt_secret": REDACTED_PLACEHOLDER,
+                },
+                "provider": [{
+                    "id": "openai",
+                    "key": REDACTED_PLACEHOLDER,
+                    "api_base": "https://x",
+                }],
+                "token": null,
+            })
+        );
+    }
+
+    #[test]
+    fn secret_redactor_replaces_known_values_and_ignores_blank_ones() {
+        let redactor = SecretRedactor::new(["s3cr3t".to_string(), " ".to_string()]);
+
+        assert_eq!(
+            redactor.redact_text("header=s3cr3t other=s3cr3t"),
+            format!("header={REDACTED_PLACEHOLDER} other={REDACTED_PLACEHOLDER}")
+        );
+        assert_eq!(redactor.redact_text("a b"), "a b");
+    }
+
+    #[test]
+    fn collect_environment_keeps_set_vars_and_redacts_secrets() {
+        let environment = collect_environment(|name| match name {
+            "ASTRBOT_ROOT" => Some("/srv/astrbot".to_string()),
+            "ASTRBOT_DESKTOP_SESSION_SECRET" => Some("abc".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(
+            environment,
+            json!({
+                "ASTRBOT_ROOT": "/srv/astrbot",
+                "ASTRBOT_DESKTOP_SESSION_SECRET": REDACTED_PLACEHOLDER,
+            })
+        );
+    }
+
+    #[test]
+    fn write_diagnostic_bundle_writes_entries_under_bundle_directory() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let output_path = temp
+            .path()
+            .join("astrbot-diagnostics-20260101-000000.tar.gz");
+        let entries = vec![
+            DiagnosticBundleEntry::text("summary.json", "{}".to_string()),
+            DiagnosticBundleEntry::text("logs/desktop/desktop.log", "line\n".to_string()),
+        ];
+
+        write_diagnostic_bundle(&output_path, &entries).expect("write bundle");
+
+        let file = File::open(&output_path).expect("open bundle");
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        let mut contents = BTreeMap::new();
+        for entry in archive.entries().expect("read entries") {
+            let mut entry = entry.expect("read entry");
+            let path = entry.path().expect("entry path").display().to_string();
+            let mut text = String::new();
+            entry.read_to_string(&mut text).expect("read entry text");
+            contents.insert(path, text);
+        }
+        assert_eq!(
+            contents,
+            BTreeMap::from([
+                (
+                    "astrbot-diagnostics-20260101-000000/logs/desktop/desktop.log".to_string(),
+                    "line\n".to_string()
+                ),
+                (
+                    "astrbot-diagnostics-20260101-000000/summary.json".to_string(),
+                    "{}".to_string()
+                ),
+            ])
+        );
+    }
+}
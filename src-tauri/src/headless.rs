@@ -0,0 +1,191 @@
+use crate::logging;
+
+/// `--headless` reaches this through the env override applied by the CLI.
+pub(crate) fn headless_requested(env_value: Option<&str>) -> bool {
+    env_value.and_then(logging::parse_log_flag).unwrap_or(false)
+}
+
+#[cfg(unix)]
+mod daemon {
+    use std::{
+        path::PathBuf,
+        sync::{mpsc, Arc},
+        thread,
+    };
+
+    use tauri::PackageInfo;
+
+    use crate::{
+        append_shutdown_log, append_startup_log, append_startup_log_at,
+        backend::{
+            host::BackendHost, port::PortRelocation, startup_cancel::BACKEND_STARTUP_CANCELLED,
+        },
+        instance_lock,
+        lifecycle::cleanup::{self, ExitTrigger},
+        logging::DesktopLogLevel,
+        profiles::{self, registry::ProfileRegistry},
+        runtime_paths, unix_signals, BackendState,
+    };
+
+    #[derive(Clone)]
+    struct HeadlessHost {
+        registry: Arc<ProfileRegistry>,
+        resource_dir: Option<PathBuf>,
+        events: mpsc::Sender<HeadlessEvent>,
+    }
+
+    impl BackendHost for HeadlessHost {
+        fn profile_state(&self, profile_id: &str) -> Option<Arc<BackendState>> {
+            self.registry.state(profile_id)
+        }
+
+        fn resource_dir(&self) -> Option<PathBuf> {
+            self.resource_dir.clone()
+        }
+
+        // No tray to refresh; status changes and port moves are already in desktop.log.
+        fn backend_status_changed(&self) {}
+
+        fn dashboard_port_relocated(&self, _relocation: PortRelocation) {}
+
+        fn backend_supervision_gave_up(&self, profile_id: &str) {
+            if profile_id == self.registry.active_profile().id {
+                let _ = self.events.send(HeadlessEvent::SupervisionGaveUp);
+            }
+        }
+    }
+
+    enum HeadlessEvent {
+        Signal(String),
+        StartupFailed,
+        SupervisionGaveUp,
+    }
+
+    fn spawn_backend_startup(
+        host: HeadlessHost,
+        profile_id: String,
+        failures: Option<mpsc::Sender<HeadlessEvent>>,
+    ) {
+        thread::spawn(move || {
+            let Some(state) = host.profile_state(&profile_id) else {
+                return;
+            };
+            match state.ensure_backend_ready(&host) {
+                Ok(()) => append_startup_log(&format!(
+                    "backend for profile '{profile_id}' is ready at {}",
+                    state.backend_url()
+                )),
+                Err(error) if error == BACKEND_STARTUP_CANCELLED => append_startup_log(&error),
+                Err(error) => {
+                    append_startup_log_at(
+                        DesktopLogLevel::Error,
+                        &format!("failed to start backend for profile '{profile_id}': {error}"),
+                    );
+                    if let Some(failures) = failures {
+                        let _ = failures.send(HeadlessEvent::StartupFailed);
+                    }
+                }
+            }
+        });
+    }
+
+    fn stop_backends(host: &HeadlessHost, trigger: ExitTrigger) {
+        let state = host.registry.active_state();
+        state.mark_quitting();
+        if !cleanup::try_begin_exit_cleanup(&state, trigger, append_shutdown_log) {
+            return;
+        }
+        cleanup::stop_backends_for_exit(
+            host,
+            false,
+            host.registry.states(),
+            trigger,
+            append_shutdown_log,
+        );
+        append_shutdown_log("headless backend stop finished, exiting");
+    }
+
+    /// Runs the backends without webview or tray until a signal arrives, then
+    /// stops them and returns the process exit code. Startup failure or a crash
+    /// loop of the active profile exits non-zero so a service manager restarts it.
+    pub(crate) fn run(package_info: &PackageInfo) -> i32 {
+        if let Err(error) = instance_lock::acquire_instance_lock(|message| {
+            append_startup_log_at(DesktopLogLevel::Warn, message)
+        }) {
+            let error = format!("refusing to start headless mode: {error}");
+            append_startup_log_at(DesktopLogLevel::Error, &error);
+            eprintln!("{error}");
+            return 1;
+        }
+        append_startup_log("headless mode enabled, skipping webview and tray");
+        let (events, receiver) = mpsc::channel();
+        let host = HeadlessHost {
+            registry: Arc::new(profiles::runtime::load_profile_registry()),
+            resource_dir: runtime_paths::packaged_resource_dir(package_info),
+            events: events.clone(),
+        };
+
+        let signal_events = events.clone();
+        unix_signals::install_handler(move |name| {
+            let _ = signal_events.send(HeadlessEvent::Signal(name));
+        });
+
+        let active_profile_id = host.registry.active_profile().id.clone();
+        for profile in host.registry.profiles() {
+            let is_active = profile.id == active_profile_id;
+            if !is_active && !profile.auto_start {
+                continue;
+            }
+            // Only the active profile decides whether the daemon keeps running.
+            let failures = is_active.then(|| events.clone());
+            spawn_backend_startup(host.clone(), profile.id.clone(), failures);
+        }
+        drop(events);
+
+        match receiver.recv() {
+            Ok(HeadlessEvent::Signal(name)) => {
+                append_shutdown_log(&format!("headless daemon stopping after {name}"));
+                stop_backends(&host, ExitTrigger::Signal);
+                0
+            }
+            Ok(HeadlessEvent::SupervisionGaveUp) => {
+                append_shutdown_log(
+                    "headless daemon stopping after the backend supervisor gave up restarting",
+                );
+                stop_backends(&host, ExitTrigger::ExitFallback);
+                1
+            }
+            Ok(HeadlessEvent::StartupFailed) | Err(_) => {
+                append_shutdown_log("headless daemon stopping after backend startup failure");
+                stop_backends(&host, ExitTrigger::ExitFallback);
+                1
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use daemon::run;
+
+#[cfg(not(unix))]
+pub(crate) fn run(_package_info: &tauri::PackageInfo) -> i32 {
+    crate::append_startup_log_at(
+        crate::logging::DesktopLogLevel::Error,
+        "headless mode is only supported on Unix",
+    );
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::headless_requested;
+
+    #[test]
+    fn headless_is_selected_by_env_flag() {
+        assert!(headless_requested(Some("1")));
+        assert!(headless_requested(Some(" true ")));
+        assert!(!headless_requested(None));
+        assert!(!headless_requested(Some("0")));
+        assert!(!headless_requested(Some("maybe")));
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    env,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{runtime_paths, ASTRBOT_ROOT_ENV, DESKTOP_INSTANCE_LOCK_RELATIVE_PATH};
+
+// Held for the rest of the process; the OS releases the lock when it exits.
+static INSTANCE_LOCK: OnceLock<File> = OnceLock::new();
+
+fn resolve_instance_lock_path(root_override: Option<PathBuf>) -> Option<PathBuf> {
+    root_override
+        .or_else(runtime_paths::default_packaged_root_dir)
+        .map(|root| root.join(DESKTOP_INSTANCE_LOCK_RELATIVE_PATH))
+}
+
+#[cfg(unix)]
+fn try_lock_file(path: &Path) -> io::Result<Option<File>> {
+    use std::os::fd::AsRawFd;
+
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(Some(file));
+    }
+    let error = io::Error::last_os_error();
+    if error.raw_os_error() == Some(libc::EWOULDBLOCK) {
+        return Ok(None);
+    }
+    Err(error)
+}
+
+#[cfg(windows)]
+fn try_lock_file(path: &Path) -> io::Result<Option<File>> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    match fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .share_mode(0)
+        .open(path)
+    {
+        Ok(file) => Ok(Some(file)),
+        Err(error) if error.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Takes the exclusive lock for the AstrBot root (`ASTRBOT_ROOT` or the packaged
+/// root), so a desktop shell and a headless daemon never manage the same root at
+/// once. Only a lock held by another process is an error; if the lockfile cannot
+/// be created, startup continues unguarded.
+pub(crate) fn acquire_instance_lock<F>(log: F) -> Result<(), String>
+where
+    F: Fn(&str),
+{
+    let root_override = env::var(ASTRBOT_ROOT_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let Some(path) = resolve_instance_lock_path(root_override) else {
+        log("cannot resolve AstrBot root, skip instance lock");
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        if let Err(error) = fs::create_dir_all(parent) {
+            log(&format!(
+                "failed to create instance lock directory {}: {error}",
+                parent.display()
+            ));
+            return Ok(());
+        }
+    }
+
+    match try_lock_file(&path) {
+        Ok(Some(file)) => {
+            let _ = INSTANCE_LOCK.set(file);
+            Ok(())
+        }
+        Ok(None) => Err(format!(
+            "another AstrBot desktop instance is already running for this root (lock: {})",
+            path.display()
+        )),
+        Err(error) => {
+            log(&format!(
+                "failed to lock instance file {}: {error}",
+                path.display()
+            ));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{resolve_instance_lock_path, try_lock_file};
+
+    #[test]
+    fn resolve_instance_lock_path_prefers_root_override() {
+        assert_eq!(
+            resolve_instance_lock_path(Some(PathBuf::from("/srv/astrbot"))),
+            Some(PathBuf::from("/srv/astrbot").join("data/desktop-instance.lock"))
+        );
+    }
+
+    #[test]
+    fn try_lock_file_refuses_a_second_holder_until_released() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("desktop-instance.lock");
+
+        let first = try_lock_file(&path)
+            .expect("lock file")
+            .expect("first lock");
+        assert!(try_lock_file(&path).expect("lock file").is_none());
+
+        drop(first);
+        assert!(try_lock_file(&path).expect("lock file").is_some());
+    }
+}
@@ -3,13 +3,35 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use tauri::AppHandle;
-
 use crate::{backend, packaged_webui, runtime_paths, LaunchPlan, RuntimeManifest};
 
 const BACKEND_RESOURCE_ALIAS: &str = env!("ASTRBOT_BACKEND_RESOURCE_ALIAS");
 const WEBUI_RESOURCE_ALIAS: &str = env!("ASTRBOT_WEBUI_RESOURCE_ALIAS");
 
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LaunchOverrides {
+    pub(crate) backend_cmd: Option<String>,
+    pub(crate) backend_cwd: Option<PathBuf>,
+    pub(crate) root_dir: Option<PathBuf>,
+    pub(crate) webui_dir: Option<PathBuf>,
+    pub(crate) dashboard_port: Option<u16>,
+}
+
+impl LaunchOverrides {
+    pub(crate) fn from_env() -> Self {
+        Self {
+            backend_cmd: env::var(crate::BACKEND_CMD_ENV)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty()),
+            backend_cwd: env::var("ASTRBOT_BACKEND_CWD").ok().map(PathBuf::from),
+            root_dir: env::var(crate::ASTRBOT_ROOT_ENV).ok().map(PathBuf::from),
+            webui_dir: env::var("ASTRBOT_WEBUI_DIR").ok().map(PathBuf::from),
+            dashboard_port: None,
+        }
+    }
+}
+
 fn build_packaged_resource_relative_path(resource_alias: &str, leaf_name: &str) -> PathBuf {
     PathBuf::from(resource_alias).join(leaf_name)
 }
@@ -27,7 +49,10 @@ fn resolve_launch_startup_heartbeat_path(
     )
 }
 
-pub fn resolve_custom_launch(custom_cmd: String) -> Result<LaunchPlan, String> {
+pub fn resolve_custom_launch(
+    custom_cmd: String,
+    overrides: &LaunchOverrides,
+) -> Result<LaunchPlan, String> {
     let mut pieces = shlex::split(&custom_cmd)
         .ok_or_else(|| format!("Invalid ASTRBOT_BACKEND_CMD: {custom_cmd}"))?;
     if pieces.is_empty() {
@@ -35,13 +60,12 @@ pub fn resolve_custom_launch(custom_cmd: String) -> Result<LaunchPlan, String> {
     }
 
     let cmd = pieces.remove(0);
-    let cwd = env::var("ASTRBOT_BACKEND_CWD")
-        .map(PathBuf::from)
-        .ok()
+    let cwd = overrides
+        .backend_cwd
+        .clone()
         .or_else(runtime_paths::detect_astrbot_source_root)
         .unwrap_or_else(runtime_paths::workspace_root_dir);
-    let root_dir = env::var(crate::ASTRBOT_ROOT_ENV).ok().map(PathBuf::from);
-    let webui_dir = env::var("ASTRBOT_WEBUI_DIR").ok().map(PathBuf::from);
+    let root_dir = overrides.root_dir.clone();
     let startup_heartbeat_path = resolve_launch_startup_heartbeat_path(root_dir.as_deref(), false);
 
     Ok(LaunchPlan {
@@ -49,28 +73,37 @@ pub fn resolve_custom_launch(custom_cmd: String) -> Result<LaunchPlan, String> {
         args: pieces,
         cwd,
         root_dir,
-        webui_dir,
+        webui_dir: overrides.webui_dir.clone(),
         startup_heartbeat_path,
+        dashboard_port: overrides.dashboard_port,
+        runtime_dir: None,
         packaged_mode: false,
     })
 }
 
+pub fn resolve_packaged_manifest_path<F>(resource_dir: Option<&Path>, log: F) -> Option<PathBuf>
+where
+    F: Fn(&str),
+{
+    let manifest_relative_path =
+        build_packaged_resource_relative_path(BACKEND_RESOURCE_ALIAS, "runtime-manifest.json");
+    let manifest_relative_path_string = manifest_relative_path.to_string_lossy().to_string();
+    runtime_paths::resolve_resource_path(resource_dir, &manifest_relative_path_string, log)
+        .filter(|path| path.is_file())
+}
+
 pub fn resolve_packaged_launch<F>(
-    app: &AppHandle,
+    resource_dir: Option<&Path>,
     default_shell_locale: &'static str,
+    overrides: &LaunchOverrides,
     log: F,
 ) -> Result<Option<LaunchPlan>, String>
 where
     F: Fn(&str) + Copy,
 {
-    let manifest_relative_path =
-        build_packaged_resource_relative_path(BACKEND_RESOURCE_ALIAS, "runtime-manifest.json");
-    let manifest_relative_path_string = manifest_relative_path.to_string_lossy().to_string();
-    let manifest_path =
-        match runtime_paths::resolve_resource_path(app, &manifest_relative_path_string, log) {
-            Some(path) if path.is_file() => path,
-            _ => return Ok(None),
-        };
+    let Some(manifest_path) = resolve_packaged_manifest_path(resource_dir, log) else {
+        return Ok(None);
+    };
     let backend_dir = manifest_path
         .parent()
         .ok_or_else(|| format!("Invalid backend manifest path: {}", manifest_path.display()))?;
@@ -122,28 +155,23 @@ where
         ));
     }
 
-    let root_dir = env::var(crate::ASTRBOT_ROOT_ENV)
-        .map(PathBuf::from)
-        .ok()
+    let root_dir = overrides
+        .root_dir
+        .clone()
         .or_else(runtime_paths::default_packaged_root_dir);
-    let cwd = env::var("ASTRBOT_BACKEND_CWD")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| {
-            root_dir
-                .clone()
-                .unwrap_or_else(|| backend_dir.to_path_buf())
-        });
-    let embedded_webui_dir = env::var("ASTRBOT_WEBUI_DIR")
-        .ok()
-        .map(PathBuf::from)
-        .or_else(|| {
-            let webui_index_relative_path =
-                build_packaged_resource_relative_path(WEBUI_RESOURCE_ALIAS, "index.html");
-            let webui_index_relative_path_string =
-                webui_index_relative_path.to_string_lossy().to_string();
-            runtime_paths::resolve_resource_path(app, &webui_index_relative_path_string, log)
-                .and_then(|index_path| index_path.parent().map(Path::to_path_buf))
-        });
+    let cwd = overrides.backend_cwd.clone().unwrap_or_else(|| {
+        root_dir
+            .clone()
+            .unwrap_or_else(|| backend_dir.to_path_buf())
+    });
+    let embedded_webui_dir = overrides.webui_dir.clone().or_else(|| {
+        let webui_index_relative_path =
+            build_packaged_resource_relative_path(WEBUI_RESOURCE_ALIAS, "index.html");
+        let webui_index_relative_path_string =
+            webui_index_relative_path.to_string_lossy().to_string();
+        runtime_paths::resolve_resource_path(resource_dir, &webui_index_relative_path_string, log)
+            .and_then(|index_path| index_path.parent().map(Path::to_path_buf))
+    });
     let webui_dir = packaged_webui::resolve_packaged_webui_dir(
         embedded_webui_dir,
         root_dir.as_deref(),
@@ -165,44 +193,43 @@ where
         root_dir,
         webui_dir: Some(webui_dir),
         startup_heartbeat_path,
+        dashboard_port: overrides.dashboard_port,
+        runtime_dir: Some(backend_dir.to_path_buf()),
         packaged_mode: true,
     };
     Ok(Some(plan))
 }
 
-pub fn resolve_dev_launch() -> Result<LaunchPlan, String> {
+pub fn resolve_dev_launch(overrides: &LaunchOverrides) -> Result<LaunchPlan, String> {
     let source_root = runtime_paths::detect_astrbot_source_root().ok_or_else(|| {
         "Cannot locate AstrBot source directory. Set ASTRBOT_SOURCE_DIR, or configure ASTRBOT_SOURCE_GIT_URL/ASTRBOT_SOURCE_GIT_REF and run resource prepare.".to_string()
     })?;
 
     let mut args = vec!["run".to_string(), "main.py".to_string()];
-    let webui_dir = env::var("ASTRBOT_WEBUI_DIR")
-        .ok()
-        .map(PathBuf::from)
-        .or_else(|| {
-            let candidate = source_root.join("dashboard").join("dist");
-            if candidate.join("index.html").is_file() {
-                Some(candidate)
-            } else {
-                None
-            }
-        });
+    let webui_dir = overrides.webui_dir.clone().or_else(|| {
+        let candidate = source_root.join("dashboard").join("dist");
+        if candidate.join("index.html").is_file() {
+            Some(candidate)
+        } else {
+            None
+        }
+    });
     if let Some(path) = &webui_dir {
         args.push("--webui-dir".to_string());
         args.push(path.to_string_lossy().to_string());
     }
-    let root_dir = env::var(crate::ASTRBOT_ROOT_ENV).ok().map(PathBuf::from);
+    let root_dir = overrides.root_dir.clone();
     let startup_heartbeat_path = resolve_launch_startup_heartbeat_path(root_dir.as_deref(), false);
 
     Ok(LaunchPlan {
         cmd: "uv".to_string(),
         args,
-        cwd: env::var("ASTRBOT_BACKEND_CWD")
-            .map(PathBuf::from)
-            .unwrap_or(source_root),
+        cwd: overrides.backend_cwd.clone().unwrap_or(source_root),
         root_dir,
         webui_dir,
         startup_heartbeat_path,
+        dashboard_port: overrides.dashboard_port,
+        runtime_dir: None,
         packaged_mode: false,
     })
 }
@@ -249,7 +276,9 @@ mod tests {
     fn resolve_custom_launch_sets_startup_heartbeat_path_from_root_dir() {
         let _root_guard = EnvVarGuard::set(crate::ASTRBOT_ROOT_ENV, "/tmp/astrbot-root");
 
-        let plan = resolve_custom_launch("python main.py".to_string()).expect("custom plan");
+        let plan =
+            resolve_custom_launch("python main.py".to_string(), &LaunchOverrides::from_env())
+                .expect("custom plan");
 
         assert_eq!(
             plan.startup_heartbeat_path,
@@ -1,10 +1,41 @@
-use crate::BackendState;
+use std::sync::Arc;
+
+use crate::{backend::host::BackendHost, BackendState};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ExitTrigger {
     ExitRequested,
     ExitFallback,
     TrayQuit,
+    TrayStopAndQuit,
+    Signal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExitBackendAction {
+    Stop,
+    Detach,
+}
+
+/// Signals keep stopping the backend: they usually mean logout or system shutdown.
+fn decide_exit_backend_action(trigger: ExitTrigger, detach_on_quit: bool) -> ExitBackendAction {
+    match trigger {
+        ExitTrigger::TrayStopAndQuit | ExitTrigger::Signal => ExitBackendAction::Stop,
+        _ if detach_on_quit => ExitBackendAction::Detach,
+        _ => ExitBackendAction::Stop,
+    }
+}
+
+impl ExitTrigger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExitTrigger::ExitRequested => "exitRequested",
+            ExitTrigger::ExitFallback => "exitFallback",
+            ExitTrigger::TrayQuit => "trayQuit",
+            ExitTrigger::TrayStopAndQuit => "trayStopAndQuit",
+            ExitTrigger::Signal => "signal",
+        }
+    }
 }
 
 fn duplicate_cleanup_message(trigger: ExitTrigger) -> &'static str {
@@ -14,6 +45,10 @@ fn duplicate_cleanup_message(trigger: ExitTrigger) -> &'static str {
             "exit fallback cleanup skipped: backend cleanup already running"
         }
         ExitTrigger::TrayQuit => "tray quit while backend cleanup is already running",
+        ExitTrigger::TrayStopAndQuit => {
+            "tray stop and quit while backend cleanup is already running"
+        }
+        ExitTrigger::Signal => "signal received while backend cleanup is already running",
     }
 }
 
@@ -22,6 +57,8 @@ fn stop_failure_prefix(trigger: ExitTrigger) -> &'static str {
         ExitTrigger::ExitRequested => "backend graceful stop on ExitRequested failed",
         ExitTrigger::ExitFallback => "backend fallback stop on Exit failed",
         ExitTrigger::TrayQuit => "backend graceful stop on tray quit failed",
+        ExitTrigger::TrayStopAndQuit => "backend graceful stop on tray stop and quit failed",
+        ExitTrigger::Signal => "backend stop on signal failed",
     }
 }
 
@@ -37,23 +74,67 @@ where
     false
 }
 
-pub fn stop_backend_for_exit<F>(state: &BackendState, trigger: ExitTrigger, log: F)
-where
+pub fn stop_backends_for_exit<'a, I, F>(
+    app: &impl BackendHost,
+    detach_on_quit: bool,
+    states: I,
+    trigger: ExitTrigger,
+    log: F,
+) where
+    I: IntoIterator<Item = &'a Arc<BackendState>>,
     F: Fn(&str),
 {
     let failure_prefix = stop_failure_prefix(trigger);
-    if let Err(error) = state.stop_backend() {
-        log(&format!("{failure_prefix}: {error}"));
+    let action = decide_exit_backend_action(trigger, detach_on_quit);
+    let states: Vec<_> = states.into_iter().collect();
+    // Abort every in-flight startup first so no profile waits behind another's stop.
+    for state in &states {
+        state.cancel_backend_startup("desktop exiting");
+    }
+    for state in states {
+        state.mark_backend_exiting();
+        if action == ExitBackendAction::Detach {
+            match state.detach_backend_for_exit(app) {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(error) => log(&format!(
+                    "backend detach on exit failed (profile {}): {error}",
+                    state.profile_id
+                )),
+            }
+        }
+        let result = if state.reattached_backend().is_some() {
+            state.stop_reattached_backend()
+        } else {
+            if state.attached_backend().is_some() {
+                log(&format!(
+                    "leaving attached backend running (profile {}): not owned by desktop",
+                    state.profile_id
+                ));
+            }
+            state.stop_backend()
+        };
+        if let Err(error) = result {
+            log(&format!(
+                "{failure_prefix} (profile {}): {error}",
+                state.profile_id
+            ));
+        }
     }
 
-    if matches!(trigger, ExitTrigger::ExitRequested | ExitTrigger::TrayQuit) {
+    if matches!(
+        trigger,
+        ExitTrigger::ExitRequested | ExitTrigger::TrayQuit | ExitTrigger::TrayStopAndQuit
+    ) {
         log("backend stop finished, exiting desktop process");
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{duplicate_cleanup_message, ExitTrigger};
+    use super::{
+        decide_exit_backend_action, duplicate_cleanup_message, ExitBackendAction, ExitTrigger,
+    };
 
     #[test]
     fn duplicate_cleanup_message_describes_tray_quit_trigger() {
@@ -63,6 +144,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detach_on_quit_never_applies_to_stop_and_quit_or_signals() {
+        assert_eq!(
+            decide_exit_backend_action(ExitTrigger::TrayQuit, true),
+            ExitBackendAction::Detach
+        );
+        assert_eq!(
+            decide_exit_backend_action(ExitTrigger::ExitRequested, true),
+            ExitBackendAction::Detach
+        );
+        assert_eq!(
+            decide_exit_backend_action(ExitTrigger::TrayQuit, false),
+            ExitBackendAction::Stop
+        );
+        assert_eq!(
+            decide_exit_backend_action(ExitTrigger::TrayStopAndQuit, true),
+            ExitBackendAction::Stop
+        );
+        assert_eq!(
+            decide_exit_backend_action(ExitTrigger::Signal, true),
+            ExitBackendAction::Stop
+        );
+    }
+
     #[test]
     fn stop_failure_prefix_describes_tray_quit_trigger() {
         assert_eq!(
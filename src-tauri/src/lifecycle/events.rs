@@ -1,6 +1,12 @@
 use tauri::{AppHandle, Manager};
 
-use crate::{append_shutdown_log, lifecycle::cleanup, BackendState};
+use crate::{
+    active_backend_state, append_shutdown_log,
+    desktop_events::{self, DesktopEvent},
+    lifecycle::cleanup,
+    profiles::registry::ProfileRegistry,
+    DesktopSettingsCache,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ExitRequestedDecision {
@@ -16,18 +22,37 @@ fn decide_exit_requested_flow(has_exit_request_allowance: bool) -> ExitRequested
     }
 }
 
+fn detach_on_quit(app_handle: &AppHandle) -> bool {
+    app_handle
+        .state::<DesktopSettingsCache>()
+        .get()
+        .detach_on_quit
+}
+
 fn stop_backend_then_exit(app_handle: &AppHandle, trigger: cleanup::ExitTrigger) {
+    desktop_events::publish(
+        None,
+        DesktopEvent::ExitRequested {
+            trigger: trigger.as_str(),
+        },
+    );
     let app_handle_cloned = app_handle.clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let state = app_handle_cloned.state::<BackendState>();
-        cleanup::stop_backend_for_exit(&state, trigger, append_shutdown_log);
-        state.allow_next_exit_request();
+        let registry = app_handle_cloned.state::<ProfileRegistry>();
+        cleanup::stop_backends_for_exit(
+            &app_handle_cloned,
+            detach_on_quit(&app_handle_cloned),
+            registry.states(),
+            trigger,
+            append_shutdown_log,
+        );
+        registry.active_state().allow_next_exit_request();
         app_handle_cloned.exit(0);
     });
 }
 
 pub fn handle_exit_requested(app_handle: &AppHandle, api: &tauri::ExitRequestApi) {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(app_handle);
     match decide_exit_requested_flow(state.take_exit_request_allowance()) {
         ExitRequestedDecision::AllowImmediateExit => {
             append_shutdown_log("exit request allowed to pass through after backend cleanup");
@@ -49,7 +74,7 @@ pub fn handle_exit_requested(app_handle: &AppHandle, api: &tauri::ExitRequestApi
 }
 
 pub fn handle_tray_quit(app_handle: &AppHandle) {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(app_handle);
     state.mark_quitting();
     if !cleanup::try_begin_exit_cleanup(&state, cleanup::ExitTrigger::TrayQuit, append_shutdown_log)
     {
@@ -60,8 +85,23 @@ pub fn handle_tray_quit(app_handle: &AppHandle) {
     stop_backend_then_exit(app_handle, cleanup::ExitTrigger::TrayQuit);
 }
 
+pub fn handle_tray_stop_and_quit(app_handle: &AppHandle) {
+    let state = active_backend_state(app_handle);
+    state.mark_quitting();
+    if !cleanup::try_begin_exit_cleanup(
+        &state,
+        cleanup::ExitTrigger::TrayStopAndQuit,
+        append_shutdown_log,
+    ) {
+        return;
+    }
+
+    append_shutdown_log("tray stop and quit requested, stopping backend asynchronously");
+    stop_backend_then_exit(app_handle, cleanup::ExitTrigger::TrayStopAndQuit);
+}
+
 pub fn handle_exit_event(app_handle: &AppHandle) {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(app_handle);
     if !cleanup::try_begin_exit_cleanup(
         &state,
         cleanup::ExitTrigger::ExitFallback,
@@ -71,8 +111,10 @@ pub fn handle_exit_event(app_handle: &AppHandle) {
     }
 
     append_shutdown_log("exit event triggered fallback backend cleanup");
-    cleanup::stop_backend_for_exit(
-        &state,
+    cleanup::stop_backends_for_exit(
+        app_handle,
+        detach_on_quit(app_handle),
+        app_handle.state::<ProfileRegistry>().states(),
         cleanup::ExitTrigger::ExitFallback,
         append_shutdown_log,
     );
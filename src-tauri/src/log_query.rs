@@ -0,0 +1,587 @@
+use std::{
+    fs::File,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::logging::{self, DesktopLogCategory};
+
+const DESKTOP_LOG_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f %z";
+const BACKEND_LOG_TIMESTAMP_FORMATS: [&str; 3] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S,%3f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+];
+const REVERSE_READ_CHUNK_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LogSource {
+    Desktop,
+    Backend,
+}
+
+impl LogSource {
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "desktop" => Some(Self::Desktop),
+            "backend" => Some(Self::Backend),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogQuery {
+    pub(crate) limit: Option<usize>,
+    pub(crate) categories: Option<Vec<String>>,
+    pub(crate) since_ms: Option<i64>,
+    pub(crate) until_ms: Option<i64>,
+    pub(crate) contains: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogQueryLine {
+    pub(crate) file: String,
+    pub(crate) timestamp_ms: Option<i64>,
+    pub(crate) category: Option<&'static str>,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogQueryResult {
+    pub(crate) ok: bool,
+    pub(crate) lines: Vec<LogQueryLine>,
+    pub(crate) truncated: bool,
+    pub(crate) reason: Option<String>,
+}
+
+impl LogQueryResult {
+    pub(crate) fn error(reason: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            lines: Vec::new(),
+            truncated: false,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LogLineFilter {
+    categories: Option<Vec<DesktopLogCategory>>,
+    since_ms: Option<i64>,
+    until_ms: Option<i64>,
+    contains: Option<String>,
+}
+
+impl LogLineFilter {
+    fn from_query(source: LogSource, query: &LogQuery) -> Result<Self, String> {
+        let categories = match query.categories.as_deref() {
+            None | Some([]) => None,
+            Some(_) if source == LogSource::Backend => {
+                return Err("Category filters only apply to the desktop log.".to_string());
+            }
+            Some(raw_categories) => Some(
+                raw_categories
+                    .iter()
+                    .map(|raw| {
+                        DesktopLogCategory::parse(raw)
+                            .ok_or_else(|| format!("Unknown desktop log category '{raw}'."))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+        if let (Some(since_ms), Some(until_ms)) = (query.since_ms, query.until_ms) {
+            if since_ms > until_ms {
+                return Err("Log time range start is after its end.".to_string());
+            }
+        }
+        let contains = query
+            .contains
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_lowercase);
+
+        Ok(Self {
+            categories,
+            since_ms: query.since_ms,
+            until_ms: query.until_ms,
+            contains,
+        })
+    }
+
+    fn matches_text(&self, text: &str) -> bool {
+        self.contains
+            .as_deref()
+            .is_none_or(|contains| text.to_lowercase().contains(contains))
+    }
+
+    fn matches_context(
+        &self,
+        timestamp_ms: Option<i64>,
+        category: Option<DesktopLogCategory>,
+    ) -> bool {
+        if let Some(categories) = self.categories.as_ref() {
+            if !category.is_some_and(|category| categories.contains(&category)) {
+                return false;
+            }
+        }
+        if self.since_ms.is_some() || self.until_ms.is_some() {
+            let Some(timestamp_ms) = timestamp_ms else {
+                return false;
+            };
+            if self
+                .since_ms
+                .is_some_and(|since_ms| timestamp_ms < since_ms)
+                || self
+                    .until_ms
+                    .is_some_and(|until_ms| timestamp_ms > until_ms)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn parse_desktop_log_header(line: &str) -> Option<(i64, DesktopLogCategory)> {
+    if line.starts_with('{') {
+        return parse_desktop_json_record(line);
+    }
+    let rest = line.strip_prefix('[')?;
+    let (raw_timestamp, rest) = rest.split_once("] [")?;
+    let (raw_category, _) = rest.split_once(']')?;
+    let timestamp = DateTime::parse_from_str(raw_timestamp, DESKTOP_LOG_TIMESTAMP_FORMAT).ok()?;
+    let category = DesktopLogCategory::parse(raw_category)?;
+    Some((timestamp.timestamp_millis(), category))
+}
+
+fn parse_desktop_json_record(line: &str) -> Option<(i64, DesktopLogCategory)> {
+    let record: serde_json::Value = serde_json::from_str(line).ok()?;
+    let timestamp = DateTime::parse_from_rfc3339(record.get("timestamp")?.as_str()?).ok()?;
+    let category = DesktopLogCategory::parse(record.get("category")?.as_str()?)?;
+    Some((timestamp.timestamp_millis(), category))
+}
+
+fn parse_backend_log_timestamp(line: &str) -> Option<i64> {
+    let candidate = line.trim_start_matches('[');
+    // Longest supported prefix: "2026-01-02 03:04:05.123456".
+    let prefix_len = candidate
+        .char_indices()
+        .take_while(|(_, ch)| {
+            ch.is_ascii_digit() || matches!(ch, '-' | ':' | ' ' | '.' | ',' | 'T')
+        })
+        .map(|(index, ch)| index + ch.len_utf8())
+        .last()?;
+    let raw_timestamp = candidate[..prefix_len].trim_end();
+    BACKEND_LOG_TIMESTAMP_FORMATS.iter().find_map(|format| {
+        let naive = NaiveDateTime::parse_from_str(raw_timestamp, format).ok()?;
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|timestamp| timestamp.timestamp_millis())
+    })
+}
+
+/// Yields the lines of a log from last to first, reading fixed-size chunks
+/// backward from the end so a tail query never holds a whole file in memory.
+struct ReverseLines<R> {
+    reader: R,
+    unread_bytes: u64,
+    chunk_bytes: u64,
+    pending: Vec<u8>,
+    finished: bool,
+}
+
+impl<R: Read + Seek> ReverseLines<R> {
+    fn new(mut reader: R, chunk_bytes: u64) -> io::Result<Self> {
+        let unread_bytes = reader.seek(SeekFrom::End(0))?;
+        let mut lines = Self {
+            reader,
+            unread_bytes,
+            chunk_bytes: chunk_bytes.max(1),
+            pending: Vec::new(),
+            finished: unread_bytes == 0,
+        };
+        // A trailing newline ends the last line rather than starting an empty one.
+        if lines.read_chunk()? && lines.pending.last() == Some(&b'\n') {
+            lines.pending.pop();
+        }
+        Ok(lines)
+    }
+
+    fn read_chunk(&mut self) -> io::Result<bool> {
+        if self.unread_bytes == 0 {
+            return Ok(false);
+        }
+        let chunk_len = self.chunk_bytes.min(self.unread_bytes);
+        self.unread_bytes -= chunk_len;
+        self.reader.seek(SeekFrom::Start(self.unread_bytes))?;
+        let mut chunk = vec![0; chunk_len as usize];
+        self.reader.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&self.pending);
+        self.pending = chunk;
+        Ok(true)
+    }
+}
+
+impl<R: Read + Seek> Iterator for ReverseLines<R> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            if let Some(index) = self.pending.iter().rposition(|byte| *byte == b'\n') {
+                let line = self.pending.split_off(index + 1);
+                self.pending.pop();
+                return Some(line);
+            }
+            match self.read_chunk() {
+                Ok(true) => {}
+                Ok(false) | Err(_) => {
+                    self.finished = true;
+                    return Some(std::mem::take(&mut self.pending));
+                }
+            }
+        }
+    }
+}
+
+fn line_header(source: LogSource, line: &str) -> Option<(i64, Option<DesktopLogCategory>)> {
+    match source {
+        LogSource::Desktop => parse_desktop_log_header(line)
+            .map(|(timestamp_ms, category)| (timestamp_ms, Some(category))),
+        LogSource::Backend => {
+            parse_backend_log_timestamp(line).map(|timestamp_ms| (timestamp_ms, None))
+        }
+    }
+}
+
+/// Collects up to `limit` matching lines from one file, newest first. Lines
+/// without their own timestamp (continuations, tracebacks) inherit the header
+/// above them, so they wait until that header is reached. A block shares one
+/// header, so only its newest `limit` candidates are ever kept.
+fn collect_newest_matching_lines(
+    source: LogSource,
+    lines: impl Iterator<Item = Vec<u8>>,
+    file: &str,
+    filter: &LogLineFilter,
+    limit: usize,
+) -> Vec<LogQueryLine> {
+    let mut matches = Vec::new();
+    let mut continuation = Vec::new();
+    for raw in lines {
+        let raw = raw.strip_suffix(b"\r").unwrap_or(&raw);
+        let line = String::from_utf8_lossy(raw).into_owned();
+        let Some((timestamp_ms, category)) = line_header(source, &line) else {
+            if continuation.len() + matches.len() < limit && filter.matches_text(&line) {
+                continuation.push(line);
+            }
+            continue;
+        };
+        let header_matches = filter.matches_text(&line);
+        if !filter.matches_context(Some(timestamp_ms), category) {
+            continuation.clear();
+            continue;
+        }
+        for text in continuation.drain(..).chain(header_matches.then_some(line)) {
+            matches.push(LogQueryLine {
+                file: file.to_string(),
+                timestamp_ms: Some(timestamp_ms),
+                category: category.map(DesktopLogCategory::as_label),
+                text,
+            });
+            if matches.len() == limit {
+                return matches;
+            }
+        }
+    }
+    if filter.matches_context(None, None) {
+        matches.extend(continuation.into_iter().map(|text| LogQueryLine {
+            file: file.to_string(),
+            timestamp_ms: None,
+            category: None,
+            text,
+        }));
+    }
+    matches
+}
+
+fn collect_matching_lines(
+    source: LogSource,
+    path: &Path,
+    filter: &LogLineFilter,
+    limit: usize,
+) -> Vec<LogQueryLine> {
+    let file = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let collected = if path.extension().is_some_and(|extension| extension == "gz") {
+        // Compressed backups cannot be read backward; they are bounded by the rotation size.
+        logging::read_log_file(path)
+            .and_then(|raw| ReverseLines::new(Cursor::new(raw), REVERSE_READ_CHUNK_BYTES))
+            .map(|lines| collect_newest_matching_lines(source, lines, &file, filter, limit))
+    } else {
+        File::open(path)
+            .and_then(|reader| ReverseLines::new(reader, REVERSE_READ_CHUNK_BYTES))
+            .map(|lines| collect_newest_matching_lines(source, lines, &file, filter, limit))
+    };
+    collected.unwrap_or_default()
+}
+
+/// Returns the active log followed by its rotated backups, newest first.
+fn log_files_newest_first(path: &Path, backup_count: usize) -> Vec<PathBuf> {
+    let mut files = vec![path.to_path_buf()];
+    files.extend(
+        (1..=backup_count).filter_map(|index| logging::existing_rotated_log_path(path, index)),
+    );
+    files
+}
+
+pub(crate) fn query_log_lines(
+    source: LogSource,
+    path: &Path,
+    backup_count: usize,
+    query: &LogQuery,
+    default_limit: usize,
+    max_limit: usize,
+) -> LogQueryResult {
+    let filter = match LogLineFilter::from_query(source, query) {
+        Ok(filter) => filter,
+        Err(error) => return LogQueryResult::error(error),
+    };
+    let limit = query.limit.unwrap_or(default_limit).clamp(1, max_limit);
+
+    let mut newest_first = Vec::new();
+    let mut truncated = false;
+    for file in log_files_newest_first(path, backup_count) {
+        let remaining = limit - newest_first.len();
+        // One extra match tells whether anything older was left out.
+        let mut matches = collect_matching_lines(source, &file, &filter, remaining + 1);
+        if matches.len() > remaining {
+            matches.truncate(remaining);
+            truncated = true;
+        }
+        newest_first.extend(matches);
+        if truncated {
+            break;
+        }
+    }
+    newest_first.reverse();
+
+    LogQueryResult {
+        ok: true,
+        lines: newest_first,
+        truncated,
+        reason: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Cursor};
+
+    use super::{
+        parse_backend_log_timestamp, parse_desktop_log_header, query_log_lines, LogQuery,
+        LogSource, ReverseLines,
+    };
+    use crate::logging::DesktopLogCategory;
+
+    fn texts(result: &super::LogQueryResult) -> Vec<&str> {
+        result.lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn parse_desktop_log_header_reads_timestamp_and_category() {
+        let (timestamp_ms, category) =
+            parse_desktop_log_header("[2026-01-02 03:04:05.678 +0000] [restart] hello")
+                .expect("header should parse");
+        assert_eq!(timestamp_ms, 1_767_323_045_678);
+        assert_eq!(category, DesktopLogCategory::Restart);
+        assert!(parse_desktop_log_header("Traceback (most recent call last):").is_none());
+    }
+
+    #[test]
+    fn parse_desktop_log_header_reads_structured_records() {
+        let (timestamp_ms, category) = parse_desktop_log_header(
+            r#"{"timestamp":"2026-01-02T03:04:05.678+00:00","seq":7,"level":"info","category":"shutdown","message":"bye","backendPid":null}"#,
+        )
+        .expect("structured record should parse");
+        assert_eq!(timestamp_ms, 1_767_323_045_678);
+        assert_eq!(category, DesktopLogCategory::Shutdown);
+    }
+
+    #[test]
+    fn parse_backend_log_timestamp_accepts_common_python_formats() {
+        assert!(parse_backend_log_timestamp("[2026-01-02 03:04:05.678] [Core] ok").is_some());
+        assert!(parse_backend_log_timestamp("2026-01-02 03:04:05,678 INFO ok").is_some());
+        assert!(parse_backend_log_timestamp("[03:04:05] [Core] ok").is_none());
+    }
+
+    fn reverse_lines(content: &str, chunk_bytes: u64) -> Vec<String> {
+        ReverseLines::new(Cursor::new(content.as_bytes().to_vec()), chunk_bytes)
+            .expect("create reverse reader")
+            .map(|line| String::from_utf8(line).expect("utf-8 line"))
+            .collect()
+    }
+
+    #[test]
+    fn reverse_lines_reads_backward_across_chunks() {
+        assert_eq!(reverse_lines("a\nbb\n\nccc", 2), vec!["ccc", "", "bb", "a"]);
+        assert_eq!(reverse_lines("first\nsecond\n", 3), vec!["second", "first"]);
+        assert_eq!(reverse_lines("\n", 4), vec![""]);
+        assert!(reverse_lines("", 4).is_empty());
+    }
+
+    #[test]
+    fn query_log_lines_keeps_only_the_newest_lines_of_a_headerless_block() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("backend.log");
+        fs::write(&path, "one\r\ntwo\r\nthree\r\nfour\r\n").expect("write active log");
+
+        let result = query_log_lines(
+            LogSource::Backend,
+            &path,
+            5,
+            &LogQuery {
+                limit: Some(2),
+                ..LogQuery::default()
+            },
+            200,
+            1_000,
+        );
+
+        assert!(result.truncated);
+        assert_eq!(texts(&result), vec!["three", "four"]);
+        assert!(result.lines.iter().all(|line| line.timestamp_ms.is_none()));
+    }
+
+    #[test]
+    fn query_log_lines_tails_across_rotated_backups() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("desktop.log");
+        fs::write(
+            temp.path().join("desktop.log.1"),
+            "[2026-01-02 03:04:01.000 +0000] [startup] one\n[2026-01-02 03:04:02.000 +0000] [startup] two\n",
+        )
+        .expect("write backup");
+        fs::write(&path, "[2026-01-02 03:04:03.000 +0000] [runtime] three\n")
+            .expect("write active log");
+
+        let result = query_log_lines(
+            LogSource::Desktop,
+            &path,
+            5,
+            &LogQuery {
+                limit: Some(2),
+                ..LogQuery::default()
+            },
+            200,
+            1_000,
+        );
+
+        assert!(result.ok);
+        assert!(result.truncated);
+        assert_eq!(
+            texts(&result),
+            vec![
+                "[2026-01-02 03:04:02.000 +0000] [startup] two",
+                "[2026-01-02 03:04:03.000 +0000] [runtime] three"
+            ]
+        );
+        assert_eq!(result.lines[0].file, "desktop.log.1");
+    }
+
+    #[test]
+    fn query_log_lines_filters_by_category_time_and_substring() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("desktop.log");
+        fs::write(
+            &path,
+            concat!(
+                "[2026-01-02 03:04:01.000 +0000] [startup] backend spawn failed\n",
+                "  continuation of failure\n",
+                "[2026-01-02 03:04:02.000 +0000] [restart] restart failed\n",
+                "[2026-01-02 03:04:03.000 +0000] [startup] backend ready\n",
+            ),
+        )
+        .expect("write active log");
+
+        let result = query_log_lines(
+            LogSource::Desktop,
+            &path,
+            5,
+            &LogQuery {
+                categories: Some(vec!["startup".to_string()]),
+                until_ms: Some(1_767_323_042_000),
+                contains: Some("FAIL".to_string()),
+                ..LogQuery::default()
+            },
+            200,
+            1_000,
+        );
+
+        assert_eq!(
+            texts(&result),
+            vec![
+                "[2026-01-02 03:04:01.000 +0000] [startup] backend spawn failed",
+                "  continuation of failure"
+            ]
+        );
+        assert_eq!(result.lines[1].category, Some("startup"));
+    }
+
+    #[test]
+    fn query_log_lines_rejects_invalid_filters() {
+        let path = std::path::Path::new("missing.log");
+        let backend_categories = query_log_lines(
+            LogSource::Backend,
+            path,
+            5,
+            &LogQuery {
+                categories: Some(vec!["startup".to_string()]),
+                ..LogQuery::default()
+            },
+            200,
+            1_000,
+        );
+        assert!(!backend_categories.ok);
+
+        let unknown_category = query_log_lines(
+            LogSource::Desktop,
+            path,
+            5,
+            &LogQuery {
+                categories: Some(vec!["verbose".to_string()]),
+                ..LogQuery::default()
+            },
+            200,
+            1_000,
+        );
+        assert!(!unknown_category.ok);
+
+        let inverted_range = query_log_lines(
+            LogSource::Desktop,
+            path,
+            5,
+            &LogQuery {
+                since_ms: Some(2),
+                until_ms: Some(1),
+                ..LogQuery::default()
+            },
+            200,
+            1_000,
+        );
+        assert!(!inverted_range.ok);
+    }
+}
@@ -1,12 +1,21 @@
 use std::{
     env,
     ffi::OsString,
-    fs::{self, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
     path::{Path, PathBuf},
-    sync::{Mutex, OnceLock},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, SystemTime},
 };
 
+use chrono::{DateTime, Local, SecondsFormat};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+static DESKTOP_LOG_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DesktopLogCategory {
     Startup,
@@ -16,7 +25,17 @@ pub enum DesktopLogCategory {
 }
 
 impl DesktopLogCategory {
-    fn as_label(self) -> &'static str {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "startup" => Some(Self::Startup),
+            "runtime" => Some(Self::Runtime),
+            "restart" => Some(Self::Restart),
+            "shutdown" => Some(Self::Shutdown),
+            _ => None,
+        }
+    }
+
+    pub fn as_label(self) -> &'static str {
         match self {
             Self::Startup => "startup",
             Self::Runtime => "runtime",
@@ -26,59 +45,304 @@ impl DesktopLogCategory {
     }
 }
 
-pub fn rotate_log_if_needed(
+/// Ordered from most to least severe, so `level <= minimum` means "enabled".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DesktopLogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl DesktopLogLevel {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "warn" | "warning" => Some(Self::Warn),
+            "info" => Some(Self::Info),
+            "debug" => Some(Self::Debug),
+            "trace" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn as_label(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Minimum enabled level per `DesktopLogCategory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DesktopLogLevelFilter {
+    startup: DesktopLogLevel,
+    runtime: DesktopLogLevel,
+    restart: DesktopLogLevel,
+    shutdown: DesktopLogLevel,
+}
+
+impl Default for DesktopLogLevelFilter {
+    fn default() -> Self {
+        Self {
+            startup: DesktopLogLevel::Info,
+            runtime: DesktopLogLevel::Info,
+            restart: DesktopLogLevel::Info,
+            shutdown: DesktopLogLevel::Info,
+        }
+    }
+}
+
+impl DesktopLogLevelFilter {
+    pub fn min_level(&self, category: DesktopLogCategory) -> DesktopLogLevel {
+        match category {
+            DesktopLogCategory::Startup => self.startup,
+            DesktopLogCategory::Runtime => self.runtime,
+            DesktopLogCategory::Restart => self.restart,
+            DesktopLogCategory::Shutdown => self.shutdown,
+        }
+    }
+
+    pub fn allows(&self, category: DesktopLogCategory, level: DesktopLogLevel) -> bool {
+        level <= self.min_level(category)
+    }
+
+    fn set_all(&mut self, level: DesktopLogLevel) {
+        *self = Self {
+            startup: level,
+            runtime: level,
+            restart: level,
+            shutdown: level,
+        };
+    }
+
+    fn set(&mut self, category: DesktopLogCategory, level: DesktopLogLevel) {
+        match category {
+            DesktopLogCategory::Startup => self.startup = level,
+            DesktopLogCategory::Runtime => self.runtime = level,
+            DesktopLogCategory::Restart => self.restart = level,
+            DesktopLogCategory::Shutdown => self.shutdown = level,
+        }
+    }
+
+    /// Applies a comma-separated spec such as `warn,startup=debug` in order. A bare
+    /// level (or `default=<level>`) sets every category. Returns the entries that
+    /// could not be parsed.
+    pub fn apply_spec(&mut self, spec: &str) -> Vec<String> {
+        let mut invalid = Vec::new();
+        for entry in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let applied = match entry.split_once('=') {
+                None => DesktopLogLevel::parse(entry).map(|level| self.set_all(level)),
+                Some((scope, raw_level)) => DesktopLogLevel::parse(raw_level).and_then(|level| {
+                    if scope.trim().eq_ignore_ascii_case("default") {
+                        self.set_all(level);
+                        return Some(());
+                    }
+                    DesktopLogCategory::parse(scope).map(|category| self.set(category, level))
+                }),
+            };
+            if applied.is_none() {
+                invalid.push(entry.to_string());
+            }
+        }
+        invalid
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DesktopLogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl DesktopLogFormat {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "json" | "ndjson" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+pub fn format_desktop_log_record(
+    format: DesktopLogFormat,
+    timestamp: DateTime<Local>,
+    category: DesktopLogCategory,
+    level: DesktopLogLevel,
+    message: &str,
+    backend_pid: Option<u32>,
+    sequence: u64,
+) -> String {
+    match format {
+        // Info lines keep the historical layout; other levels get a tag after the category.
+        DesktopLogFormat::Text if level == DesktopLogLevel::Info => format!(
+            "[{}] [{}] {}\n",
+            timestamp.format("%Y-%m-%d %H:%M:%S%.3f %z"),
+            category.as_label(),
+            message
+        ),
+        DesktopLogFormat::Text => format!(
+            "[{}] [{}] [{}] {}\n",
+            timestamp.format("%Y-%m-%d %H:%M:%S%.3f %z"),
+            category.as_label(),
+            level.as_label(),
+            message
+        ),
+        DesktopLogFormat::Json => {
+            let record = serde_json::json!({
+                "timestamp": timestamp.to_rfc3339_opts(SecondsFormat::Millis, false),
+                "seq": sequence,
+                "level": level.as_label(),
+                "category": category.as_label(),
+                "message": message,
+                "backendPid": backend_pid,
+            });
+            format!("{record}\n")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRotationPolicy {
+    pub max_bytes: u64,
+    pub backup_count: usize,
+    pub compress_backups: bool,
+    pub max_age: Option<Duration>,
+}
+
+impl LogRotationPolicy {
+    pub fn new(max_bytes: u64, backup_count: usize) -> Self {
+        Self {
+            max_bytes,
+            backup_count,
+            compress_backups: false,
+            max_age: None,
+        }
+    }
+}
+
+pub fn parse_log_flag(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn remove_file_if_exists(path: &Path, log_scope: &str, description: &str) {
+    if let Err(error) = fs::remove_file(path) {
+        if error.kind() != io::ErrorKind::NotFound {
+            eprintln!(
+                "[log rotation:{log_scope}] failed to remove {description} {}: {}",
+                path.display(),
+                error
+            );
+        }
+    }
+}
+
+fn compressed_log_path(path: &Path) -> PathBuf {
+    let mut value = OsString::from(path.as_os_str());
+    value.push(".gz");
+    PathBuf::from(value)
+}
+
+fn compress_log_file(source: &Path, target: &Path) -> io::Result<()> {
+    let mut input = File::open(source)?;
+    let mut encoder = GzEncoder::new(File::create(target)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::remove_file(source)
+}
+
+fn remove_backup(path: &Path, index: usize, log_scope: &str) {
+    let plain = rotated_log_path(path, index);
+    remove_file_if_exists(&plain, log_scope, "backup");
+    remove_file_if_exists(&compressed_log_path(&plain), log_scope, "backup");
+}
+
+/// Deletes rotated backups whose last write is older than `max_age`.
+pub fn prune_expired_log_backups(
     path: &Path,
-    max_bytes: u64,
     backup_count: usize,
+    max_age: Duration,
+    now: SystemTime,
     log_scope: &str,
-    copy_and_truncate: bool,
 ) {
-    if max_bytes == 0 || backup_count == 0 {
-        return;
+    for index in 1..=backup_count {
+        let Some(backup) = existing_rotated_log_path(path, index) else {
+            continue;
+        };
+        let expired = fs::metadata(&backup)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+        if expired {
+            remove_file_if_exists(&backup, log_scope, "expired backup");
+        }
+    }
+}
+
+pub fn rotate_log_if_needed(path: &Path, policy: &LogRotationPolicy, log_scope: &str) {
+    if shift_log_backups_if_needed(path, policy, log_scope) {
+        finish_log_rotation(path, policy, log_scope);
+    }
+}
+
+/// Moves the active log into backup slot 1 once it has outgrown the policy and
+/// returns whether it did. Compression and pruning are left to
+/// `finish_log_rotation` so callers can run them off a hot path.
+pub fn shift_log_backups_if_needed(
+    path: &Path,
+    policy: &LogRotationPolicy,
+    log_scope: &str,
+) -> bool {
+    if policy.max_bytes == 0 || policy.backup_count == 0 {
+        return false;
     }
 
     let metadata = match fs::metadata(path) {
         Ok(metadata) => metadata,
         Err(error) => {
-            if error.kind() != std::io::ErrorKind::NotFound {
+            if error.kind() != io::ErrorKind::NotFound {
                 eprintln!(
                     "[log rotation:{log_scope}] failed to read metadata for {}: {}",
                     path.display(),
                     error
                 );
             }
-            return;
+            return false;
         }
     };
-    if metadata.len() < max_bytes {
-        return;
+    if metadata.len() < policy.max_bytes {
+        return false;
     }
 
-    let oldest = rotated_log_path(path, backup_count);
-    if let Err(error) = fs::remove_file(&oldest) {
-        if error.kind() != std::io::ErrorKind::NotFound {
-            eprintln!(
-                "[log rotation:{log_scope}] failed to remove oldest backup {}: {}",
-                oldest.display(),
-                error
-            );
-        }
-    }
+    remove_backup(path, policy.backup_count, log_scope);
 
-    for index in (1..backup_count).rev() {
-        let source = rotated_log_path(path, index);
-        if !source.exists() {
+    for index in (1..policy.backup_count).rev() {
+        let Some(source) = existing_rotated_log_path(path, index) else {
             continue;
-        }
-        let target = rotated_log_path(path, index + 1);
-        if let Err(error) = fs::remove_file(&target) {
-            if error.kind() != std::io::ErrorKind::NotFound {
-                eprintln!(
-                    "[log rotation:{log_scope}] failed to remove backup {}: {}",
-                    target.display(),
-                    error
-                );
-            }
+        };
+        remove_backup(path, index + 1, log_scope);
+        let mut target = rotated_log_path(path, index + 1);
+        if source
+            .extension()
+            .is_some_and(|extension| extension == "gz")
+        {
+            target = compressed_log_path(&target);
         }
         if let Err(error) = fs::rename(&source, &target) {
             eprintln!(
@@ -91,52 +355,71 @@ pub fn rotate_log_if_needed(
     }
 
     let rotated = rotated_log_path(path, 1);
-    if let Err(error) = fs::remove_file(&rotated) {
-        if error.kind() != std::io::ErrorKind::NotFound {
+    remove_backup(path, 1, log_scope);
+
+    if let Err(error) = fs::rename(path, &rotated) {
+        eprintln!(
+            "[log rotation:{log_scope}] failed to rotate {} to {}: {}",
+            path.display(),
+            rotated.display(),
+            error
+        );
+        return false;
+    }
+    true
+}
+
+pub fn finish_log_rotation(path: &Path, policy: &LogRotationPolicy, log_scope: &str) {
+    let rotated = rotated_log_path(path, 1);
+    if policy.compress_backups && rotated.exists() {
+        let compressed = compressed_log_path(&rotated);
+        if let Err(error) = compress_log_file(&rotated, &compressed) {
             eprintln!(
-                "[log rotation:{log_scope}] failed to remove first backup {}: {}",
+                "[log rotation:{log_scope}] failed to compress {}: {}",
                 rotated.display(),
                 error
             );
+            remove_file_if_exists(&compressed, log_scope, "partial compressed backup");
         }
     }
 
-    if copy_and_truncate {
-        match fs::copy(path, &rotated) {
-            Ok(_) => {
-                if let Err(error) = OpenOptions::new().write(true).truncate(true).open(path) {
-                    eprintln!(
-                        "[log rotation:{log_scope}] failed to truncate active log {}: {}",
-                        path.display(),
-                        error
-                    );
-                }
-            }
-            Err(error) => {
-                eprintln!(
-                    "[log rotation:{log_scope}] failed to copy {} to {}: {}",
-                    path.display(),
-                    rotated.display(),
-                    error
-                );
-            }
-        }
-    } else if let Err(error) = fs::rename(path, &rotated) {
-        eprintln!(
-            "[log rotation:{log_scope}] failed to rotate {} to {}: {}",
-            path.display(),
-            rotated.display(),
-            error
+    if let Some(max_age) = policy.max_age {
+        prune_expired_log_backups(
+            path,
+            policy.backup_count,
+            max_age,
+            SystemTime::now(),
+            log_scope,
         );
     }
 }
 
-fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+pub fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
     let mut value = OsString::from(path.as_os_str());
     value.push(format!(".{index}"));
     PathBuf::from(value)
 }
 
+/// Resolves backup `index`, preferring the gzip-compressed variant.
+pub fn existing_rotated_log_path(path: &Path, index: usize) -> Option<PathBuf> {
+    let plain = rotated_log_path(path, index);
+    let compressed = compressed_log_path(&plain);
+    if compressed.is_file() {
+        return Some(compressed);
+    }
+    plain.is_file().then_some(plain)
+}
+
+/// Reads an active or rotated log file, transparently decompressing `.gz` backups.
+pub fn read_log_file(path: &Path) -> io::Result<Vec<u8>> {
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        let mut raw = Vec::new();
+        GzDecoder::new(File::open(path)?).read_to_end(&mut raw)?;
+        return Ok(raw);
+    }
+    fs::read(path)
+}
+
 pub fn resolve_desktop_log_path(packaged_root: Option<PathBuf>, desktop_log_file: &str) -> PathBuf {
     if let Ok(custom) = env::var("ASTRBOT_DESKTOP_LOG_PATH") {
         let candidate = PathBuf::from(custom.trim());
@@ -185,13 +468,16 @@ pub fn resolve_backend_log_path(
         .join("backend.log")
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn append_desktop_log(
     category: DesktopLogCategory,
+    level: DesktopLogLevel,
     message: &str,
+    backend_pid: Option<u32>,
     packaged_root: Option<PathBuf>,
     desktop_log_file: &str,
-    max_bytes: u64,
-    backup_count: usize,
+    rotation: &LogRotationPolicy,
+    format: DesktopLogFormat,
     write_lock: &OnceLock<Mutex<()>>,
 ) {
     let path = resolve_desktop_log_path(packaged_root, desktop_log_file);
@@ -202,14 +488,224 @@ pub fn append_desktop_log(
         Ok(guard) => guard,
         Err(poisoned) => poisoned.into_inner(),
     };
-    rotate_log_if_needed(&path, max_bytes, backup_count, "desktop", false);
-    let timestamp = chrono::Local::now()
-        .format("%Y-%m-%d %H:%M:%S%.3f %z")
-        .to_string();
-    let line = format!("[{}] [{}] {}\n", timestamp, category.as_label(), message);
+    rotate_log_if_needed(&path, rotation, "desktop");
+    // Taken under the write lock so sequence numbers follow file order.
+    let sequence = DESKTOP_LOG_SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1;
+    let line = format_desktop_log_record(
+        format,
+        Local::now(),
+        category,
+        level,
+        message,
+        backend_pid,
+        sequence,
+    );
     let _ = OpenOptions::new()
         .create(true)
         .append(true)
         .open(path)
         .and_then(|mut file| file.write_all(line.as_bytes()));
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    use chrono::{Local, TimeZone};
+
+    use super::{
+        existing_rotated_log_path, format_desktop_log_record, parse_log_flag,
+        prune_expired_log_backups, read_log_file, rotate_log_if_needed, rotated_log_path,
+        DesktopLogCategory, DesktopLogFormat, DesktopLogLevel, DesktopLogLevelFilter,
+        LogRotationPolicy,
+    };
+
+    #[test]
+    fn rotate_log_if_needed_shifts_plain_backups() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("desktop.log");
+        fs::write(rotated_log_path(&path, 1), "older").expect("write backup");
+        fs::write(rotated_log_path(&path, 2), "oldest").expect("write backup");
+        fs::write(&path, "current").expect("write active log");
+
+        rotate_log_if_needed(&path, &LogRotationPolicy::new(4, 2), "test");
+
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(rotated_log_path(&path, 1)).expect("read backup 1"),
+            "current"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_log_path(&path, 2)).expect("read backup 2"),
+            "older"
+        );
+    }
+
+    #[test]
+    fn rotate_log_if_needed_compresses_backups_and_keeps_them_readable() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("backend.log");
+        let policy = LogRotationPolicy {
+            compress_backups: true,
+            ..LogRotationPolicy::new(4, 3)
+        };
+
+        fs::write(&path, "first").expect("write active log");
+        rotate_log_if_needed(&path, &policy, "test");
+        fs::write(&path, "second").expect("write active log");
+        rotate_log_if_needed(&path, &policy, "test");
+
+        let newest = existing_rotated_log_path(&path, 1).expect("backup 1 exists");
+        let older = existing_rotated_log_path(&path, 2).expect("backup 2 exists");
+        assert!(newest.to_string_lossy().ends_with("backend.log.1.gz"));
+        assert!(older.to_string_lossy().ends_with("backend.log.2.gz"));
+        assert!(!rotated_log_path(&path, 1).exists());
+        assert_eq!(read_log_file(&newest).expect("read backup 1"), b"second");
+        assert_eq!(read_log_file(&older).expect("read backup 2"), b"first");
+    }
+
+    #[test]
+    fn prune_expired_log_backups_only_removes_old_backups() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let path = temp.path().join("desktop.log");
+        fs::write(&path, "active").expect("write active log");
+        fs::write(rotated_log_path(&path, 1), "backup").expect("write backup");
+
+        prune_expired_log_backups(&path, 3, Duration::from_secs(60), SystemTime::now(), "test");
+        assert!(rotated_log_path(&path, 1).exists());
+
+        prune_expired_log_backups(
+            &path,
+            3,
+            Duration::from_secs(60),
+            SystemTime::now() + Duration::from_secs(3_600),
+            "test",
+        );
+        assert!(!rotated_log_path(&path, 1).exists());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn parse_log_flag_accepts_common_spellings() {
+        assert_eq!(parse_log_flag("On"), Some(true));
+        assert_eq!(parse_log_flag("0"), Some(false));
+        assert_eq!(parse_log_flag("maybe"), None);
+    }
+
+    #[test]
+    fn desktop_log_format_parse_accepts_known_values() {
+        assert_eq!(
+            DesktopLogFormat::parse("TEXT"),
+            Some(DesktopLogFormat::Text)
+        );
+        assert_eq!(
+            DesktopLogFormat::parse(" json "),
+            Some(DesktopLogFormat::Json)
+        );
+        assert_eq!(
+            DesktopLogFormat::parse("ndjson"),
+            Some(DesktopLogFormat::Json)
+        );
+        assert_eq!(DesktopLogFormat::parse("xml"), None);
+        assert_eq!(DesktopLogFormat::default(), DesktopLogFormat::Text);
+    }
+
+    #[test]
+    fn format_desktop_log_record_writes_one_json_object_per_line() {
+        let timestamp = Local.timestamp_millis_opt(1_767_323_045_678).unwrap();
+        let line = format_desktop_log_record(
+            DesktopLogFormat::Json,
+            timestamp,
+            DesktopLogCategory::Restart,
+            DesktopLogLevel::Warn,
+            "line one\nline two",
+            Some(4242),
+            9,
+        );
+
+        assert!(line.ends_with('\n'));
+        assert_eq!(line.matches('\n').count(), 1);
+        let record: serde_json::Value = serde_json::from_str(line.trim_end()).expect("parse");
+        assert_eq!(record["seq"], 9);
+        assert_eq!(record["level"], "warn");
+        assert_eq!(record["category"], "restart");
+        assert_eq!(record["message"], "line one\nline two");
+        assert_eq!(record["backendPid"], 4242);
+        assert_eq!(
+            chrono::DateTime::parse_from_rfc3339(record["timestamp"].as_str().unwrap())
+                .expect("rfc3339 timestamp")
+                .timestamp_millis(),
+            1_767_323_045_678
+        );
+    }
+
+    #[test]
+    fn format_desktop_log_record_keeps_text_layout_by_default() {
+        let timestamp = Local.timestamp_millis_opt(0).unwrap();
+        let line = format_desktop_log_record(
+            DesktopLogFormat::Text,
+            timestamp,
+            DesktopLogCategory::Startup,
+            DesktopLogLevel::Info,
+            "hello",
+            None,
+            1,
+        );
+
+        assert!(line.starts_with('['));
+        assert!(line.ends_with("] [startup] hello\n"));
+    }
+
+    #[test]
+    fn format_desktop_log_record_tags_non_info_text_lines_with_level() {
+        let timestamp = Local.timestamp_millis_opt(0).unwrap();
+        let line = format_desktop_log_record(
+            DesktopLogFormat::Text,
+            timestamp,
+            DesktopLogCategory::Runtime,
+            DesktopLogLevel::Error,
+            "boom",
+            None,
+            1,
+        );
+
+        assert!(line.ends_with("] [runtime] [error] boom\n"));
+    }
+
+    #[test]
+    fn level_filter_defaults_to_info_for_every_category() {
+        let filter = DesktopLogLevelFilter::default();
+
+        assert!(filter.allows(DesktopLogCategory::Startup, DesktopLogLevel::Error));
+        assert!(filter.allows(DesktopLogCategory::Restart, DesktopLogLevel::Info));
+        assert!(!filter.allows(DesktopLogCategory::Runtime, DesktopLogLevel::Debug));
+    }
+
+    #[test]
+    fn level_filter_apply_spec_sets_default_then_category_overrides() {
+        let mut filter = DesktopLogLevelFilter::default();
+
+        let invalid = filter.apply_spec("warn, startup=trace,restart=DEBUG,bogus,runtime=loud");
+
+        assert_eq!(
+            invalid,
+            vec!["bogus".to_string(), "runtime=loud".to_string()]
+        );
+        assert_eq!(
+            filter.min_level(DesktopLogCategory::Startup),
+            DesktopLogLevel::Trace
+        );
+        assert_eq!(
+            filter.min_level(DesktopLogCategory::Restart),
+            DesktopLogLevel::Debug
+        );
+        assert_eq!(
+            filter.min_level(DesktopLogCategory::Runtime),
+            DesktopLogLevel::Warn
+        );
+        assert!(!filter.allows(DesktopLogCategory::Shutdown, DesktopLogLevel::Info));
+    }
+}
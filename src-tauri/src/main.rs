@@ -8,17 +8,24 @@ mod app_types;
 
 mod backend;
 mod bridge;
+mod cli;
 mod desktop_auth;
+mod desktop_events;
 mod desktop_settings;
 mod desktop_state;
+mod diagnostics;
 
 mod exit_state;
+mod headless;
+mod instance_lock;
 mod lifecycle;
 
 mod launch_plan;
+mod log_query;
 mod logging;
 mod packaged_webui;
 mod process_control;
+mod profiles;
 mod restart_backend_flow;
 mod runtime_paths;
 mod shell_locale;
@@ -27,6 +34,7 @@ mod startup_task;
 
 mod tray;
 mod ui_dispatch;
+mod unix_signals;
 mod update_channel;
 mod webui_paths;
 mod window;
@@ -34,16 +42,50 @@ mod windows_shutdown;
 
 pub(crate) use app_constants::*;
 pub(crate) use app_helpers::{
-    append_desktop_log, append_restart_log, append_shutdown_log, append_startup_log,
-    backend_path_override, build_debug_command, inject_desktop_bridge,
-    navigate_main_window_to_backend,
+    active_backend_state, append_desktop_log, append_desktop_log_at, append_restart_log,
+    append_restart_log_at, append_shutdown_log, append_startup_log, append_startup_log_at,
+    backend_log_rotation_policy, backend_path_override, build_debug_command,
+    desktop_log_rotation_policy, inject_desktop_bridge, navigate_main_window_to_backend,
 };
 pub(crate) use app_types::{
-    AtomicFlagGuard, BackendBridgeResult, BackendBridgeState, BackendState,
-    DesktopAuthBridgeResult, LaunchPlan, RuntimeManifest, TrayMenuState,
+    BackendBridgeResult, BackendBridgeState, BackendState, DesktopAuthBridgeResult, LaunchPlan,
+    RuntimeManifest, TrayMenuState,
 };
 pub(crate) use desktop_settings::DesktopSettingsCache;
 
 fn main() {
-    app_runtime::run();
+    let cli_args = match cli::parse_cli_args(std::env::args_os()) {
+        Ok(cli_args) => cli_args,
+        Err(error) => {
+            cli::attach_parent_console();
+            eprintln!("{error}\n\n{}", cli::usage());
+            std::process::exit(2);
+        }
+    };
+    if cli_args.help || cli_args.version {
+        cli::attach_parent_console();
+    }
+    if cli_args.help {
+        println!("{}", cli::usage());
+        return;
+    }
+    if cli_args.version {
+        println!("astrbot-desktop {}", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+    // Still single-threaded here, so updating the environment is safe.
+    for (key, value) in cli_args.env_overrides() {
+        std::env::set_var(key, value);
+    }
+    // Logged after the overrides so `--root` also picks the desktop.log location.
+    if !cli_args.unrecognized.is_empty() {
+        append_startup_log_at(
+            logging::DesktopLogLevel::Warn,
+            &format!(
+                "ignoring unrecognized arguments: {}",
+                cli_args.unrecognized.join(" ")
+            ),
+        );
+    }
+    app_runtime::run(cli_args);
 }
@@ -1,13 +1,21 @@
-#[cfg(any(target_os = "windows", test))]
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
 use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "linux")]
+use std::fs;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
+#[cfg(any(target_os = "windows", test))]
+use std::process::ExitStatus;
+#[cfg(target_os = "windows")]
+use std::process::{Command, Stdio};
 use std::{
     io,
-    process::{Child, Command, ExitStatus, Stdio},
+    process::Child,
     thread,
     time::{Duration, Instant},
 };
+
+use crate::logging::DesktopLogLevel;
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::{
     Foundation::{
@@ -33,7 +41,7 @@ const FORCE_STOP_WAIT_MAX_NON_WINDOWS_MS: u64 = 1_500;
 #[cfg(target_os = "windows")]
 const WINDOWS_CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
-fn wait_for_child_exit(child: &mut Child, timeout: Duration) -> bool {
+pub fn wait_for_child_exit(child: &mut Child, timeout: Duration) -> bool {
     let start = Instant::now();
     loop {
         match child.try_wait() {
@@ -49,6 +57,7 @@ fn wait_for_child_exit(child: &mut Child, timeout: Duration) -> bool {
     }
 }
 
+#[cfg(target_os = "windows")]
 fn run_stop_command<F>(
     pid: u32,
     label: &str,
@@ -57,7 +66,7 @@ fn run_stop_command<F>(
     log: F,
 ) -> io::Result<ExitStatus>
 where
-    F: Fn(&str) + Copy,
+    F: Fn(DesktopLogLevel, &str) + Copy,
 {
     let mut command = Command::new(program);
     command
@@ -74,12 +83,14 @@ where
 
     match &status {
         Ok(exit_status) if exit_status.success() => {}
-        Ok(exit_status) => log(&format!(
-            "{label} returned non-zero: pid={pid}, status={exit_status:?}"
-        )),
-        Err(error) => log(&format!(
-            "{label} failed to start: pid={pid}, error={error}"
-        )),
+        Ok(exit_status) => log(
+            DesktopLogLevel::Warn,
+            &format!("{label} returned non-zero: pid={pid}, status={exit_status:?}"),
+        ),
+        Err(error) => log(
+            DesktopLogLevel::Error,
+            &format!("{label} failed to start: pid={pid}, error={error}"),
+        ),
     }
 
     status
@@ -95,6 +106,7 @@ fn compute_followup_wait(timeout: Duration, max_extra_wait: Duration) -> Duratio
     }
 }
 
+#[cfg(any(target_os = "windows", test))]
 fn resolve_graceful_wait_timeout<F>(
     pid: u32,
     timeout: Duration,
@@ -104,7 +116,7 @@ fn resolve_graceful_wait_timeout<F>(
     log: F,
 ) -> Duration
 where
-    F: Fn(&str) + Copy,
+    F: Fn(DesktopLogLevel, &str) + Copy,
 {
     match graceful_status {
         Ok(status) if status.success() => timeout,
@@ -115,7 +127,7 @@ where
                     Ok(status) => format!("status={status:?}"),
                     Err(error) => format!("error={error}"),
                 };
-                log(&format!(
+                log(DesktopLogLevel::Warn, &format!(
                     "{command_label} not successful; shorten graceful wait: pid={pid}, {outcome}, requested_wait_ms={}, effective_wait_ms={}",
                     timeout.as_millis(),
                     shortened_wait.as_millis()
@@ -133,7 +145,7 @@ fn close_handle(handle: HANDLE) {
     }
 }
 
-#[cfg(any(target_os = "windows", test))]
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
 fn collect_descendant_processes(root_pid: u32, entries: &[(u32, u32)]) -> Vec<u32> {
     let mut children_by_parent: HashMap<u32, Vec<u32>> = HashMap::new();
     for (pid, parent_pid) in entries {
@@ -250,13 +262,16 @@ fn is_expected_shutdown_termination_error(error: &io::Error) -> bool {
 }
 
 #[cfg(target_os = "windows")]
-fn terminate_process_tree_native(root_pid: u32, log: &dyn Fn(&str)) {
+fn terminate_process_tree_native(root_pid: u32, log: &dyn Fn(DesktopLogLevel, &str)) {
     let process_tree = match snapshot_process_tree(root_pid) {
         Ok(process_tree) => process_tree,
         Err(error) => {
-            log(&format!(
-                "native Windows process tree snapshot failed: pid={root_pid}, error={error}"
-            ));
+            log(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "native Windows process tree snapshot failed: pid={root_pid}, error={error}"
+                ),
+            );
             vec![root_pid]
         }
     };
@@ -264,9 +279,10 @@ fn terminate_process_tree_native(root_pid: u32, log: &dyn Fn(&str)) {
     for pid in process_tree {
         if let Err(error) = terminate_process_by_pid(pid) {
             if !is_expected_shutdown_termination_error(&error) {
-                log(&format!(
-                    "native Windows process termination failed: pid={pid}, error={error}"
-                ));
+                log(
+                    DesktopLogLevel::Warn,
+                    &format!("native Windows process termination failed: pid={pid}, error={error}"),
+                );
             }
         }
     }
@@ -279,7 +295,7 @@ pub fn stop_child_process_for_system_shutdown<F>(
     log: F,
 ) -> bool
 where
-    F: Fn(&str) + Copy,
+    F: Fn(DesktopLogLevel, &str) + Copy,
 {
     let pid = child.id();
     terminate_process_tree_native(pid, &log);
@@ -289,7 +305,7 @@ where
 #[cfg(target_os = "windows")]
 pub fn stop_child_process_gracefully<F>(child: &mut Child, timeout: Duration, log: F) -> bool
 where
-    F: Fn(&str) + Copy,
+    F: Fn(DesktopLogLevel, &str) + Copy,
 {
     // Normal Windows app exits intentionally keep the existing taskkill-based
     // process-tree cleanup. The system-shutdown path uses native Win32
@@ -330,43 +346,461 @@ where
         timeout,
         Duration::from_millis(FORCE_STOP_WAIT_MAX_WINDOWS_MS),
     );
-    log(&format!(
+    log(DesktopLogLevel::Warn, &format!(
         "child graceful stop timed out, force-kill issued: pid={pid}, graceful={graceful_status:?}, force={force_status:?}, followup_wait_ms={}",
         followup_wait.as_millis(),
     ));
     wait_for_child_exit(child, followup_wait)
 }
 
-#[cfg(not(target_os = "windows"))]
-pub fn stop_child_process_gracefully<F>(child: &mut Child, timeout: Duration, log: F) -> bool
-where
-    F: Fn(&str) + Copy,
-{
-    // Normal Unix app exits intentionally keep the existing external kill-based
-    // process cleanup.
-    let pid = child.id();
-    let pid_arg = pid.to_string();
+#[cfg(unix)]
+fn to_pid_t(pid: u32) -> io::Result<libc::pid_t> {
+    libc::pid_t::try_from(pid)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pid out of range"))
+}
 
-    let graceful_status = run_stop_command(pid, "kill -TERM", "kill", &["-TERM", &pid_arg], log);
+#[cfg(unix)]
+fn send_signal(target: libc::pid_t, signal: libc::c_int) -> io::Result<()> {
+    if unsafe { libc::kill(target, signal) } == 0 {
+        return Ok(());
+    }
+    let error = io::Error::last_os_error();
+    if error.raw_os_error() == Some(libc::ESRCH) {
+        return Ok(());
+    }
+    Err(error)
+}
 
-    let graceful_wait_timeout =
-        resolve_graceful_wait_timeout(pid, timeout, timeout, &graceful_status, "kill -TERM", log);
-    if wait_for_child_exit(child, graceful_wait_timeout) {
+#[cfg(unix)]
+fn signal_process_group(pgid: u32, signal: libc::c_int) -> io::Result<()> {
+    let pgid = to_pid_t(pgid)?;
+    // Never signal our own group: that would take the desktop shell down with the backend.
+    if pgid <= 1 || pgid == unsafe { libc::getpgrp() } {
+        return send_signal(pgid, signal);
+    }
+    send_signal(-pgid, signal)
+}
+
+#[cfg(target_os = "linux")]
+fn process_group_alive(pgid: u32) -> bool {
+    // Zombies still answer `kill(-pgid, 0)` until their new parent reaps them.
+    match read_all_proc_stats() {
+        Ok(stats) => stats.values().any(|stat| stat.pgrp == pgid && !stat.zombie),
+        Err(_) => false,
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_group_alive(pgid: u32) -> bool {
+    let Ok(pgid) = to_pid_t(pgid) else {
+        return false;
+    };
+    if unsafe { libc::kill(-pgid, 0) } == 0 {
         return true;
     }
+    io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
 
-    let force_status = run_stop_command(pid, "kill -KILL", "kill", &["-KILL", &pid_arg], log);
+/// Waits for what is left of a process group after its leader exited, so members
+/// still handling their own shutdown are not killed mid-flush.
+#[cfg(unix)]
+pub fn wait_for_process_group_exit(pgid: u32, timeout: Duration) -> bool {
+    let start = Instant::now();
+    // Never wait on our own group: the desktop shell is a member.
+    if pgid <= 1 || to_pid_t(pgid).ok() == Some(unsafe { libc::getpgrp() }) {
+        return true;
+    }
+    while process_group_alive(pgid) {
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(120).min(timeout - elapsed));
+    }
+    true
+}
 
-    let followup_wait = compute_followup_wait(
-        timeout,
-        Duration::from_millis(FORCE_STOP_WAIT_MAX_NON_WINDOWS_MS),
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProcStat {
+    ppid: u32,
+    pgrp: u32,
+    cpu_ticks: u64,
+    start_time: u64,
+    rss_pages: u64,
+    zombie: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_stat(content: &str) -> Option<ProcStat> {
+    // `comm` may contain spaces and parentheses, so split after the last ')'.
+    let fields: Vec<&str> = content[content.rfind(')')? + 1..]
+        .split_whitespace()
+        .collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(ProcStat {
+        ppid: fields.get(1)?.parse().ok()?,
+        pgrp: fields.get(2)?.parse().ok()?,
+        cpu_ticks: utime.saturating_add(stime),
+        start_time: fields.get(19)?.parse().ok()?,
+        rss_pages: fields.get(21)?.parse().ok()?,
+        zombie: *fields.first()? == "Z",
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_stat(pid: u32) -> Option<ProcStat> {
+    fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|content| parse_proc_stat(&content))
+}
+
+#[cfg(target_os = "linux")]
+fn read_all_proc_stats() -> io::Result<HashMap<u32, ProcStat>> {
+    let mut stats = HashMap::new();
+    for entry in fs::read_dir("/proc")? {
+        let Some(pid) = entry?
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if let Some(stat) = read_proc_stat(pid) {
+            stats.insert(pid, stat);
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(target_os = "linux")]
+fn proc_tree_members(root_pid: u32, stats: &HashMap<u32, ProcStat>) -> Vec<u32> {
+    let entries = stats
+        .iter()
+        .map(|(pid, stat)| (*pid, stat.ppid))
+        .collect::<Vec<_>>();
+    collect_descendant_processes(root_pid, &entries)
+}
+
+#[cfg(target_os = "linux")]
+fn snapshot_proc_descendants(root_pid: u32) -> io::Result<Vec<(u32, u64)>> {
+    let stats = read_all_proc_stats()?;
+    Ok(proc_tree_members(root_pid, &stats)
+        .into_iter()
+        .filter(|pid| *pid != root_pid)
+        .filter_map(|pid| stats.get(&pid).map(|stat| (pid, stat.start_time)))
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessTreeSample {
+    pub rss_bytes: u64,
+    pub cpu_ticks: u64,
+    pub process_count: usize,
+}
+
+#[cfg(target_os = "linux")]
+pub fn clock_ticks_per_second() -> u64 {
+    // SAFETY: sysconf only reads a system configuration value.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    u64::try_from(ticks)
+        .ok()
+        .filter(|ticks| *ticks > 0)
+        .unwrap_or(100)
+}
+
+#[cfg(target_os = "linux")]
+fn page_size_bytes() -> u64 {
+    // SAFETY: sysconf only reads a system configuration value.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(page_size)
+        .ok()
+        .filter(|size| *size > 0)
+        .unwrap_or(4096)
+}
+
+/// Sums resident memory and CPU time over the backend and every live descendant.
+#[cfg(target_os = "linux")]
+pub fn sample_process_tree(root_pid: u32) -> Option<ProcessTreeSample> {
+    let stats = read_all_proc_stats().ok()?;
+    if stats.get(&root_pid).is_none_or(|stat| stat.zombie) {
+        return None;
+    }
+    let page_size = page_size_bytes();
+    let mut sample = ProcessTreeSample {
+        rss_bytes: 0,
+        cpu_ticks: 0,
+        process_count: 0,
+    };
+    for stat in proc_tree_members(root_pid, &stats)
+        .iter()
+        .filter_map(|pid| stats.get(pid))
+        .filter(|stat| !stat.zombie)
+    {
+        sample.rss_bytes = sample
+            .rss_bytes
+            .saturating_add(stat.rss_pages.saturating_mul(page_size));
+        sample.cpu_ticks = sample.cpu_ticks.saturating_add(stat.cpu_ticks);
+        sample.process_count += 1;
+    }
+    Some(sample)
+}
+
+#[cfg(target_os = "linux")]
+fn surviving_descendants(descendants: &[(u32, u64)]) -> Vec<u32> {
+    // Matching start times guard against signalling a recycled pid.
+    descendants
+        .iter()
+        .filter(|(pid, start_time)| {
+            read_proc_stat(*pid).is_some_and(|stat| stat.start_time == *start_time && !stat.zombie)
+        })
+        .map(|(pid, _)| *pid)
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn sweep_surviving_descendants<F>(root_pid: u32, descendants: &[(u32, u64)], log: F)
+where
+    F: Fn(DesktopLogLevel, &str) + Copy,
+{
+    let survivors = surviving_descendants(descendants);
+    if survivors.is_empty() {
+        return;
+    }
+    for pid in &survivors {
+        let _ = to_pid_t(*pid).and_then(|pid| send_signal(pid, libc::SIGKILL));
+    }
+    log(
+        DesktopLogLevel::Warn,
+        &format!("killed backend descendants that outlived the process group stop: root_pid={root_pid}, pids={survivors:?}"),
     );
-    log(&format!(
-        "child graceful stop timed out, force-kill issued: pid={pid}, graceful={graceful_status:?}, force={force_status:?}, followup_wait_ms={}",
-        followup_wait.as_millis(),
+
+    thread::sleep(Duration::from_millis(FORCE_STOP_WAIT_MIN_MS));
+    let remaining = surviving_descendants(descendants);
+    if !remaining.is_empty() {
+        log(
+            DesktopLogLevel::Error,
+            &format!("backend descendants still alive after sweep: root_pid={root_pid}, pids={remaining:?}"),
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn process_start_time(pid: u32) -> Option<u64> {
+    read_proc_stat(pid)
+        .filter(|stat| !stat.zombie)
+        .map(|stat| stat.start_time)
+}
+
+#[cfg(target_os = "linux")]
+fn wait_until_process_gone(pid: u32, start_time: u64, timeout: Duration) -> bool {
+    let start = Instant::now();
+    loop {
+        if process_start_time(pid) != Some(start_time) {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(120));
+    }
+}
+
+/// Stops a backend left behind by a previous shell. It is not our child, so
+/// liveness is tracked through `/proc` with the recorded start time.
+#[cfg(target_os = "linux")]
+pub fn terminate_orphaned_process_tree<F>(
+    pid: u32,
+    start_time: u64,
+    timeout: Duration,
+    log: F,
+) -> bool
+where
+    F: Fn(DesktopLogLevel, &str) + Copy,
+{
+    let descendants = snapshot_proc_descendants(pid).unwrap_or_else(|error| {
+        log(
+            DesktopLogLevel::Warn,
+            &format!("orphaned backend process tree snapshot failed: pid={pid}, error={error}"),
+        );
+        Vec::new()
+    });
+    let leads_group = read_proc_stat(pid).is_some_and(|stat| stat.pgrp == pid);
+    let signal = |signal: libc::c_int| {
+        if leads_group {
+            signal_process_group(pid, signal)
+        } else {
+            to_pid_t(pid).and_then(|pid| send_signal(pid, signal))
+        }
+    };
+
+    let graceful_result = signal(libc::SIGTERM);
+    let gone = if wait_until_process_gone(pid, start_time, timeout) {
+        true
+    } else {
+        let force_result = signal(libc::SIGKILL);
+        log(DesktopLogLevel::Warn, &format!(
+            "orphaned backend ignored SIGTERM, force-kill issued: pid={pid}, graceful={graceful_result:?}, force={force_result:?}"
+        ));
+        wait_until_process_gone(
+            pid,
+            start_time,
+            Duration::from_millis(FORCE_STOP_WAIT_MAX_NON_WINDOWS_MS),
+        )
+    };
+
+    if gone {
+        if leads_group {
+            let _ = signal_process_group(pid, libc::SIGKILL);
+        }
+        sweep_surviving_descendants(pid, &descendants, log);
+    }
+    gone
+}
+
+/// Stops a backend adopted from an earlier session's detached record. Without a recorded
+/// start time there is no way to rule out pid reuse, so nothing is signalled.
+#[cfg(target_os = "linux")]
+pub fn terminate_detached_process_tree<F>(
+    pid: u32,
+    start_time: Option<u64>,
+    timeout: Duration,
+    log: F,
+) -> bool
+where
+    F: Fn(DesktopLogLevel, &str) + Copy,
+{
+    match start_time {
+        Some(start_time) => terminate_orphaned_process_tree(pid, start_time, timeout, log),
+        None => {
+            log(
+                DesktopLogLevel::Warn,
+                &format!("detached backend has no recorded start time, not signalling: pid={pid}"),
+            );
+            false
+        }
+    }
+}
+
+/// Other Unix systems have no `/proc` start time; the caller has just verified the
+/// backend over HTTP, and the backend leads its own process group.
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn terminate_detached_process_tree<F>(
+    pid: u32,
+    _start_time: Option<u64>,
+    timeout: Duration,
+    log: F,
+) -> bool
+where
+    F: Fn(DesktopLogLevel, &str) + Copy,
+{
+    let alive = || to_pid_t(pid).is_ok_and(|pid| unsafe { libc::kill(pid, 0) } == 0);
+    let wait_gone = |timeout: Duration| {
+        let start = Instant::now();
+        while alive() {
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(120));
+        }
+        true
+    };
+
+    let graceful_result = signal_process_group(pid, libc::SIGTERM);
+    if wait_gone(timeout) {
+        let _ = signal_process_group(pid, libc::SIGKILL);
+        return true;
+    }
+    let force_result = signal_process_group(pid, libc::SIGKILL);
+    log(DesktopLogLevel::Warn, &format!(
+        "detached backend ignored SIGTERM, force-kill issued: pid={pid}, graceful={graceful_result:?}, force={force_result:?}"
     ));
+    wait_gone(Duration::from_millis(FORCE_STOP_WAIT_MAX_NON_WINDOWS_MS))
+}
 
-    wait_for_child_exit(child, followup_wait)
+#[cfg(target_os = "windows")]
+pub fn terminate_detached_process_tree<F>(
+    pid: u32,
+    _start_time: Option<u64>,
+    _timeout: Duration,
+    log: F,
+) -> bool
+where
+    F: Fn(DesktopLogLevel, &str) + Copy,
+{
+    terminate_process_tree_native(pid, &log);
+    true
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn stop_child_process_gracefully<F>(child: &mut Child, timeout: Duration, log: F) -> bool
+where
+    F: Fn(DesktopLogLevel, &str) + Copy,
+{
+    // The backend leads its own process group (see `start_backend_process`), so
+    // signalling the group reaches plugin subprocesses as well.
+    let pid = child.id();
+    let started = Instant::now();
+    #[cfg(target_os = "linux")]
+    let descendants = snapshot_proc_descendants(pid).unwrap_or_else(|error| {
+        log(
+            DesktopLogLevel::Warn,
+            &format!("backend process tree snapshot failed: pid={pid}, error={error}"),
+        );
+        Vec::new()
+    });
+
+    let graceful_result = signal_process_group(pid, libc::SIGTERM);
+    if let Err(error) = &graceful_result {
+        log(
+            DesktopLogLevel::Warn,
+            &format!("SIGTERM to backend process group failed: pid={pid}, error={error}"),
+        );
+    }
+
+    let exited = if wait_for_child_exit(child, timeout) {
+        true
+    } else {
+        let force_result = signal_process_group(pid, libc::SIGKILL);
+        let followup_wait = compute_followup_wait(
+            timeout,
+            Duration::from_millis(FORCE_STOP_WAIT_MAX_NON_WINDOWS_MS),
+        );
+        log(DesktopLogLevel::Warn, &format!(
+            "child graceful stop timed out, force-kill issued: pid={pid}, graceful={graceful_result:?}, force={force_result:?}, followup_wait_ms={}",
+            followup_wait.as_millis(),
+        ));
+        wait_for_child_exit(child, followup_wait)
+    };
+
+    if exited {
+        // Members still handling SIGTERM get the rest of the timeout; pointless
+        // once the group was already sent SIGKILL above.
+        let remaining = timeout.saturating_sub(started.elapsed());
+        if !remaining.is_zero() && !wait_for_process_group_exit(pid, remaining) {
+            log(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "backend process group outlived its leader's stop timeout: pid={pid}, timeout_ms={}",
+                    timeout.as_millis()
+                ),
+            );
+        }
+        // Group members that ignored SIGTERM must not outlive the backend.
+        if let Err(error) = signal_process_group(pid, libc::SIGKILL) {
+            log(
+                DesktopLogLevel::Warn,
+                &format!(
+                    "SIGKILL to remaining backend process group failed: pid={pid}, error={error}"
+                ),
+            );
+        }
+        #[cfg(target_os = "linux")]
+        sweep_surviving_descendants(pid, &descendants, log);
+    }
+    exited
 }
 
 #[cfg(test)]
@@ -420,6 +854,101 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn parse_proc_stat_handles_parentheses_in_comm() {
+        let stat = parse_proc_stat(
+            "4242 (python (plugin) x) S 4200 4242 4242 0 -1 4194560 1 0 0 0 150 25 0 0 20 0 1 0 987654 104857600 25600",
+        )
+        .expect("parse stat");
+        assert_eq!(
+            stat,
+            ProcStat {
+                ppid: 4200,
+                pgrp: 4242,
+                cpu_ticks: 175,
+                start_time: 987654,
+                rss_pages: 25600,
+                zombie: false,
+            }
+        );
+        assert!(
+            parse_proc_stat("7 (ffmpeg) Z 1 7 7 0 -1 0 0 0 0 0 0 0 0 0 20 0 1 0 55 0 0")
+                .expect("parse zombie")
+                .zombie
+        );
+        assert_eq!(parse_proc_stat("garbage"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn stop_child_process_gracefully_terminates_grandchildren() {
+        use std::os::unix::process::CommandExt as _;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("sh")
+            .args(["-c", "sleep 30 & sleep 30 & wait"])
+            .stdin(Stdio::null())
+            .process_group(0)
+            .spawn()
+            .expect("spawn shell");
+        let pid = child.id();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let descendants = loop {
+            let descendants = snapshot_proc_descendants(pid).expect("snapshot");
+            if descendants.len() >= 2 || Instant::now() >= deadline {
+                break descendants;
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(descendants.len(), 2);
+
+        assert!(stop_child_process_gracefully(
+            &mut child,
+            Duration::from_millis(2_000),
+            |_, _| {}
+        ));
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !surviving_descendants(&descendants).is_empty() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert!(surviving_descendants(&descendants).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn stop_child_process_gracefully_lets_group_members_finish_their_shutdown() {
+        use std::os::unix::process::CommandExt as _;
+        use std::process::{Command, Stdio};
+
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let ready = temp.path().join("ready");
+        let flushed = temp.path().join("flushed");
+        let script = format!(
+            "(trap 'sleep 0.4; touch \"{}\"; exit 0' TERM; touch \"{}\"; while :; do sleep 0.05; done) & wait",
+            flushed.display(),
+            ready.display()
+        );
+        let mut child = Command::new("sh")
+            .args(["-c", &script])
+            .stdin(Stdio::null())
+            .process_group(0)
+            .spawn()
+            .expect("spawn shell");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !ready.exists() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert!(ready.exists());
+
+        assert!(stop_child_process_gracefully(
+            &mut child,
+            Duration::from_millis(3_000),
+            |_, _| {}
+        ));
+        assert!(flushed.exists());
+        assert!(!process_group_alive(child.id()));
+    }
+
     #[test]
     fn compute_followup_wait_respects_min_and_cap() {
         assert_eq!(
@@ -446,12 +975,17 @@ mod tests {
             Duration::from_millis(350),
             &graceful_status,
             "taskkill graceful stop",
-            |message| logs.lock().expect("lock logs").push(message.to_string()),
+            |level, message| {
+                logs.lock()
+                    .expect("lock logs")
+                    .push((level, message.to_string()))
+            },
         );
 
         assert_eq!(wait, Duration::from_millis(350));
         let snapshot = logs.lock().expect("lock logs");
         assert_eq!(snapshot.len(), 1);
-        assert!(snapshot[0].contains("shorten graceful wait"));
+        assert_eq!(snapshot[0].0, DesktopLogLevel::Warn);
+        assert!(snapshot[0].1.contains("shorten graceful wait"));
     }
 }
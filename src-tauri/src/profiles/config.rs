@@ -0,0 +1,403 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::{backend, launch_plan::LaunchOverrides, DEFAULT_BACKEND_URL};
+
+pub(crate) const DEFAULT_PROFILE_ID: &str = "default";
+const PROFILES_FIELD: &str = "profiles";
+const ACTIVE_PROFILE_FIELD: &str = "activeProfile";
+const PROFILE_ID_MAX_LEN: usize = 64;
+const PROFILES_DIR_NAME: &str = "profiles";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DesktopProfileEntry {
+    pub(crate) id: String,
+    pub(crate) name: Option<String>,
+    pub(crate) root_dir: Option<PathBuf>,
+    pub(crate) dashboard_port: Option<u16>,
+    pub(crate) backend_url: Option<String>,
+    pub(crate) backend_cmd: Option<String>,
+    pub(crate) backend_cwd: Option<PathBuf>,
+    pub(crate) webui_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub(crate) auto_start: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DesktopProfile {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) backend_url: String,
+    pub(crate) launch_overrides: LaunchOverrides,
+    pub(crate) auto_start: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ProfilesConfig {
+    pub(crate) profiles: Vec<DesktopProfile>,
+    pub(crate) active_profile: Option<String>,
+}
+
+pub(crate) fn is_valid_profile_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= PROFILE_ID_MAX_LEN
+        && id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn loopback_backend_url(port: u16) -> String {
+    format!("http://127.0.0.1:{port}/")
+}
+
+pub(crate) fn resolve_profile(
+    entry: DesktopProfileEntry,
+    packaged_root_dir: Option<&Path>,
+    env_overrides: &LaunchOverrides,
+    env_backend_url: &str,
+) -> Result<DesktopProfile, String> {
+    let id = entry.id.trim().to_string();
+    if !is_valid_profile_id(&id) {
+        return Err(format!(
+            "invalid profile id '{id}': use 1-{PROFILE_ID_MAX_LEN} ASCII letters, digits, '-' or '_'"
+        ));
+    }
+    let is_default = id == DEFAULT_PROFILE_ID;
+    let name = non_blank(entry.name).unwrap_or_else(|| id.clone());
+
+    let explicit_url = non_blank(entry.backend_url)
+        .map(|raw| {
+            Url::parse(&raw)
+                .map(|_| backend::config::normalize_backend_url(&raw, DEFAULT_BACKEND_URL))
+                .map_err(|error| format!("invalid backendUrl for profile '{id}': {error}"))
+        })
+        .transpose()?;
+    let backend_url = match (explicit_url, entry.dashboard_port) {
+        (Some(url), _) => url,
+        (None, Some(port)) => loopback_backend_url(port),
+        (None, None) if is_default => env_backend_url.to_string(),
+        (None, None) => {
+            return Err(format!(
+                "profile '{id}' needs a dashboardPort or backendUrl distinct from the default profile"
+            ));
+        }
+    };
+
+    let launch_overrides = if is_default {
+        LaunchOverrides {
+            backend_cmd: non_blank(entry.backend_cmd).or_else(|| env_overrides.backend_cmd.clone()),
+            backend_cwd: entry
+                .backend_cwd
+                .or_else(|| env_overrides.backend_cwd.clone()),
+            root_dir: entry.root_dir.or_else(|| env_overrides.root_dir.clone()),
+            webui_dir: entry.webui_dir.or_else(|| env_overrides.webui_dir.clone()),
+            dashboard_port: entry.dashboard_port,
+        }
+    } else {
+        // Named profiles never inherit ASTRBOT_ROOT/ASTRBOT_BACKEND_CWD: sharing
+        // a root would make two backends fight over the same data directory.
+        let root_dir = match entry.root_dir {
+            Some(root_dir) => root_dir,
+            None => packaged_root_dir
+                .map(|root| root.join(PROFILES_DIR_NAME).join(&id))
+                .ok_or_else(|| {
+                    format!("profile '{id}' has no rootDir and no default root is available")
+                })?,
+        };
+        let dashboard_port = entry.dashboard_port.or_else(|| {
+            Url::parse(&backend_url)
+                .ok()
+                .and_then(|url| url.port_or_known_default())
+        });
+        LaunchOverrides {
+            backend_cmd: non_blank(entry.backend_cmd).or_else(|| env_overrides.backend_cmd.clone()),
+            backend_cwd: entry.backend_cwd,
+            root_dir: Some(root_dir),
+            webui_dir: entry.webui_dir.or_else(|| env_overrides.webui_dir.clone()),
+            dashboard_port,
+        }
+    };
+
+    Ok(DesktopProfile {
+        id,
+        name,
+        backend_url,
+        launch_overrides,
+        auto_start: entry.auto_start,
+    })
+}
+
+pub(crate) fn parse_profiles_config<F>(
+    state: &Value,
+    packaged_root_dir: Option<&Path>,
+    env_overrides: &LaunchOverrides,
+    env_backend_url: &str,
+    mut log: F,
+) -> ProfilesConfig
+where
+    F: FnMut(String),
+{
+    let mut default_entry = DesktopProfileEntry {
+        id: DEFAULT_PROFILE_ID.to_string(),
+        ..DesktopProfileEntry::default()
+    };
+    let mut named_entries = Vec::new();
+    match state.get(PROFILES_FIELD) {
+        None | Some(Value::Null) => {}
+        Some(Value::Array(items)) => {
+            for item in items {
+                match DesktopProfileEntry::deserialize(item) {
+                    Ok(entry) if entry.id.trim() == DEFAULT_PROFILE_ID => default_entry = entry,
+                    Ok(entry) => named_entries.push(entry),
+                    Err(error) => log(format!("ignoring malformed profile entry: {error}")),
+                }
+            }
+        }
+        Some(_) => log(format!("ignoring non-array '{PROFILES_FIELD}' setting")),
+    }
+
+    let mut profiles = Vec::new();
+    let mut seen_ids = HashSet::new();
+    let mut seen_urls = HashSet::new();
+    for entry in std::iter::once(default_entry).chain(named_entries) {
+        match resolve_profile(entry, packaged_root_dir, env_overrides, env_backend_url) {
+            Ok(profile) if !seen_ids.insert(profile.id.clone()) => {
+                log(format!("ignoring duplicate profile id '{}'", profile.id));
+            }
+            Ok(profile) if !seen_urls.insert(profile.backend_url.clone()) => {
+                log(format!(
+                    "ignoring profile '{}': backend URL {} is already used by another profile",
+                    profile.id, profile.backend_url
+                ));
+            }
+            Ok(profile) => profiles.push(profile),
+            Err(error) => log(format!("ignoring profile: {error}")),
+        }
+    }
+
+    let active_profile = state
+        .get(ACTIVE_PROFILE_FIELD)
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    ProfilesConfig {
+        profiles,
+        active_profile,
+    }
+}
+
+pub(crate) fn read_profiles_config<F>(
+    packaged_root_dir: Option<&Path>,
+    env_overrides: &LaunchOverrides,
+    env_backend_url: &str,
+    mut log: F,
+) -> ProfilesConfig
+where
+    F: FnMut(String),
+{
+    let state = crate::desktop_state::resolve_desktop_state_path(packaged_root_dir)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| match serde_json::from_str::<Value>(&raw) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                log(format!(
+                    "failed to parse desktop state for profiles: {error}"
+                ));
+                None
+            }
+        })
+        .unwrap_or(Value::Null);
+    parse_profiles_config(
+        &state,
+        packaged_root_dir,
+        env_overrides,
+        env_backend_url,
+        log,
+    )
+}
+
+pub(crate) fn write_active_profile(
+    profile_id: &str,
+    packaged_root_dir: Option<&Path>,
+) -> Result<(), String> {
+    let Some(state_path) = crate::desktop_state::resolve_desktop_state_path(packaged_root_dir)
+    else {
+        return Ok(());
+    };
+    if let Some(parent_dir) = state_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(|error| {
+            format!(
+                "Failed to create desktop state directory {}: {}",
+                parent_dir.display(),
+                error
+            )
+        })?;
+    }
+
+    let mut object = match fs::read_to_string(&state_path) {
+        Ok(raw) => match serde_json::from_str::<Value>(&raw) {
+            Ok(Value::Object(object)) => object,
+            _ => {
+                return Err(format!(
+                    "Refusing to overwrite unreadable desktop state {}",
+                    state_path.display()
+                ));
+            }
+        },
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Map::new(),
+        Err(error) => {
+            return Err(format!(
+                "Failed to read desktop state {}: {}",
+                state_path.display(),
+                error
+            ));
+        }
+    };
+    object.insert(
+        ACTIVE_PROFILE_FIELD.to_string(),
+        Value::String(profile_id.to_string()),
+    );
+
+    let serialized = serde_json::to_string_pretty(&Value::Object(object))
+        .map_err(|error| format!("Failed to serialize desktop state: {error}"))?;
+    fs::write(&state_path, serialized).map_err(|error| {
+        format!(
+            "Failed to write desktop state {}: {}",
+            state_path.display(),
+            error
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use serde_json::json;
+
+    use super::{is_valid_profile_id, parse_profiles_config, DEFAULT_PROFILE_ID};
+    use crate::launch_plan::LaunchOverrides;
+
+    const ENV_URL: &str = "http://127.0.0.1:6185/";
+
+    fn parse(state: serde_json::Value) -> (super::ProfilesConfig, Vec<String>) {
+        let mut messages = Vec::new();
+        let env_overrides = LaunchOverrides {
+            root_dir: Some(PathBuf::from("/env/root")),
+            backend_cwd: Some(PathBuf::from("/env/cwd")),
+            ..LaunchOverrides::default()
+        };
+        let config = parse_profiles_config(
+            &state,
+            Some(Path::new("/home/user/.astrbot")),
+            &env_overrides,
+            ENV_URL,
+            |message| messages.push(message),
+        );
+        (config, messages)
+    }
+
+    #[test]
+    fn missing_profiles_yield_only_the_env_backed_default_profile() {
+        let (config, messages) = parse(json!({ "closeToTray": true }));
+
+        assert!(messages.is_empty());
+        assert_eq!(config.profiles.len(), 1);
+        let profile = &config.profiles[0];
+        assert_eq!(profile.id, DEFAULT_PROFILE_ID);
+        assert_eq!(profile.backend_url, ENV_URL);
+        assert_eq!(
+            profile.launch_overrides.root_dir,
+            Some(PathBuf::from("/env/root"))
+        );
+        assert_eq!(profile.launch_overrides.dashboard_port, None);
+        assert_eq!(config.active_profile, None);
+    }
+
+    #[test]
+    fn named_profiles_get_their_own_root_and_pinned_port() {
+        let (config, messages) = parse(json!({
+            "activeProfile": "customer-a",
+            "profiles": [
+                { "id": "customer-a", "name": "Customer A", "dashboardPort": 6190 },
+                { "id": "customer-b", "backendUrl": "http://127.0.0.1:6191", "rootDir": "/srv/b" }
+            ]
+        }));
+
+        assert!(messages.is_empty(), "{messages:?}");
+        assert_eq!(config.active_profile.as_deref(), Some("customer-a"));
+        let ids: Vec<_> = config.profiles.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec![DEFAULT_PROFILE_ID, "customer-a", "customer-b"]);
+
+        let a = &config.profiles[1];
+        assert_eq!(a.name, "Customer A");
+        assert_eq!(a.backend_url, "http://127.0.0.1:6190/");
+        assert_eq!(
+            a.launch_overrides.root_dir,
+            Some(PathBuf::from("/home/user/.astrbot/profiles/customer-a"))
+        );
+        assert_eq!(a.launch_overrides.backend_cwd, None);
+        assert_eq!(a.launch_overrides.dashboard_port, Some(6190));
+
+        let b = &config.profiles[2];
+        assert_eq!(b.backend_url, "http://127.0.0.1:6191/");
+        assert_eq!(b.launch_overrides.root_dir, Some(PathBuf::from("/srv/b")));
+        assert_eq!(b.launch_overrides.dashboard_port, Some(6191));
+    }
+
+    #[test]
+    fn invalid_duplicate_and_conflicting_profiles_are_skipped() {
+        let (config, messages) = parse(json!({
+            "profiles": [
+                { "id": "no-port" },
+                { "id": "bad id", "dashboardPort": 6192 },
+                { "id": "a", "dashboardPort": 6193 },
+                { "id": "a", "dashboardPort": 6194 },
+                { "id": "clash", "dashboardPort": 6185 },
+                { "id": 42 }
+            ]
+        }));
+
+        let ids: Vec<_> = config.profiles.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec![DEFAULT_PROFILE_ID, "a"]);
+        assert_eq!(messages.len(), 5, "{messages:?}");
+    }
+
+    #[test]
+    fn default_entry_customizes_the_default_profile() {
+        let (config, _) = parse(json!({
+            "profiles": [{ "id": "default", "name": "Main", "dashboardPort": 6200 }]
+        }));
+
+        let profile = &config.profiles[0];
+        assert_eq!(profile.name, "Main");
+        assert_eq!(profile.backend_url, "http://127.0.0.1:6200/");
+        assert_eq!(profile.launch_overrides.dashboard_port, Some(6200));
+        assert_eq!(
+            profile.launch_overrides.root_dir,
+            Some(PathBuf::from("/env/root"))
+        );
+    }
+
+    #[test]
+    fn is_valid_profile_id_accepts_simple_slugs_only() {
+        assert!(is_valid_profile_id("customer_a-1"));
+        assert!(!is_valid_profile_id(""));
+        assert!(!is_valid_profile_id("../escape"));
+        assert!(!is_valid_profile_id(&"x".repeat(65)));
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use crate::{exit_state::ExitStateMachine, profiles::config::DesktopProfile, BackendState};
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DesktopProfileSummary {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) backend_url: String,
+    pub(crate) active: bool,
+    pub(crate) managed: bool,
+    pub(crate) attached: bool,
+}
+
+#[derive(Debug)]
+struct ProfileEntry {
+    profile: DesktopProfile,
+    state: Arc<BackendState>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ProfileRegistry {
+    entries: Vec<ProfileEntry>,
+    active: AtomicUsize,
+}
+
+impl ProfileRegistry {
+    pub(crate) fn new(profiles: Vec<DesktopProfile>, active_profile: Option<&str>) -> Self {
+        let exit_state = Arc::new(Mutex::new(ExitStateMachine::default()));
+        let entries: Vec<_> = profiles
+            .into_iter()
+            .map(|profile| {
+                let state = Arc::new(BackendState::new(
+                    profile.id.clone(),
+                    profile.backend_url.clone(),
+                    profile.launch_overrides.clone(),
+                    exit_state.clone(),
+                ));
+                ProfileEntry { profile, state }
+            })
+            .collect();
+        assert!(
+            !entries.is_empty(),
+            "profile registry requires at least the default profile"
+        );
+        let active = active_profile
+            .and_then(|id| entries.iter().position(|entry| entry.profile.id == id))
+            .unwrap_or(0);
+        Self {
+            entries,
+            active: AtomicUsize::new(active),
+        }
+    }
+
+    fn active_entry(&self) -> &ProfileEntry {
+        &self.entries[self.active.load(Ordering::Acquire)]
+    }
+
+    pub(crate) fn active_profile(&self) -> &DesktopProfile {
+        &self.active_entry().profile
+    }
+
+    pub(crate) fn active_state(&self) -> Arc<BackendState> {
+        self.active_entry().state.clone()
+    }
+
+    pub(crate) fn is_active(&self, profile_id: &str) -> bool {
+        self.active_profile().id == profile_id
+    }
+
+    pub(crate) fn state(&self, profile_id: &str) -> Option<Arc<BackendState>> {
+        self.entries
+            .iter()
+            .find(|entry| entry.profile.id == profile_id)
+            .map(|entry| entry.state.clone())
+    }
+
+    pub(crate) fn profiles(&self) -> impl Iterator<Item = &DesktopProfile> {
+        self.entries.iter().map(|entry| &entry.profile)
+    }
+
+    pub(crate) fn states(&self) -> impl Iterator<Item = &Arc<BackendState>> {
+        self.entries.iter().map(|entry| &entry.state)
+    }
+
+    pub(crate) fn has_multiple_profiles(&self) -> bool {
+        self.entries.len() > 1
+    }
+
+    pub(crate) fn set_active(&self, profile_id: &str) -> Result<Arc<BackendState>, String> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.profile.id == profile_id)
+            .ok_or_else(|| format!("Unknown profile '{profile_id}'."))?;
+        self.active.store(index, Ordering::Release);
+        Ok(self.entries[index].state.clone())
+    }
+
+    pub(crate) fn summaries(&self) -> Vec<DesktopProfileSummary> {
+        let active_id = &self.active_profile().id;
+        self.entries
+            .iter()
+            .map(|entry| DesktopProfileSummary {
+                id: entry.profile.id.clone(),
+                name: entry.profile.name.clone(),
+                backend_url: entry.state.backend_url(),
+                active: &entry.profile.id == active_id,
+                managed: entry
+                    .state
+                    .child
+                    .lock()
+                    .map(|guard| guard.is_some())
+                    .unwrap_or(false),
+                attached: entry.state.attached_backend().is_some(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ProfileRegistry;
+    use crate::{launch_plan::LaunchOverrides, profiles::config::DesktopProfile};
+
+    fn profile(id: &str, port: u16) -> DesktopProfile {
+        DesktopProfile {
+            id: id.to_string(),
+            name: id.to_string(),
+            backend_url: format!("http://127.0.0.1:{port}/"),
+            launch_overrides: LaunchOverrides::default(),
+            auto_start: false,
+        }
+    }
+
+    #[test]
+    fn registry_switches_active_profile_and_keeps_separate_states() {
+        let registry = ProfileRegistry::new(
+            vec![profile("default", 6185), profile("customer-a", 6190)],
+            Some("customer-a"),
+        );
+        assert!(registry.is_active("customer-a"));
+        assert_eq!(
+            registry.active_state().backend_url(),
+            "http://127.0.0.1:6190/"
+        );
+
+        let default_state = registry.set_active("default").expect("switch to default");
+        assert!(registry.is_active("default"));
+        assert!(Arc::ptr_eq(&default_state, &registry.active_state()));
+        assert!(!Arc::ptr_eq(
+            &default_state,
+            &registry.state("customer-a").expect("customer-a state")
+        ));
+        assert!(registry.set_active("missing").is_err());
+        assert!(registry.is_active("default"));
+    }
+
+    #[test]
+    fn registry_shares_exit_state_between_profiles() {
+        let registry = ProfileRegistry::new(
+            vec![profile("default", 6185), profile("customer-a", 6190)],
+            Some("unknown"),
+        );
+        assert!(registry.is_active("default"));
+
+        registry
+            .state("customer-a")
+            .expect("customer-a state")
+            .mark_quitting();
+        assert!(registry.active_state().is_quitting());
+    }
+}
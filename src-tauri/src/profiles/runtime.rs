@@ -0,0 +1,136 @@
+use std::env;
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    append_desktop_log, append_desktop_log_at, append_startup_log, append_startup_log_at, backend,
+    launch_plan::LaunchOverrides,
+    logging::DesktopLogLevel,
+    navigate_main_window_to_backend,
+    profiles::{config, registry::ProfileRegistry},
+    runtime_paths, ui_dispatch, window, DEFAULT_BACKEND_URL, DEFAULT_SHELL_LOCALE,
+    DESKTOP_PROFILE_ENV,
+};
+
+pub(crate) fn load_profile_registry() -> ProfileRegistry {
+    let packaged_root_dir = runtime_paths::default_packaged_root_dir();
+    let profiles_config = config::read_profiles_config(
+        packaged_root_dir.as_deref(),
+        &LaunchOverrides::from_env(),
+        &backend::config::backend_url_from_env(DEFAULT_BACKEND_URL),
+        |message| append_startup_log_at(DesktopLogLevel::Warn, &message),
+    );
+
+    let requested_profile = env::var(DESKTOP_PROFILE_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or(profiles_config.active_profile);
+    if let Some(profile_id) = requested_profile.as_deref() {
+        if !profiles_config
+            .profiles
+            .iter()
+            .any(|profile| profile.id == profile_id)
+        {
+            append_startup_log_at(
+                DesktopLogLevel::Warn,
+                &format!("unknown profile '{profile_id}', falling back to the default profile"),
+            );
+        }
+    }
+
+    let registry = ProfileRegistry::new(profiles_config.profiles, requested_profile.as_deref());
+    if registry.has_multiple_profiles() {
+        append_startup_log(&format!(
+            "loaded {} profiles, active profile: {}",
+            registry.profiles().count(),
+            registry.active_profile().id
+        ));
+    }
+    registry
+}
+
+pub(crate) fn switch_active_profile(
+    app_handle: &AppHandle,
+    profile_id: &str,
+) -> Result<(), String> {
+    let registry = app_handle.state::<ProfileRegistry>();
+    if registry.active_state().is_quitting() {
+        return Err("Desktop is quitting.".to_string());
+    }
+
+    if !registry.is_active(profile_id) {
+        registry.set_active(profile_id)?;
+        append_desktop_log(&format!("switched active profile to '{profile_id}'"));
+        if let Err(error) = config::write_active_profile(
+            profile_id,
+            runtime_paths::default_packaged_root_dir().as_deref(),
+        ) {
+            append_desktop_log_at(
+                DesktopLogLevel::Warn,
+                &format!("failed to persist active profile: {error}"),
+            );
+        }
+        spawn_profile_backend(app_handle.clone(), profile_id.to_string());
+    }
+
+    // Also refreshes the tray, which re-syncs the profile check marks.
+    window::actions::show_main_window(app_handle, DEFAULT_SHELL_LOCALE, append_desktop_log);
+    Ok(())
+}
+
+pub(crate) fn spawn_profile_backend(app_handle: AppHandle, profile_id: String) {
+    tauri::async_runtime::spawn(async move {
+        let worker_handle = app_handle.clone();
+        let worker_profile_id = profile_id.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || {
+            let state = worker_handle
+                .state::<ProfileRegistry>()
+                .state(&worker_profile_id)
+                .ok_or_else(|| format!("Unknown profile '{worker_profile_id}'."))?;
+            state.ensure_backend_ready(&worker_handle)
+        })
+        .await
+        .map_err(|error| format!("Profile backend task failed: {error}"))
+        .and_then(|result| result);
+
+        if let Err(error) = result {
+            append_desktop_log_at(
+                DesktopLogLevel::Error,
+                &format!("failed to start backend for profile '{profile_id}': {error}"),
+            );
+            return;
+        }
+        // The user may have switched again while this backend was starting.
+        if !app_handle.state::<ProfileRegistry>().is_active(&profile_id) {
+            append_desktop_log(&format!("backend for profile '{profile_id}' is ready"));
+            return;
+        }
+        if let Err(error) = ui_dispatch::run_on_main_thread_dispatch(
+            &app_handle,
+            "navigate profile backend",
+            move |main_app| {
+                if let Err(error) = navigate_main_window_to_backend(main_app) {
+                    append_desktop_log(&format!(
+                        "failed to navigate main window to profile backend: {error}"
+                    ));
+                }
+            },
+        ) {
+            append_desktop_log(&error);
+        }
+    });
+}
+
+pub(crate) fn spawn_auto_start_profiles(app_handle: &AppHandle) {
+    let registry = app_handle.state::<ProfileRegistry>();
+    for profile in registry.profiles() {
+        if profile.auto_start && !registry.is_active(&profile.id) {
+            append_startup_log(&format!(
+                "auto-starting backend for profile '{}'",
+                profile.id
+            ));
+            spawn_profile_backend(app_handle.clone(), profile.id.clone());
+        }
+    }
+}
@@ -1,16 +1,17 @@
-use std::sync::atomic::Ordering;
+use tauri::AppHandle;
 
-use tauri::{AppHandle, Manager};
-
-use crate::{BackendBridgeResult, BackendState};
+use crate::{
+    active_backend_state, append_desktop_log, tray, BackendBridgeResult, BackendState,
+    DEFAULT_SHELL_LOCALE,
+};
 
 fn do_restart_backend(app_handle: &AppHandle, auth_token: Option<&str>) -> Result<(), String> {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(app_handle);
     state.restart_backend(app_handle, auth_token)
 }
 
 pub fn is_backend_action_in_progress(state: &BackendState) -> bool {
-    state.is_spawning.load(Ordering::Relaxed) || state.is_restarting.load(Ordering::Relaxed)
+    state.backend_phase().is_busy()
 }
 
 pub async fn run_restart_backend_task(
@@ -18,11 +19,16 @@ pub async fn run_restart_backend_task(
     auth_token: Option<String>,
 ) -> BackendBridgeResult {
     let app_handle_for_worker = app_handle.clone();
-    match tauri::async_runtime::spawn_blocking(move || {
+    let result = tauri::async_runtime::spawn_blocking(move || {
         do_restart_backend(&app_handle_for_worker, auth_token.as_deref())
     })
-    .await
-    {
+    .await;
+    tray::labels::update_backend_status_label(
+        &app_handle,
+        DEFAULT_SHELL_LOCALE,
+        append_desktop_log,
+    );
+    match result {
         Ok(Ok(())) => BackendBridgeResult {
             ok: true,
             reason: None,
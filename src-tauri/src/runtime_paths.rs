@@ -2,7 +2,8 @@ use std::{
     env,
     path::{Path, PathBuf},
 };
-use tauri::{path::BaseDirectory, AppHandle, Manager};
+
+use tauri::PackageInfo;
 
 pub fn detect_astrbot_source_root() -> Option<PathBuf> {
     let explicit_source_dir = env::var("ASTRBOT_SOURCE_DIR")
@@ -15,23 +16,31 @@ pub fn default_packaged_root_dir() -> Option<PathBuf> {
     home::home_dir().map(|home| home.join(".astrbot"))
 }
 
-pub fn resolve_resource_path<F>(app: &AppHandle, relative_path: &str, log: F) -> Option<PathBuf>
+/// Resource directory of the packaged app, resolved without building the Tauri app.
+pub fn packaged_resource_dir(package_info: &PackageInfo) -> Option<PathBuf> {
+    tauri::utils::platform::resource_dir(package_info, &tauri::Env::default()).ok()
+}
+
+pub fn resolve_resource_path<F>(
+    resource_dir: Option<&Path>,
+    relative_path: &str,
+    log: F,
+) -> Option<PathBuf>
 where
     F: Fn(&str),
 {
-    if let Ok(path) = app.path().resolve(relative_path, BaseDirectory::Resource) {
+    if let Some(resource_dir) = resource_dir {
+        let path = resource_dir.join(relative_path);
         if path.exists() {
             return Some(path);
         }
-    }
 
-    let updater_resource = Path::new("_up_").join("resources").join(relative_path);
-    if let Ok(path) = app
-        .path()
-        .resolve(&updater_resource, BaseDirectory::Resource)
-    {
-        if path.exists() {
-            return Some(path);
+        let updater_resource = resource_dir
+            .join("_up_")
+            .join("resources")
+            .join(relative_path);
+        if updater_resource.exists() {
+            return Some(updater_resource);
         }
     }
 
@@ -139,4 +148,26 @@ mod tests {
         fs::remove_dir_all(&workspace).expect("cleanup workspace dir");
         fs::remove_dir_all(&explicit).expect("cleanup explicit dir");
     }
+
+    #[test]
+    fn resolve_resource_path_falls_back_to_updater_layout() {
+        let dir = create_temp_case_dir("resources");
+        let updater_dir = dir.join("_up_").join("resources").join("backend");
+        fs::create_dir_all(&updater_dir).expect("create updater resource dir");
+        File::create(updater_dir.join("runtime-manifest.json")).expect("create manifest");
+
+        assert_eq!(
+            resolve_resource_path(Some(&dir), "backend/runtime-manifest.json", |_| {}),
+            Some(updater_dir.join("runtime-manifest.json"))
+        );
+        assert_eq!(
+            resolve_resource_path(Some(&dir), "webui/index.html", |_| {}),
+            None
+        );
+        assert_eq!(
+            resolve_resource_path(None, "backend/runtime-manifest.json", |_| {}),
+            None
+        );
+        fs::remove_dir_all(&dir).expect("cleanup temp case dir");
+    }
 }
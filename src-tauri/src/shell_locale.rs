@@ -13,39 +13,69 @@ fn empty_state_object() -> Value {
 
 #[derive(Debug, Clone, Copy)]
 pub struct ShellTexts {
+    pub tray_backend_managed: &'static str,
+    pub tray_backend_attached: &'static str,
+    pub tray_backend_reattached: &'static str,
+    pub tray_backend_starting: &'static str,
+    pub tray_backend_stopped: &'static str,
     pub tray_hide: &'static str,
     pub tray_show: &'static str,
     pub tray_reload: &'static str,
     pub tray_restart_backend: &'static str,
+    pub tray_export_diagnostics: &'static str,
+    pub tray_profiles: &'static str,
+    pub tray_port_relocated: &'static str,
     pub tray_launch_at_login: &'static str,
     pub tray_silent_launch: &'static str,
     pub tray_close_to_tray: &'static str,
+    pub tray_detach_on_quit: &'static str,
     pub tray_quit: &'static str,
+    pub tray_stop_and_quit: &'static str,
 }
 
 pub fn shell_texts_for_locale(locale: &str) -> ShellTexts {
     if locale == "en-US" {
         return ShellTexts {
+            tray_backend_managed: "Backend: running",
+            tray_backend_attached: "Backend: attached (unmanaged)",
+            tray_backend_reattached: "Backend: running (kept from last session)",
+            tray_backend_starting: "Backend: starting…",
+            tray_backend_stopped: "Backend: not running",
             tray_hide: "Hide AstrBot",
             tray_show: "Show AstrBot",
             tray_reload: "Reload UI",
             tray_restart_backend: "Restart Backend",
+            tray_export_diagnostics: "Export Diagnostics",
+            tray_profiles: "Switch Profile",
+            tray_port_relocated: "Dashboard port {from} was in use, switched to {to}",
             tray_launch_at_login: "Launch at Login",
             tray_silent_launch: "Silent Launch",
             tray_close_to_tray: "Close to Tray",
+            tray_detach_on_quit: "Keep Bot Running After Quit",
             tray_quit: "Quit",
+            tray_stop_and_quit: "Stop Bot and Quit",
         };
     }
 
     ShellTexts {
+        tray_backend_managed: "后端：运行中",
+        tray_backend_attached: "后端：已附加（非托管）",
+        tray_backend_reattached: "后端：运行中（沿用上次会话）",
+        tray_backend_starting: "后端：启动中…",
+        tray_backend_stopped: "后端：未运行",
         tray_hide: "隐藏 AstrBot",
         tray_show: "显示 AstrBot",
         tray_reload: "重载界面",
         tray_restart_backend: "重启后端",
+        tray_export_diagnostics: "导出诊断包",
+        tray_profiles: "切换实例",
+        tray_port_relocated: "面板端口 {from} 已被占用，已改用 {to}",
         tray_launch_at_login: "开机自启",
         tray_silent_launch: "静默启动",
         tray_close_to_tray: "关闭到托盘",
+        tray_detach_on_quit: "退出后保持机器人运行",
         tray_quit: "退出",
+        tray_stop_and_quit: "停止机器人并退出",
     }
 }
 
@@ -205,6 +235,7 @@ mod tests {
         assert_eq!(texts.tray_silent_launch, "Silent Launch");
         assert_eq!(texts.tray_close_to_tray, "Close to Tray");
         assert_eq!(texts.tray_quit, "Quit");
+        assert_eq!(texts.tray_stop_and_quit, "Stop Bot and Quit");
     }
 
     #[test]
@@ -215,6 +246,7 @@ mod tests {
         assert_eq!(texts.tray_silent_launch, "静默启动");
         assert_eq!(texts.tray_close_to_tray, "关闭到托盘");
         assert_eq!(texts.tray_quit, "退出");
+        assert_eq!(texts.tray_stop_and_quit, "停止机器人并退出");
     }
 
     #[test]
@@ -1,22 +1,39 @@
 use tauri::{AppHandle, Manager};
 
-use crate::{navigate_main_window_to_backend, ui_dispatch, BackendState};
+use crate::{
+    backend::startup_cancel::BACKEND_STARTUP_CANCELLED, navigate_main_window_to_backend,
+    profiles::registry::ProfileRegistry, ui_dispatch,
+};
 
 pub fn spawn_startup_task<F>(app_handle: AppHandle, log: F)
 where
     F: Fn(&str) + Copy + Send + 'static,
 {
     let startup_app_handle = app_handle.clone();
+    let startup_state = app_handle.state::<ProfileRegistry>().active_state();
+    let profile_id = startup_state.profile_id.clone();
     tauri::async_runtime::spawn(async move {
         let startup_worker_handle = startup_app_handle.clone();
         let startup_result = tauri::async_runtime::spawn_blocking(move || {
-            let state = startup_worker_handle.state::<BackendState>();
-            state.ensure_backend_ready(&startup_worker_handle)
+            startup_state.ensure_backend_ready(&startup_worker_handle)
         })
         .await
         .map_err(|error| format!("Backend startup task failed: {error}"))
         .and_then(|result| result);
 
+        // A profile switch during startup hands the window over to that profile's task.
+        if !startup_app_handle
+            .state::<ProfileRegistry>()
+            .is_active(&profile_id)
+        {
+            if let Err(error) = startup_result {
+                log(&format!(
+                    "startup backend for profile '{profile_id}' failed after switching away: {error}"
+                ));
+            }
+            return;
+        }
+
         match startup_result {
             Ok(()) => {
                 if let Err(error) = ui_dispatch::run_on_main_thread_dispatch(
@@ -36,6 +53,8 @@ where
                     );
                 }
             }
+            // Cancelled by quit or an explicit stop; there is nothing to report.
+            Err(error) if error == BACKEND_STARTUP_CANCELLED => log(&error),
             Err(error) => {
                 ui_dispatch::show_startup_error_on_main_thread(&startup_app_handle, &error, log);
             }
@@ -1,20 +1,29 @@
 pub const TRAY_MENU_TOGGLE_WINDOW: &str = "tray_toggle_window";
 pub const TRAY_MENU_RELOAD_WINDOW: &str = "tray_reload_window";
 pub const TRAY_MENU_RESTART_BACKEND: &str = "tray_restart_backend";
+pub const TRAY_MENU_EXPORT_DIAGNOSTICS: &str = "tray_export_diagnostics";
 pub const TRAY_MENU_LAUNCH_AT_LOGIN: &str = "tray_launch_at_login";
 pub const TRAY_MENU_SILENT_LAUNCH: &str = "tray_silent_launch";
 pub const TRAY_MENU_CLOSE_TO_TRAY: &str = "tray_close_to_tray";
+pub const TRAY_MENU_DETACH_ON_QUIT: &str = "tray_detach_on_quit";
 pub const TRAY_MENU_QUIT: &str = "tray_quit";
+pub const TRAY_MENU_STOP_AND_QUIT: &str = "tray_stop_and_quit";
+pub const TRAY_MENU_PROFILES: &str = "tray_profiles";
+pub const TRAY_MENU_BACKEND_STATUS: &str = "tray_backend_status";
+const TRAY_MENU_PROFILE_PREFIX: &str = "tray_profile:";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrayMenuAction {
     ToggleWindow,
     ReloadWindow,
     RestartBackend,
+    ExportDiagnostics,
     LaunchAtLogin,
     SilentLaunch,
     CloseToTray,
+    DetachOnQuit,
     Quit,
+    StopAndQuit,
 }
 
 pub fn action_from_menu_id(menu_id: &str) -> Option<TrayMenuAction> {
@@ -22,14 +31,27 @@ pub fn action_from_menu_id(menu_id: &str) -> Option<TrayMenuAction> {
         TRAY_MENU_TOGGLE_WINDOW => Some(TrayMenuAction::ToggleWindow),
         TRAY_MENU_RELOAD_WINDOW => Some(TrayMenuAction::ReloadWindow),
         TRAY_MENU_RESTART_BACKEND => Some(TrayMenuAction::RestartBackend),
+        TRAY_MENU_EXPORT_DIAGNOSTICS => Some(TrayMenuAction::ExportDiagnostics),
         TRAY_MENU_LAUNCH_AT_LOGIN => Some(TrayMenuAction::LaunchAtLogin),
         TRAY_MENU_SILENT_LAUNCH => Some(TrayMenuAction::SilentLaunch),
         TRAY_MENU_CLOSE_TO_TRAY => Some(TrayMenuAction::CloseToTray),
+        TRAY_MENU_DETACH_ON_QUIT => Some(TrayMenuAction::DetachOnQuit),
         TRAY_MENU_QUIT => Some(TrayMenuAction::Quit),
+        TRAY_MENU_STOP_AND_QUIT => Some(TrayMenuAction::StopAndQuit),
         _ => None,
     }
 }
 
+pub fn profile_menu_id(profile_id: &str) -> String {
+    format!("{TRAY_MENU_PROFILE_PREFIX}{profile_id}")
+}
+
+pub fn profile_id_from_menu_id(menu_id: &str) -> Option<&str> {
+    menu_id
+        .strip_prefix(TRAY_MENU_PROFILE_PREFIX)
+        .filter(|profile_id| !profile_id.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +70,10 @@ mod tests {
             action_from_menu_id(TRAY_MENU_RESTART_BACKEND),
             Some(TrayMenuAction::RestartBackend)
         );
+        assert_eq!(
+            action_from_menu_id(TRAY_MENU_EXPORT_DIAGNOSTICS),
+            Some(TrayMenuAction::ExportDiagnostics)
+        );
         assert_eq!(
             action_from_menu_id(TRAY_MENU_QUIT),
             Some(TrayMenuAction::Quit)
@@ -64,10 +90,27 @@ mod tests {
             action_from_menu_id(TRAY_MENU_CLOSE_TO_TRAY),
             Some(TrayMenuAction::CloseToTray)
         );
+        assert_eq!(
+            action_from_menu_id(TRAY_MENU_DETACH_ON_QUIT),
+            Some(TrayMenuAction::DetachOnQuit)
+        );
+        assert_eq!(
+            action_from_menu_id(TRAY_MENU_STOP_AND_QUIT),
+            Some(TrayMenuAction::StopAndQuit)
+        );
     }
 
     #[test]
     fn action_from_menu_id_returns_none_for_unknown_menu_id() {
         assert_eq!(action_from_menu_id("unknown-menu"), None);
     }
+
+    #[test]
+    fn profile_menu_ids_round_trip() {
+        let menu_id = profile_menu_id("customer-a");
+        assert_eq!(profile_id_from_menu_id(&menu_id), Some("customer-a"));
+        assert_eq!(action_from_menu_id(&menu_id), None);
+        assert_eq!(profile_id_from_menu_id("tray_profile:"), None);
+        assert_eq!(profile_id_from_menu_id(TRAY_MENU_QUIT), None);
+    }
 }
@@ -3,7 +3,14 @@ use tauri::{
     AppHandle, Manager,
 };
 
-use crate::{runtime_paths, shell_locale, tray::actions, TrayMenuState};
+use crate::{
+    active_backend_state,
+    backend::{attach::BackendOwnership, port::PortRelocation, state_machine::BackendPhase},
+    profiles::registry::ProfileRegistry,
+    runtime_paths, shell_locale,
+    tray::actions,
+    TrayMenuState, TRAY_ID,
+};
 
 fn set_menu_text_safe<F>(item: &MenuItem<tauri::Wry>, text: &str, item_name: &str, log: F)
 where
@@ -33,6 +40,75 @@ fn set_check_menu_text_safe<F>(
     }
 }
 
+pub fn show_port_relocation_tooltip<F>(
+    app_handle: &AppHandle,
+    default_shell_locale: &'static str,
+    relocation: PortRelocation,
+    log: F,
+) where
+    F: Fn(&str),
+{
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let locale = shell_locale::resolve_shell_locale(
+        default_shell_locale,
+        runtime_paths::default_packaged_root_dir(),
+    );
+    let notice = shell_locale::shell_texts_for_locale(locale)
+        .tray_port_relocated
+        .replace("{from}", &relocation.from.to_string())
+        .replace("{to}", &relocation.to.to_string());
+    if let Err(error) = tray.set_tooltip(Some(format!("AstrBot\n{notice}"))) {
+        log(&format!("failed to update tray tooltip: {error}"));
+    }
+}
+
+fn backend_status_text(
+    shell_texts: &shell_locale::ShellTexts,
+    ownership: BackendOwnership,
+    spawning: bool,
+) -> &'static str {
+    match ownership {
+        BackendOwnership::Managed => shell_texts.tray_backend_managed,
+        BackendOwnership::Attached => shell_texts.tray_backend_attached,
+        BackendOwnership::Reattached => shell_texts.tray_backend_reattached,
+        BackendOwnership::Absent if spawning => shell_texts.tray_backend_starting,
+        BackendOwnership::Absent => shell_texts.tray_backend_stopped,
+    }
+}
+
+pub fn update_backend_status_label<F>(
+    app_handle: &AppHandle,
+    default_shell_locale: &'static str,
+    log: F,
+) where
+    F: Fn(&str),
+{
+    let Some(tray_state) = app_handle.try_state::<TrayMenuState>() else {
+        return;
+    };
+    let locale = shell_locale::resolve_shell_locale(
+        default_shell_locale,
+        runtime_paths::default_packaged_root_dir(),
+    );
+    let shell_texts = shell_locale::shell_texts_for_locale(locale);
+    let state = active_backend_state(app_handle);
+    set_menu_text_safe(
+        &tray_state.backend_status_item,
+        backend_status_text(
+            &shell_texts,
+            state.backend_ownership(),
+            matches!(
+                state.backend_phase(),
+                BackendPhase::Spawning | BackendPhase::WaitingForReady
+            ),
+        ),
+        actions::TRAY_MENU_BACKEND_STATUS,
+        &log,
+    );
+}
+
 pub fn update_tray_menu_labels<F>(
     app_handle: &AppHandle,
     default_shell_locale: &'static str,
@@ -75,6 +151,7 @@ pub fn update_tray_menu_labels_with_visibility<F>(
         shell_texts.tray_show
     };
 
+    update_backend_status_label(app_handle, default_shell_locale, &log);
     set_menu_text_safe(
         &tray_state.toggle_item,
         toggle_label,
@@ -93,6 +170,12 @@ pub fn update_tray_menu_labels_with_visibility<F>(
         actions::TRAY_MENU_RESTART_BACKEND,
         &log,
     );
+    set_menu_text_safe(
+        &tray_state.export_diagnostics_item,
+        shell_texts.tray_export_diagnostics,
+        actions::TRAY_MENU_EXPORT_DIAGNOSTICS,
+        &log,
+    );
     set_check_menu_text_safe(
         &tray_state.launch_at_login_item,
         shell_texts.tray_launch_at_login,
@@ -111,10 +194,68 @@ pub fn update_tray_menu_labels_with_visibility<F>(
         actions::TRAY_MENU_CLOSE_TO_TRAY,
         &log,
     );
+    set_check_menu_text_safe(
+        &tray_state.detach_on_quit_item,
+        shell_texts.tray_detach_on_quit,
+        actions::TRAY_MENU_DETACH_ON_QUIT,
+        &log,
+    );
     set_menu_text_safe(
         &tray_state.quit_item,
         shell_texts.tray_quit,
         actions::TRAY_MENU_QUIT,
         &log,
     );
+    set_menu_text_safe(
+        &tray_state.stop_and_quit_item,
+        shell_texts.tray_stop_and_quit,
+        actions::TRAY_MENU_STOP_AND_QUIT,
+        &log,
+    );
+
+    if let Some(submenu) = tray_state.profiles_submenu.as_ref() {
+        if let Err(error) = submenu.set_text(shell_texts.tray_profiles) {
+            log(&format!(
+                "failed to update tray menu text for {}: {}",
+                actions::TRAY_MENU_PROFILES,
+                error
+            ));
+        }
+    }
+    let registry = app_handle.state::<ProfileRegistry>();
+    for (profile_id, item) in &tray_state.profile_items {
+        if let Err(error) = item.set_checked(registry.is_active(profile_id)) {
+            log(&format!(
+                "failed to update tray menu check state for profile {}: {}",
+                profile_id, error
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backend_status_text;
+    use crate::{backend::attach::BackendOwnership, shell_locale};
+
+    #[test]
+    fn backend_status_text_reports_attached_backend_as_unmanaged() {
+        let texts = shell_locale::shell_texts_for_locale("en-US");
+        assert_eq!(
+            backend_status_text(&texts, BackendOwnership::Attached, false),
+            "Backend: attached (unmanaged)"
+        );
+        assert_eq!(
+            backend_status_text(&texts, BackendOwnership::Reattached, false),
+            "Backend: running (kept from last session)"
+        );
+        assert_eq!(
+            backend_status_text(&texts, BackendOwnership::Absent, true),
+            "Backend: starting…"
+        );
+        assert_eq!(
+            backend_status_text(&texts, BackendOwnership::Managed, false),
+            "Backend: running"
+        );
+    }
 }
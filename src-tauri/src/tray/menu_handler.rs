@@ -1,11 +1,17 @@
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+};
+
 use tauri::{AppHandle, Manager};
 use tauri_plugin_autostart::ManagerExt;
 
 use crate::{
-    append_desktop_log, append_restart_log, desktop_settings, lifecycle, restart_backend_flow,
-    runtime_paths,
-    tray::{actions, bridge_event},
-    ui_dispatch, window, BackendState, DesktopSettingsCache, TrayMenuState, DEFAULT_SHELL_LOCALE,
+    active_backend_state, append_desktop_log, append_restart_log,
+    desktop_events::{self, DesktopEvent},
+    desktop_settings, lifecycle, profiles, restart_backend_flow, runtime_paths,
+    tray::{actions, bridge_event, labels},
+    ui_dispatch, window, DesktopSettingsCache, TrayMenuState, DEFAULT_SHELL_LOCALE,
     TRAY_RESTART_BACKEND_EVENT,
 };
 
@@ -23,6 +29,53 @@ fn decide_tray_restart(backend_action_in_progress: bool) -> TrayRestartDecision
     }
 }
 
+#[cfg(target_os = "macos")]
+fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|error| format!("Failed to run 'open': {error}"))
+}
+
+#[cfg(target_os = "windows")]
+fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+
+    // explorer does not understand a quoted "/select,<path>" argument, so pass it raw.
+    Command::new("explorer")
+        .raw_arg(format!("/select,\"{}\"", path.display()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|error| format!("Failed to run 'explorer': {error}"))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    // xdg-open cannot select a file, so open the folder that holds it.
+    let folder = path.parent().unwrap_or(path);
+    Command::new("xdg-open")
+        .arg(folder)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|error| format!("Failed to run 'xdg-open': {error}"))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", unix)))]
+fn reveal_in_file_manager(_path: &Path) -> Result<(), String> {
+    Err("Revealing files is not supported on this platform.".to_string())
+}
+
 fn set_checked_safe(item: &tauri::menu::CheckMenuItem<tauri::Wry>, checked: bool, item_name: &str) {
     if let Err(error) = item.set_checked(checked) {
         append_desktop_log(&format!(
@@ -50,6 +103,7 @@ fn persist_bool_setting_and_update_tray(
                 .state::<DesktopSettingsCache>()
                 .set(updated_settings);
             set_checked_safe(item, new_value, item_name);
+            desktop_events::publish(None, DesktopEvent::SettingsChanged { key: key.as_str() });
         }
         Err(error) => {
             append_desktop_log(&format!(
@@ -140,7 +194,37 @@ fn handle_close_to_tray_toggle(app_handle: &AppHandle) {
     );
 }
 
+fn handle_detach_on_quit_toggle(app_handle: &AppHandle) {
+    let Some(tray_state) = app_handle.try_state::<TrayMenuState>() else {
+        return;
+    };
+    let current_settings = app_handle.state::<DesktopSettingsCache>().get();
+    persist_bool_setting_and_update_tray(
+        app_handle,
+        desktop_settings::DesktopSettingKey::DetachOnQuit,
+        !current_settings.detach_on_quit,
+        current_settings.detach_on_quit,
+        &tray_state.detach_on_quit_item,
+        actions::TRAY_MENU_DETACH_ON_QUIT,
+    );
+}
+
+fn handle_profile_switch(app_handle: &AppHandle, profile_id: &str) {
+    if let Err(error) = profiles::runtime::switch_active_profile(app_handle, profile_id) {
+        append_desktop_log(&format!(
+            "failed to switch to profile '{profile_id}' from tray menu: {error}"
+        ));
+        // The clicked item toggled itself; put the check marks back.
+        labels::update_tray_menu_labels(app_handle, DEFAULT_SHELL_LOCALE, append_desktop_log);
+    }
+}
+
 pub fn handle_tray_menu_event(app_handle: &AppHandle, menu_id: &str) {
+    if let Some(profile_id) = actions::profile_id_from_menu_id(menu_id) {
+        handle_profile_switch(app_handle, profile_id);
+        return;
+    }
+
     match actions::action_from_menu_id(menu_id) {
         Some(actions::TrayMenuAction::ToggleWindow) => window::actions::toggle_main_window(
             app_handle,
@@ -151,7 +235,7 @@ pub fn handle_tray_menu_event(app_handle: &AppHandle, menu_id: &str) {
             window::actions::reload_main_window(app_handle, append_desktop_log)
         }
         Some(actions::TrayMenuAction::RestartBackend) => {
-            let state = app_handle.state::<BackendState>();
+            let state = active_backend_state(app_handle);
             match decide_tray_restart(restart_backend_flow::is_backend_action_in_progress(&state)) {
                 TrayRestartDecision::IgnoreBecauseBackendActionInProgress => {
                     append_restart_log("tray restart ignored: backend action already in progress");
@@ -191,12 +275,35 @@ pub fn handle_tray_menu_event(app_handle: &AppHandle, menu_id: &str) {
                 }
             });
         }
+        Some(actions::TrayMenuAction::ExportDiagnostics) => {
+            let app_handle_cloned = app_handle.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                let state = active_backend_state(&app_handle_cloned);
+                match state.export_diagnostic_bundle(&app_handle_cloned) {
+                    Ok(path) => {
+                        if let Err(error) = reveal_in_file_manager(&path) {
+                            append_desktop_log(&format!(
+                                "failed to reveal diagnostic bundle {}: {error}",
+                                path.display()
+                            ));
+                        }
+                    }
+                    Err(error) => append_desktop_log(&format!(
+                        "diagnostic bundle export from tray menu failed: {error}"
+                    )),
+                }
+            });
+        }
         Some(actions::TrayMenuAction::LaunchAtLogin) => handle_launch_at_login_toggle(app_handle),
         Some(actions::TrayMenuAction::SilentLaunch) => handle_silent_launch_toggle(app_handle),
         Some(actions::TrayMenuAction::CloseToTray) => handle_close_to_tray_toggle(app_handle),
+        Some(actions::TrayMenuAction::DetachOnQuit) => handle_detach_on_quit_toggle(app_handle),
         Some(actions::TrayMenuAction::Quit) => {
             lifecycle::events::handle_tray_quit(app_handle);
         }
+        Some(actions::TrayMenuAction::StopAndQuit) => {
+            lifecycle::events::handle_tray_stop_and_quit(app_handle);
+        }
         None => {}
     }
 }
@@ -1,16 +1,65 @@
 use tauri::{
-    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Manager,
 };
 use tauri_plugin_autostart::ManagerExt;
 
 use crate::{
-    append_desktop_log, runtime_paths, shell_locale,
+    append_desktop_log,
+    profiles::registry::ProfileRegistry,
+    runtime_paths, shell_locale,
     tray::{actions, labels, menu_handler},
     window, TrayMenuState, DEFAULT_SHELL_LOCALE, TRAY_ID,
 };
 
+type ProfileMenu = (
+    Submenu<tauri::Wry>,
+    Vec<(String, CheckMenuItem<tauri::Wry>)>,
+);
+
+fn build_profiles_submenu(
+    app_handle: &AppHandle,
+    title: &str,
+) -> Result<Option<ProfileMenu>, String> {
+    let registry = app_handle.state::<ProfileRegistry>();
+    if !registry.has_multiple_profiles() {
+        return Ok(None);
+    }
+
+    let mut profile_items = Vec::new();
+    for profile in registry.profiles() {
+        let item = CheckMenuItem::with_id(
+            app_handle,
+            actions::profile_menu_id(&profile.id),
+            &profile.name,
+            true,
+            registry.is_active(&profile.id),
+            None::<&str>,
+        )
+        .map_err(|error| {
+            format!(
+                "Failed to create tray profile menu item for {}: {error}",
+                profile.id
+            )
+        })?;
+        profile_items.push((profile.id.clone(), item));
+    }
+    let item_refs: Vec<&dyn IsMenuItem<tauri::Wry>> = profile_items
+        .iter()
+        .map(|(_, item)| item as &dyn IsMenuItem<tauri::Wry>)
+        .collect();
+    let submenu = Submenu::with_id_and_items(
+        app_handle,
+        actions::TRAY_MENU_PROFILES,
+        title,
+        true,
+        &item_refs,
+    )
+    .map_err(|error| format!("Failed to create tray profiles submenu: {error}"))?;
+    Ok(Some((submenu, profile_items)))
+}
+
 pub fn setup_tray(app_handle: &AppHandle) -> Result<(), String> {
     let locale = shell_locale::resolve_shell_locale(
         DEFAULT_SHELL_LOCALE,
@@ -32,6 +81,16 @@ pub fn setup_tray(app_handle: &AppHandle) -> Result<(), String> {
         shell_texts.tray_show
     };
 
+    let backend_status_item = MenuItem::with_id(
+        app_handle,
+        actions::TRAY_MENU_BACKEND_STATUS,
+        shell_texts.tray_backend_stopped,
+        false,
+        None::<&str>,
+    )
+    .map_err(|error| format!("Failed to create tray backend status menu item: {error}"))?;
+    let status_separator = PredefinedMenuItem::separator(app_handle)
+        .map_err(|error| format!("Failed to create tray status separator menu item: {error}"))?;
     let toggle_item = MenuItem::with_id(
         app_handle,
         actions::TRAY_MENU_TOGGLE_WINDOW,
@@ -56,6 +115,14 @@ pub fn setup_tray(app_handle: &AppHandle) -> Result<(), String> {
         None::<&str>,
     )
     .map_err(|error| format!("Failed to create tray restart menu item: {error}"))?;
+    let export_diagnostics_item = MenuItem::with_id(
+        app_handle,
+        actions::TRAY_MENU_EXPORT_DIAGNOSTICS,
+        shell_texts.tray_export_diagnostics,
+        true,
+        None::<&str>,
+    )
+    .map_err(|error| format!("Failed to create tray export diagnostics menu item: {error}"))?;
     let launch_at_login_item = CheckMenuItem::with_id(
         app_handle,
         actions::TRAY_MENU_LAUNCH_AT_LOGIN,
@@ -83,6 +150,15 @@ pub fn setup_tray(app_handle: &AppHandle) -> Result<(), String> {
         None::<&str>,
     )
     .map_err(|error| format!("Failed to create tray close to tray menu item: {error}"))?;
+    let detach_on_quit_item = CheckMenuItem::with_id(
+        app_handle,
+        actions::TRAY_MENU_DETACH_ON_QUIT,
+        shell_texts.tray_detach_on_quit,
+        true,
+        desktop_settings.detach_on_quit,
+        None::<&str>,
+    )
+    .map_err(|error| format!("Failed to create tray detach on quit menu item: {error}"))?;
     let quit_item = MenuItem::with_id(
         app_handle,
         actions::TRAY_MENU_QUIT,
@@ -91,35 +167,62 @@ pub fn setup_tray(app_handle: &AppHandle) -> Result<(), String> {
         None::<&str>,
     )
     .map_err(|error| format!("Failed to create tray quit menu item: {error}"))?;
+    let stop_and_quit_item = MenuItem::with_id(
+        app_handle,
+        actions::TRAY_MENU_STOP_AND_QUIT,
+        shell_texts.tray_stop_and_quit,
+        true,
+        None::<&str>,
+    )
+    .map_err(|error| format!("Failed to create tray stop and quit menu item: {error}"))?;
     let separator = PredefinedMenuItem::separator(app_handle)
         .map_err(|error| format!("Failed to create tray separator menu item: {error}"))?;
     let settings_separator = PredefinedMenuItem::separator(app_handle)
         .map_err(|error| format!("Failed to create tray settings separator menu item: {error}"))?;
+    let (profiles_submenu, profile_items) =
+        match build_profiles_submenu(app_handle, shell_texts.tray_profiles)? {
+            Some((submenu, items)) => (Some(submenu), items),
+            None => (None, Vec::new()),
+        };
 
-    let menu = Menu::with_items(
-        app_handle,
-        &[
-            &toggle_item,
-            &reload_item,
-            &restart_backend_item,
-            &settings_separator,
-            &launch_at_login_item,
-            &silent_launch_item,
-            &close_to_tray_item,
-            &separator,
-            &quit_item,
-        ],
-    )
-    .map_err(|error| format!("Failed to build tray menu: {error}"))?;
+    let mut menu_items: Vec<&dyn IsMenuItem<tauri::Wry>> = vec![
+        &backend_status_item,
+        &status_separator,
+        &toggle_item,
+        &reload_item,
+        &restart_backend_item,
+        &export_diagnostics_item,
+    ];
+    if let Some(submenu) = profiles_submenu.as_ref() {
+        menu_items.push(submenu);
+    }
+    menu_items.extend([
+        &settings_separator as &dyn IsMenuItem<tauri::Wry>,
+        &launch_at_login_item,
+        &silent_launch_item,
+        &close_to_tray_item,
+        &detach_on_quit_item,
+        &separator,
+        &quit_item,
+        &stop_and_quit_item,
+    ]);
+    let menu = Menu::with_items(app_handle, &menu_items)
+        .map_err(|error| format!("Failed to build tray menu: {error}"))?;
 
     if !app_handle.manage(TrayMenuState {
+        backend_status_item: backend_status_item.clone(),
         toggle_item: toggle_item.clone(),
         reload_item: reload_item.clone(),
         restart_backend_item: restart_backend_item.clone(),
+        export_diagnostics_item: export_diagnostics_item.clone(),
+        profiles_submenu: profiles_submenu.clone(),
+        profile_items,
         launch_at_login_item: launch_at_login_item.clone(),
         silent_launch_item: silent_launch_item.clone(),
         close_to_tray_item: close_to_tray_item.clone(),
+        detach_on_quit_item: detach_on_quit_item.clone(),
         quit_item: quit_item.clone(),
+        stop_and_quit_item: stop_and_quit_item.clone(),
     }) {
         append_desktop_log("tray menu state already exists, skipping manage");
     }
@@ -0,0 +1,227 @@
+#[cfg(unix)]
+mod platform {
+    use std::{
+        io, mem, ptr,
+        sync::{
+            atomic::{AtomicI32, AtomicUsize, Ordering},
+            OnceLock,
+        },
+        thread,
+    };
+
+    use tauri::{AppHandle, Manager};
+
+    use crate::{
+        active_backend_state, append_shutdown_log,
+        desktop_events::{self, DesktopEvent},
+        lifecycle::cleanup::{self, ExitTrigger},
+        profiles::registry::ProfileRegistry,
+    };
+
+    const HANDLED_SIGNALS: [libc::c_int; 3] = [libc::SIGTERM, libc::SIGINT, libc::SIGHUP];
+
+    type SignalHandler = Box<dyn Fn(String) + Send + Sync>;
+
+    static SIGNAL_HANDLER: OnceLock<SignalHandler> = OnceLock::new();
+    static SIGNAL_PIPE_WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+    static SIGNALS_RECEIVED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(super) enum SignalDisposition {
+        StopBackendThenExit,
+        ForceExit,
+    }
+
+    pub(super) fn signal_disposition(previous_signals: usize) -> SignalDisposition {
+        if previous_signals == 0 {
+            SignalDisposition::StopBackendThenExit
+        } else {
+            SignalDisposition::ForceExit
+        }
+    }
+
+    pub(super) fn signal_name(signal: libc::c_int) -> String {
+        match signal {
+            libc::SIGTERM => "SIGTERM".to_string(),
+            libc::SIGINT => "SIGINT".to_string(),
+            libc::SIGHUP => "SIGHUP".to_string(),
+            other => format!("signal {other}"),
+        }
+    }
+
+    // Only async-signal-safe work here: hand the signal number to the watcher thread.
+    extern "C" fn forward_signal(signal: libc::c_int) {
+        let fd = SIGNAL_PIPE_WRITE_FD.load(Ordering::Relaxed);
+        if fd >= 0 {
+            let byte = signal as u8;
+            unsafe {
+                libc::write(fd, (&byte as *const u8).cast(), 1);
+            }
+        }
+    }
+
+    fn set_fd_flags(fd: libc::c_int, nonblocking: bool) {
+        unsafe {
+            libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            if nonblocking {
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+                libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+            }
+        }
+    }
+
+    pub(crate) fn install(app_handle: &AppHandle) {
+        let app_handle = app_handle.clone();
+        install_handler(move |name| stop_desktop_after_signal(&app_handle, name));
+    }
+
+    /// Runs `handler` on the watcher thread for the first signal; a second one
+    /// force-exits the process.
+    pub(crate) fn install_handler(handler: impl Fn(String) + Send + Sync + 'static) {
+        if SIGNAL_HANDLER.set(Box::new(handler)).is_err() {
+            return;
+        }
+
+        let mut fds = [0 as libc::c_int; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            append_shutdown_log(&format!(
+                "Unix signal handler skipped: pipe failed: {}",
+                io::Error::last_os_error()
+            ));
+            return;
+        }
+        let [read_fd, write_fd] = fds;
+        set_fd_flags(read_fd, false);
+        set_fd_flags(write_fd, true);
+
+        if let Err(error) = thread::Builder::new()
+            .name("astrbot-signal-watcher".to_string())
+            .spawn(move || watch_signals(read_fd))
+        {
+            append_shutdown_log(&format!("Unix signal handler skipped: {error}"));
+            return;
+        }
+        SIGNAL_PIPE_WRITE_FD.store(write_fd, Ordering::Relaxed);
+
+        for signal in HANDLED_SIGNALS {
+            let installed = unsafe {
+                let mut action: libc::sigaction = mem::zeroed();
+                action.sa_sigaction = forward_signal as *const () as usize;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                libc::sigaction(signal, &action, ptr::null_mut()) == 0
+            };
+            if !installed {
+                append_shutdown_log(&format!(
+                    "failed to install {} handler: {}",
+                    signal_name(signal),
+                    io::Error::last_os_error()
+                ));
+            }
+        }
+        append_shutdown_log("Unix signal handler installed for SIGTERM, SIGINT and SIGHUP");
+    }
+
+    fn watch_signals(read_fd: libc::c_int) {
+        loop {
+            let mut byte = 0_u8;
+            let read = unsafe { libc::read(read_fd, (&mut byte as *mut u8).cast(), 1) };
+            if read == 1 {
+                handle_signal(libc::c_int::from(byte));
+                continue;
+            }
+            let error = io::Error::last_os_error();
+            if read < 0 && error.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            append_shutdown_log(&format!("Unix signal watcher stopped: {error}"));
+            return;
+        }
+    }
+
+    fn handle_signal(signal: libc::c_int) {
+        let name = signal_name(signal);
+        let previous_signals = SIGNALS_RECEIVED.fetch_add(1, Ordering::SeqCst);
+        if signal_disposition(previous_signals) == SignalDisposition::ForceExit {
+            append_shutdown_log(&format!(
+                "received {name} again while shutting down, forcing exit"
+            ));
+            std::process::exit(128 + signal);
+        }
+
+        let Some(handler) = SIGNAL_HANDLER.get() else {
+            append_shutdown_log(&format!(
+                "received {name} before a handler was set, exiting"
+            ));
+            std::process::exit(128 + signal);
+        };
+        append_shutdown_log(&format!("received {name}, stopping backend before exit"));
+        handler(name);
+    }
+
+    fn stop_desktop_after_signal(app_handle: &AppHandle, name: String) {
+        let state = active_backend_state(app_handle);
+        state.mark_quitting();
+        if !cleanup::try_begin_exit_cleanup(&state, ExitTrigger::Signal, append_shutdown_log) {
+            append_shutdown_log("send the signal again to force exit");
+            return;
+        }
+        desktop_events::publish(
+            None,
+            DesktopEvent::ExitRequested {
+                trigger: ExitTrigger::Signal.as_str(),
+            },
+        );
+
+        // Keep the watcher free so a second signal can still force the exit.
+        let app_handle = app_handle.clone();
+        thread::spawn(move || {
+            let registry = app_handle.state::<ProfileRegistry>();
+            // Signals never detach, but reattached backends still need their own stop path.
+            cleanup::stop_backends_for_exit(
+                &app_handle,
+                false,
+                registry.states(),
+                ExitTrigger::Signal,
+                append_shutdown_log,
+            );
+            append_shutdown_log(&format!(
+                "backend stop after {name} finished, exiting desktop process"
+            ));
+            registry.active_state().allow_next_exit_request();
+            app_handle.exit(0);
+        });
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use platform::{install, install_handler};
+
+#[cfg(not(unix))]
+pub(crate) fn install(_app_handle: &tauri::AppHandle) {}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::platform::{signal_disposition, signal_name, SignalDisposition};
+
+    #[test]
+    fn only_the_first_signal_stops_the_backend_gracefully() {
+        assert_eq!(
+            signal_disposition(0),
+            SignalDisposition::StopBackendThenExit
+        );
+        assert_eq!(signal_disposition(1), SignalDisposition::ForceExit);
+        assert_eq!(signal_disposition(5), SignalDisposition::ForceExit);
+    }
+
+    #[test]
+    fn signal_names_cover_handled_signals() {
+        assert_eq!(signal_name(libc::SIGTERM), "SIGTERM");
+        assert_eq!(signal_name(libc::SIGINT), "SIGINT");
+        assert_eq!(signal_name(libc::SIGHUP), "SIGHUP");
+        assert_eq!(
+            signal_name(libc::SIGUSR1),
+            format!("signal {}", libc::SIGUSR1)
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use tauri::{AppHandle, Manager};
 
-use crate::{tray, window::main_window};
+use crate::{
+    desktop_events::{self, DesktopEvent},
+    runtime_paths, shell_locale, tray,
+    window::main_window,
+};
 
 pub fn show_main_window<F>(app_handle: &AppHandle, default_shell_locale: &'static str, log: F)
 where
@@ -46,6 +50,24 @@ where
     }
 }
 
+pub fn set_shell_locale<F>(
+    app_handle: &AppHandle,
+    locale: Option<&str>,
+    default_shell_locale: &'static str,
+    log: F,
+) -> Result<(), String>
+where
+    F: Fn(&str),
+{
+    shell_locale::write_cached_shell_locale(
+        locale,
+        runtime_paths::default_packaged_root_dir().as_deref(),
+    )?;
+    tray::labels::update_tray_menu_labels(app_handle, default_shell_locale, log);
+    desktop_events::publish(None, DesktopEvent::SettingsChanged { key: "shellLocale" });
+    Ok(())
+}
+
 pub fn reload_main_window<F>(app_handle: &AppHandle, log: F)
 where
     F: Fn(&str),
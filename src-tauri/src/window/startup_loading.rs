@@ -1,9 +1,9 @@
 use std::env;
 
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use url::Url;
 
-use crate::{startup_mode, BackendState};
+use crate::{active_backend_state, startup_mode, BackendState};
 
 pub fn should_apply_startup_loading_mode(webview_label: &str, page_url: &Url) -> bool {
     if webview_label != "main" {
@@ -27,13 +27,27 @@ pub fn apply_startup_loading_mode<F>(
     F: Fn(&str) + Copy,
 {
     let mode = resolve_startup_loading_mode(app_handle, startup_mode_env, log);
+    let state = active_backend_state(app_handle);
     let mode_js = serde_json::to_string(mode).expect("serializing startup mode");
+    let profile_js =
+        serde_json::to_string(&state.profile_id).expect("serializing active profile id");
     let script = format!(
-        "if (typeof window !== 'undefined' && typeof window.__astrbotSetStartupMode === 'function') {{ window.__astrbotSetStartupMode({mode_js}); }}"
+        "if (typeof window !== 'undefined' && typeof window.__astrbotSetStartupMode === 'function') {{ window.__astrbotSetStartupMode({mode_js}, {profile_js}); }}"
     );
     if let Err(error) = webview.eval(&script) {
         log(&format!("failed to apply startup loading mode: {error}"));
     }
+
+    let Some(progress) = state.last_startup_progress() else {
+        return;
+    };
+    let progress_js = serde_json::to_string(&progress).expect("serializing startup progress");
+    let script = format!(
+        "if (typeof window !== 'undefined' && typeof window.__astrbotSetStartupProgress === 'function') {{ window.__astrbotSetStartupProgress({progress_js}); }}"
+    );
+    if let Err(error) = webview.eval(&script) {
+        log(&format!("failed to replay startup progress: {error}"));
+    }
 }
 
 fn resolve_startup_loading_mode<F>(
@@ -44,7 +58,7 @@ fn resolve_startup_loading_mode<F>(
 where
     F: Fn(&str) + Copy,
 {
-    let state = app_handle.state::<BackendState>();
+    let state = active_backend_state(app_handle);
     match state.startup_loading_mode.lock() {
         Ok(guard) => {
             if let Some(mode) = *guard {
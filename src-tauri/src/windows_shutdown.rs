@@ -16,7 +16,9 @@ mod platform {
         },
     };
 
-    use crate::{append_shutdown_log, BackendState, SYSTEM_SHUTDOWN_STOP_TIMEOUT_MS};
+    use crate::{
+        append_shutdown_log, profiles::registry::ProfileRegistry, SYSTEM_SHUTDOWN_STOP_TIMEOUT_MS,
+    };
 
     const SHUTDOWN_PRIORITY_EARLY: u32 = 0x100;
 
@@ -119,14 +121,19 @@ mod platform {
         };
 
         append_shutdown_log("Windows shutdown requested, stopping backend quickly");
-        let state = app_handle.state::<BackendState>();
         // Avoid launching taskkill.exe during OS shutdown. Late in shutdown,
         // Windows can fail to initialize new console/system helper processes
         // with 0xc0000142, which is the issue this hook must prevent.
-        if let Err(error) = state.stop_backend_for_system_shutdown(Duration::from_millis(
-            SYSTEM_SHUTDOWN_STOP_TIMEOUT_MS,
-        )) {
-            append_shutdown_log(&format!("backend stop on Windows shutdown failed: {error}"));
+        for state in app_handle.state::<ProfileRegistry>().states() {
+            state.cancel_backend_startup("Windows shutdown");
+            if let Err(error) = state.stop_backend_for_system_shutdown(Duration::from_millis(
+                SYSTEM_SHUTDOWN_STOP_TIMEOUT_MS,
+            )) {
+                append_shutdown_log(&format!(
+                    "backend stop on Windows shutdown failed (profile {}): {error}",
+                    state.profile_id
+                ));
+            }
         }
     }
 
@@ -43,4 +43,58 @@ window.astrbot.startupShell = deepFreeze({
       },
     },
   },
+  // Keyed by the `stage` / `heartbeat` fields of the shell's startupProgress event.
+  STARTUP_PROGRESS_COPY: {
+    en: {
+      stages: {
+        launchPlanResolved: 'Launch plan ready, starting backend...',
+        spawned: 'Backend process started, loading modules...',
+        waitingForHttp: 'Waiting for the dashboard to respond...',
+        ready: 'Dashboard ready, opening...',
+      },
+      details: {
+        pid: 'PID {pid}',
+        tcpReachable: 'port open',
+        tcpPending: 'port not open yet',
+        http: 'HTTP {status}',
+        heartbeatFresh: 'backend active',
+        heartbeatStale: 'no heartbeat for {seconds}s',
+        heartbeatMissing: 'waiting for first heartbeat',
+        phaseProgress: '{percent}%',
+        warnings: '{count} warning(s)',
+        elapsed: '{elapsed}s elapsed',
+        elapsedWithTimeout: '{elapsed}s / {timeout}s',
+      },
+    },
+    zh: {
+      stages: {
+        launchPlanResolved: '启动计划已就绪，正在启动后端...',
+        spawned: '后端进程已启动，正在加载模块...',
+        waitingForHttp: '正在等待面板响应...',
+        ready: '面板已就绪，正在打开...',
+      },
+      details: {
+        pid: 'PID {pid}',
+        tcpReachable: '端口已开放',
+        tcpPending: '端口尚未开放',
+        http: 'HTTP {status}',
+        heartbeatFresh: '后端运行中',
+        heartbeatStale: '已 {seconds} 秒无心跳',
+        heartbeatMissing: '等待首次心跳',
+        phaseProgress: '{percent}%',
+        warnings: '{count} 条警告',
+        elapsed: '已用时 {elapsed} 秒',
+        elapsedWithTimeout: '{elapsed} / {timeout} 秒',
+      },
+    },
+  },
+  // Keyed by the `kind` of other shell events worth surfacing while the backend starts.
+  STARTUP_NOTICE_COPY: {
+    en: {
+      portRelocated: 'Dashboard port {from} was in use, switched to {to}.',
+    },
+    zh: {
+      portRelocated: '面板端口 {from} 已被占用，已改用 {to}。',
+    },
+  },
 });